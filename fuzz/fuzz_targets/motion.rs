@@ -0,0 +1,256 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+//! Coverage-guided fuzz target for `WordMotion`. Run with
+//! `cargo fuzz run motion -- -corpus=fuzz/corpus/motion`.
+//!
+//! The harness decodes an arbitrary byte slice into a `FuzzInput` (buffer
+//! lines, cursor, count and a motion opcode), invokes the matching
+//! `WordMotion` method, and asserts the invariants documented on
+//! [`check_invariants`]. Any violation aborts the process so that
+//! `cargo fuzz` minimizes and saves the triggering input under
+//! `fuzz/artifacts/motion/`.
+
+#![no_main]
+
+mod vim_model;
+
+use std::cell::RefCell;
+
+use arbitrary::{Arbitrary, Unstructured};
+use jieba_rs::Jieba;
+use jieba_vim_rs_core::motion::{BufferLike, WordMotion};
+use jieba_vim_rs_core::token::JiebaPlaceholder;
+use libfuzzer_sys::fuzz_target;
+
+/// One of the opcodes `WordMotion` exposes, mirroring the dispatch table in
+/// `pythonx/src/wrappers.rs`.
+#[derive(Debug, Arbitrary)]
+enum Opcode {
+    NmapW(bool),
+    XmapW(bool),
+    OmapW(bool),
+    NmapE(bool),
+    XmapE(bool),
+    OmapE(bool),
+    NmapB(bool),
+    XmapB(bool),
+    OmapB(bool),
+    NmapGe(bool),
+    XmapGe(bool),
+    OmapGe(bool),
+}
+
+#[derive(Debug, Arbitrary)]
+struct FuzzInput {
+    lines: Vec<String>,
+    lnum: usize,
+    col: usize,
+    count: u64,
+    opcode: Opcode,
+}
+
+/// A `RefCell`-guarded lazy jieba instance, mirroring
+/// `pythonx::wrappers::LazyJiebaWrapper` but without the pyo3 dependency so
+/// the fuzz crate stays editor-agnostic.
+struct LazyJieba(RefCell<Option<Jieba>>);
+
+impl JiebaPlaceholder for LazyJieba {
+    fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        self.0
+            .borrow_mut()
+            .get_or_insert_with(Jieba::new)
+            .cut(sentence, true)
+    }
+}
+
+struct FuzzBuffer(Vec<String>);
+
+impl BufferLike for FuzzBuffer {
+    type Error = std::convert::Infallible;
+
+    fn getline(&self, lnum: usize) -> Result<String, Self::Error> {
+        Ok(self.0[lnum - 1].clone())
+    }
+
+    fn lines(&self) -> Result<usize, Self::Error> {
+        Ok(self.0.len())
+    }
+}
+
+/// Assert the crate invariants the fuzz target is meant to uphold:
+/// `new_cursor_pos` stays within buffer bounds, forward motions never move
+/// lexicographically backwards and backward motions never move forwards.
+fn check_invariants(
+    before: (usize, usize),
+    after: (usize, usize),
+    lines: usize,
+    forward: bool,
+    backward: bool,
+) {
+    assert!(after.0 >= 1 && after.0 <= lines, "lnum out of bounds: {:?}", after);
+    if forward {
+        assert!(after >= before, "forward motion moved backwards: {:?} -> {:?}", before, after);
+    }
+    if backward {
+        assert!(after <= before, "backward motion moved forwards: {:?} -> {:?}", before, after);
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+    let Ok(input) = FuzzInput::arbitrary(&mut u) else {
+        return;
+    };
+    if input.lines.is_empty() {
+        return;
+    }
+    let lnum = 1 + input.lnum % input.lines.len();
+    let line_len = input.lines[lnum - 1].chars().count();
+    let col = if line_len == 0 { 0 } else { input.col % line_len };
+
+    let wm = WordMotion::new(LazyJieba(RefCell::new(None)));
+    let buffer = FuzzBuffer(input.lines.clone());
+    let cursor = (lnum, col);
+    let n_lines = buffer.0.len();
+
+    // Each arm invokes the matching motion and checks that it, and a
+    // double-count call starting from the same cursor, agree with the
+    // directionality the opcode implies. `count` scaling monotonicity is
+    // approximated by checking that count=2*n lands at-or-past count=n for
+    // forward motions (and at-or-before for backward ones).
+    macro_rules! check {
+        ($call:expr, $forward:expr, $backward:expr) => {{
+            if let Ok(out1) = $call(1) {
+                check_invariants(cursor, out1, n_lines, $forward, $backward);
+                if let Ok(out2) = $call(2) {
+                    check_invariants(cursor, out2, n_lines, $forward, $backward);
+                    if $forward {
+                        assert!(out2 >= out1);
+                    }
+                    if $backward {
+                        assert!(out2 <= out1);
+                    }
+                }
+            }
+        }};
+    }
+
+    // On ASCII-only buffers jieba cannot segment differently from Vim's own
+    // keyword classification, so the normal-mode motions must land on
+    // exactly the same cursor position as `vim_model`'s reference
+    // implementation. This turns "behaves like Vim when there's no Chinese
+    // text" into a fuzzable property instead of a hand-wavy claim.
+    let ascii_only = vim_model::is_ascii_only(&input.lines);
+    macro_rules! check_against_model {
+        ($model:path, $out:expr, $word:expr) => {{
+            if ascii_only {
+                if let Ok(out) = $out {
+                    assert_eq!(
+                        out,
+                        $model(&input.lines, cursor, input.count, $word),
+                        "diverged from Vim reference model"
+                    );
+                }
+            }
+        }};
+    }
+
+    match input.opcode {
+        Opcode::NmapW(word) => {
+            check_against_model!(
+                vim_model::nmap_w,
+                wm.nmap_w(&buffer, cursor, input.count, word),
+                word
+            );
+            check!(
+                |c| wm.nmap_w(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+                true,
+                false
+            )
+        }
+        Opcode::XmapW(word) => check!(
+            |c| wm.xmap_w(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+            true,
+            false
+        ),
+        Opcode::OmapW(word) => check!(
+            |c| wm.omap_w(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+            true,
+            false
+        ),
+        Opcode::NmapE(word) => {
+            check_against_model!(
+                vim_model::nmap_e,
+                wm.nmap_e(&buffer, cursor, input.count, word).map(|o| o.new_cursor_pos),
+                word
+            );
+            check!(
+                |c| wm.nmap_e(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+                true,
+                false
+            )
+        }
+        Opcode::XmapE(word) => check!(
+            |c| wm.xmap_e(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+            true,
+            false
+        ),
+        Opcode::OmapE(word) => check!(
+            |c| wm.omap_e(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+            true,
+            false
+        ),
+        Opcode::NmapB(word) => {
+            check_against_model!(
+                vim_model::nmap_b,
+                wm.nmap_b(&buffer, cursor, input.count, word),
+                word
+            );
+            check!(|c| wm.nmap_b(&buffer, cursor, c, word), false, true)
+        }
+        Opcode::XmapB(word) => check!(
+            |c| wm.xmap_b(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+            false,
+            true
+        ),
+        Opcode::OmapB(word) => check!(
+            |c| wm.omap_b(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+            false,
+            true
+        ),
+        Opcode::NmapGe(word) => {
+            check_against_model!(
+                vim_model::nmap_ge,
+                wm.nmap_ge(&buffer, cursor, input.count, word).map(|o| o.new_cursor_pos),
+                word
+            );
+            check!(
+                |c| wm.nmap_ge(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+                false,
+                true
+            )
+        }
+        Opcode::XmapGe(word) => check!(
+            |c| wm.xmap_ge(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+            false,
+            true
+        ),
+        Opcode::OmapGe(word) => check!(
+            |c| wm.omap_ge(&buffer, cursor, c, word).map(|o| o.new_cursor_pos),
+            false,
+            true
+        ),
+    }
+});
@@ -0,0 +1,174 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+//! A pure-Rust reference model of Vim's default-`iskeyword` word/WORD
+//! classification, used by the `motion` fuzz target as a differential oracle
+//! against [`jieba_vim_rs_core::motion::WordMotion`] on buffers that contain
+//! no CJK/segmentable text. Only the normal-mode motions (`w`/`W`, `e`/`E`,
+//! `b`/`B`, `ge`/`gE`) are modeled here: the operator-pending and visual-mode
+//! variants add inclusive/exclusive and `cw`-style special cases on top of
+//! the same cursor trajectory, so they are out of scope for this model.
+//!
+//! # Is the input eligible?
+//!
+//! [`is_ascii_only`] decides whether a buffer is simple enough for the
+//! oracle to apply: every character must be ASCII, since anything else may
+//! be segmented by jieba and no longer correspond to a single Vim
+//! "keyword"/"non-keyword" run.
+
+/// Vim's three-way character classification under the default `iskeyword`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Keyword,
+    Punct,
+    Space,
+}
+
+fn classify(c: char, word: bool) -> CharClass {
+    if c == ' ' || c == '\t' {
+        CharClass::Space
+    } else if !word {
+        CharClass::Punct
+    } else if c.is_ascii_alphanumeric() || c == '_' {
+        CharClass::Keyword
+    } else {
+        CharClass::Punct
+    }
+}
+
+/// Returns `true` if every character in every line is ASCII, which is the
+/// precondition under which jieba cannot segment the buffer differently
+/// from Vim's own keyword classification.
+pub fn is_ascii_only(lines: &[String]) -> bool {
+    lines.iter().all(|line| line.is_ascii())
+}
+
+/// A flattened position in the buffer: `(lnum, col, class)`, plus a marker
+/// for the synthetic token representing an empty line (which Vim's `w`/`b`
+/// treat as a one-character word, but `e`/`ge` skip over).
+#[derive(Debug, Clone, Copy)]
+struct Pos {
+    lnum: usize,
+    col: usize,
+    class: CharClass,
+    empty_line: bool,
+}
+
+fn flatten(lines: &[String], word: bool) -> Vec<Pos> {
+    let mut out = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let lnum = i + 1;
+        if line.is_empty() {
+            out.push(Pos {
+                lnum,
+                col: 0,
+                class: CharClass::Space,
+                empty_line: true,
+            });
+            continue;
+        }
+        for (col, c) in line.chars().enumerate() {
+            out.push(Pos {
+                lnum,
+                col,
+                class: classify(c, word),
+                empty_line: false,
+            });
+        }
+    }
+    out
+}
+
+fn index_of(flat: &[Pos], cursor: (usize, usize)) -> Option<usize> {
+    flat.iter()
+        .position(|p| (p.lnum, p.col) == cursor || (p.lnum == cursor.0 && p.empty_line))
+}
+
+/// Reference model for `w`/`W` in normal mode.
+pub fn nmap_w(lines: &[String], cursor: (usize, usize), mut count: u64, word: bool) -> (usize, usize) {
+    let flat = flatten(lines, word);
+    let Some(mut i) = index_of(&flat, cursor) else {
+        return cursor;
+    };
+    while count > 0 && i + 1 < flat.len() {
+        i += 1;
+        let on_boundary = flat[i].empty_line
+            || flat[i].class != CharClass::Space
+                && (i == 0 || flat[i - 1].class != flat[i].class || flat[i - 1].lnum != flat[i].lnum);
+        if on_boundary {
+            count -= 1;
+        }
+    }
+    (flat[i].lnum, flat[i].col)
+}
+
+/// Reference model for `e`/`E` in normal mode.
+pub fn nmap_e(lines: &[String], cursor: (usize, usize), mut count: u64, word: bool) -> (usize, usize) {
+    let flat: Vec<Pos> = flatten(lines, word).into_iter().filter(|p| !p.empty_line).collect();
+    if flat.is_empty() {
+        return cursor;
+    }
+    let mut i = flat
+        .iter()
+        .position(|p| (p.lnum, p.col) == cursor)
+        .unwrap_or(0);
+    while count > 0 && i + 1 < flat.len() {
+        i += 1;
+        let at_run_end = flat[i].class != CharClass::Space
+            && (i + 1 == flat.len() || flat[i + 1].class != flat[i].class || flat[i + 1].lnum != flat[i].lnum);
+        if at_run_end {
+            count -= 1;
+        }
+    }
+    (flat[i].lnum, flat[i].col)
+}
+
+/// Reference model for `b`/`B` in normal mode.
+pub fn nmap_b(lines: &[String], cursor: (usize, usize), mut count: u64, word: bool) -> (usize, usize) {
+    let flat = flatten(lines, word);
+    let Some(mut i) = index_of(&flat, cursor) else {
+        return cursor;
+    };
+    while count > 0 && i > 0 {
+        i -= 1;
+        let on_boundary = flat[i].empty_line
+            || flat[i].class != CharClass::Space
+                && (i == 0 || flat[i - 1].class != flat[i].class || flat[i - 1].lnum != flat[i].lnum);
+        if on_boundary {
+            count -= 1;
+        }
+    }
+    (flat[i].lnum, flat[i].col)
+}
+
+/// Reference model for `ge`/`gE` in normal mode.
+pub fn nmap_ge(lines: &[String], cursor: (usize, usize), mut count: u64, word: bool) -> (usize, usize) {
+    let flat: Vec<Pos> = flatten(lines, word).into_iter().filter(|p| !p.empty_line).collect();
+    if flat.is_empty() {
+        return cursor;
+    }
+    let mut i = flat
+        .iter()
+        .position(|p| (p.lnum, p.col) == cursor)
+        .unwrap_or(0);
+    while count > 0 && i > 0 {
+        i -= 1;
+        let at_run_end = flat[i].class != CharClass::Space
+            && (i + 1 == flat.len() || flat[i + 1].class != flat[i].class || flat[i + 1].lnum != flat[i].lnum);
+        if at_run_end {
+            count -= 1;
+        }
+    }
+    (flat[i].lnum, flat[i].col)
+}
@@ -0,0 +1,133 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use clap::{Parser, ValueEnum};
+
+/// Drive jieba.vim's segmentation-aware word motions from a shell or a
+/// non-Vim editor.
+#[derive(Debug, Parser)]
+#[command(name = "jieba-vim-motion", version)]
+pub struct Cli {
+    /// Which motion to run. `w`/`e`/`b`/`ge` select "word", `W`/`E`/`B`/`gE`
+    /// select "WORD" (see `:help word` and `:help WORD`).
+    #[arg(long, value_enum)]
+    pub motion: Motion,
+
+    /// Operator the motion is run under. Omit for normal-mode movement;
+    /// `c`/`d` select the operator-pending variants (`cw`, `dw`, ...).
+    #[arg(long, value_enum)]
+    pub operator: Option<Operator>,
+
+    /// Cursor position as `lnum,col`, 1-indexed line and 0-indexed column.
+    #[arg(long, default_value = "1,0")]
+    pub cursor: Cursor,
+
+    /// Number of times to repeat the motion.
+    #[arg(long, default_value_t = 1)]
+    pub count: u64,
+
+    /// Custom jieba dictionary path. Defaults to jieba's bundled dictionary.
+    #[arg(long)]
+    pub dict: Option<PathBuf>,
+
+    /// Disable jieba's HMM-based new-word discovery, so segmentation of
+    /// Hanzi runs not covered by the dictionary is strictly dictionary-driven.
+    #[arg(long)]
+    pub no_hmm: bool,
+
+    /// Load `--dict` through a read-only mmap instead of reading it into
+    /// memory up front, so the OS pages the file in lazily. Ignored if
+    /// `--dict` is not given; falls back to the regular load path if the
+    /// platform can't mmap the file.
+    #[arg(long)]
+    pub mmap_dict: bool,
+
+    /// Read the buffer from this file instead of stdin.
+    #[arg(long)]
+    pub file: Option<PathBuf>,
+
+    /// Emit the successive cursor stops instead of a single motion result,
+    /// reusing the same traversal as `preview::preview`.
+    #[arg(long)]
+    pub preview: bool,
+
+    /// Maximum number of stops to emit in `--preview` mode. `0` means "until
+    /// the motion leaves the current line".
+    #[arg(long, default_value_t = 0)]
+    pub limit: usize,
+
+    /// Print the result as JSON instead of plain text.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Motion {
+    W,
+    #[value(name = "W")]
+    UpperW,
+    E,
+    #[value(name = "E")]
+    UpperE,
+    B,
+    #[value(name = "B")]
+    UpperB,
+    Ge,
+    #[value(name = "gE")]
+    UpperGe,
+}
+
+impl Motion {
+    /// Whether this motion selects "word" (`true`) as opposed to "WORD"
+    /// (`false`), mirroring the `word` parameter threaded through
+    /// `WordMotion`.
+    pub fn is_word(self) -> bool {
+        !matches!(self, Motion::UpperW | Motion::UpperE | Motion::UpperB | Motion::UpperGe)
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Operator {
+    C,
+    D,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor {
+    pub lnum: usize,
+    pub col: usize,
+}
+
+impl FromStr for Cursor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (lnum, col) = s
+            .split_once(',')
+            .ok_or_else(|| format!("expected `lnum,col`, got `{}`", s))?;
+        Ok(Cursor {
+            lnum: lnum.parse().map_err(|_| format!("invalid lnum: `{}`", lnum))?,
+            col: col.parse().map_err(|_| format!("invalid col: `{}`", col))?,
+        })
+    }
+}
+
+impl From<Cursor> for (usize, usize) {
+    fn from(cursor: Cursor) -> Self {
+        (cursor.lnum, cursor.col)
+    }
+}
@@ -0,0 +1,133 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+//! Editor-agnostic CLI front end for [`jieba_vim_rs_core::motion::WordMotion`].
+//!
+//! This binary exposes the same motions as `WordMotionWrapper`
+//! (`pythonx/src/wrappers.rs`) without linking the PyO3 layer, so shell
+//! scripts and non-Vim hosts (Emacs, Kakoune, VS Code extensions) can drive
+//! jieba.vim's segmentation-aware motions over a stable process/JSON
+//! boundary.
+//!
+//! Run `jieba-vim-motion --help` for the full flag list.
+
+mod buffer;
+mod cli;
+mod motion;
+mod output;
+
+use std::fs;
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+use std::process::ExitCode;
+
+use clap::Parser;
+use jieba_rs::Jieba;
+use jieba_vim_rs_core::motion::WordMotion;
+
+use buffer::LineBuffer;
+use cli::Cli;
+use motion::JiebaWrapper;
+use output::Report;
+
+fn read_lines(file: Option<&Path>) -> io::Result<Vec<String>> {
+    let contents = match file {
+        Some(path) => fs::read_to_string(path)?,
+        None => {
+            let mut buf = String::new();
+            io::stdin().read_to_string(&mut buf)?;
+            buf
+        }
+    };
+    Ok(contents.lines().map(str::to_owned).collect())
+}
+
+/// Load `path` through a read-only `mmap`, letting the OS page the
+/// (potentially large) dictionary file in lazily instead of `read`ing it
+/// into an owned buffer up front. Falls back to the regular
+/// [`BufReader`]-backed path on any `mmap` failure (e.g. the platform
+/// doesn't support it, or `path` lives on a filesystem that rejects it),
+/// since the parsed `Jieba` is identical either way -- only how the bytes
+/// reached `Jieba::with_dict` differs.
+fn load_jieba_mmapped(path: &Path) -> Result<Jieba, String> {
+    let file = fs::File::open(path).map_err(|err| format!("cannot open dict: {}", err))?;
+    // Safe as long as nothing else truncates or writes to `path` while the
+    // mapping is alive, which holds for a dictionary file during a single
+    // short-lived CLI invocation.
+    match unsafe { memmap2::Mmap::map(&file) } {
+        Ok(mmap) => {
+            let mut reader = io::Cursor::new(&mmap[..]);
+            Jieba::with_dict(&mut reader).map_err(|err| format!("jieba error: {}", err))
+        }
+        Err(_) => load_jieba_buffered(path),
+    }
+}
+
+fn load_jieba_buffered(path: &Path) -> Result<Jieba, String> {
+    let mut reader =
+        BufReader::new(fs::File::open(path).map_err(|err| format!("cannot open dict: {}", err))?);
+    Jieba::with_dict(&mut reader).map_err(|err| format!("jieba error: {}", err))
+}
+
+fn load_jieba(dict: Option<&Path>, mmap_dict: bool) -> Result<Jieba, String> {
+    match dict {
+        None => Ok(Jieba::new()),
+        Some(path) if mmap_dict => load_jieba_mmapped(path),
+        Some(path) => load_jieba_buffered(path),
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let lines = match read_lines(cli.file.as_deref()) {
+        Ok(lines) => lines,
+        Err(err) => {
+            eprintln!("error: cannot read buffer: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let jieba = match load_jieba(cli.dict.as_deref(), cli.mmap_dict) {
+        Ok(jieba) => jieba,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let wm = WordMotion::new(JiebaWrapper {
+        jieba,
+        hmm: !cli.no_hmm,
+    });
+    let buffer = LineBuffer::new(lines);
+    let cursor = cli.cursor.into();
+
+    let report = if cli.preview {
+        motion::preview(&wm, &buffer, cursor, cli.motion, cli.operator, cli.limit)
+            .map(Report::Preview)
+    } else {
+        motion::run(&wm, &buffer, cursor, cli.count, cli.motion, cli.operator).map(Report::Motion)
+    };
+
+    match report {
+        Ok(report) => {
+            output::print(&report, cli.json);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
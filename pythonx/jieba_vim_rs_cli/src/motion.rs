@@ -0,0 +1,107 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use jieba_rs::Jieba;
+use jieba_vim_rs_core::motion::{MotionOutput, WordMotion};
+use jieba_vim_rs_core::token::JiebaPlaceholder;
+
+use crate::buffer::{BufferError, LineBuffer};
+use crate::cli::{Motion, Operator};
+
+/// `hmm` mirrors jieba-rs's own `Jieba::cut` flag: with it on, unknown runs
+/// of Hanzi fall back to the HMM-based new-word discovery model instead of
+/// only the dictionary; `--no-hmm` turns it off for users who want strictly
+/// dictionary-driven segmentation (e.g. to make a custom dictionary's word
+/// boundaries fully deterministic).
+pub struct JiebaWrapper {
+    pub jieba: Jieba,
+    pub hmm: bool,
+}
+
+impl JiebaPlaceholder for JiebaWrapper {
+    fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        self.jieba.cut(sentence, self.hmm)
+    }
+
+    fn cut_other<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        jieba_vim_rs_core::token::subword::split(sentence)
+    }
+}
+
+fn bare(new_cursor_pos: (usize, usize)) -> MotionOutput {
+    MotionOutput {
+        new_cursor_pos,
+        d_special: false,
+        prevent_change: false,
+    }
+}
+
+/// Run `motion` once (`count` times) from `cursor`, dispatching to the
+/// `nmap_*`/`omap_*` family the same way `WordMotionWrapper::omap_*` picks
+/// between the plain and operator-specific variant based on `operator`.
+pub fn run(
+    wm: &WordMotion<JiebaWrapper>,
+    buffer: &LineBuffer,
+    cursor: (usize, usize),
+    count: u64,
+    motion: Motion,
+    operator: Option<Operator>,
+) -> Result<MotionOutput, BufferError> {
+    let word = motion.is_word();
+    match (motion, operator) {
+        (Motion::W | Motion::UpperW, None) => wm.nmap_w(buffer, cursor, count, word).map(bare),
+        (Motion::W | Motion::UpperW, Some(Operator::C)) => {
+            wm.omap_c_w(buffer, cursor, count, word)
+        }
+        (Motion::W | Motion::UpperW, Some(Operator::D)) => wm.omap_w(buffer, cursor, count, word),
+        (Motion::E | Motion::UpperE, None) => wm.nmap_e(buffer, cursor, count, word),
+        (Motion::E | Motion::UpperE, Some(_)) => wm.omap_e(buffer, cursor, count, word),
+        (Motion::B | Motion::UpperB, None) => wm.nmap_b(buffer, cursor, count, word).map(bare),
+        (Motion::B | Motion::UpperB, Some(_)) => wm.omap_b(buffer, cursor, count, word),
+        (Motion::Ge | Motion::UpperGe, None) => {
+            wm.nmap_ge(buffer, cursor, count, word).map(bare)
+        }
+        (Motion::Ge | Motion::UpperGe, Some(_)) => wm.omap_ge(buffer, cursor, count, word),
+    }
+}
+
+/// Successive one-step cursor stops, reusing the same traversal
+/// `preview::preview` (`pythonx/src/preview.rs`) uses to highlight upcoming
+/// motion targets. `limit == 0` stops as soon as the motion would cross a
+/// line boundary, matching `preview_limit == 0` there.
+pub fn preview(
+    wm: &WordMotion<JiebaWrapper>,
+    buffer: &LineBuffer,
+    mut cursor: (usize, usize),
+    motion: Motion,
+    operator: Option<Operator>,
+    limit: usize,
+) -> Result<Vec<(usize, usize)>, BufferError> {
+    let mut positions = vec![];
+    loop {
+        if limit != 0 && positions.len() >= limit {
+            break;
+        }
+        let next = run(wm, buffer, cursor, 1, motion, operator)?.new_cursor_pos;
+        if next == cursor {
+            break;
+        }
+        if limit == 0 && next.0 != cursor.0 {
+            break;
+        }
+        positions.push(next);
+        cursor = next;
+    }
+    Ok(positions)
+}
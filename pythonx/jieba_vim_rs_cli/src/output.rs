@@ -0,0 +1,65 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use jieba_vim_rs_core::motion::MotionOutput;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct MotionJson {
+    cursor: (usize, usize),
+    d_special: bool,
+    prevent_change: bool,
+}
+
+impl From<&MotionOutput> for MotionJson {
+    fn from(out: &MotionOutput) -> Self {
+        Self {
+            cursor: out.new_cursor_pos,
+            d_special: out.d_special,
+            prevent_change: out.prevent_change,
+        }
+    }
+}
+
+pub enum Report {
+    Motion(MotionOutput),
+    Preview(Vec<(usize, usize)>),
+}
+
+pub fn print(report: &Report, json: bool) {
+    match report {
+        Report::Motion(out) => {
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string(&MotionJson::from(out)).unwrap()
+                );
+            } else {
+                println!(
+                    "{},{} d_special={} prevent_change={}",
+                    out.new_cursor_pos.0, out.new_cursor_pos.1, out.d_special, out.prevent_change
+                );
+            }
+        }
+        Report::Preview(positions) => {
+            if json {
+                println!("{}", serde_json::to_string(positions).unwrap());
+            } else {
+                for (lnum, col) in positions {
+                    println!("{},{}", lnum, col);
+                }
+            }
+        }
+    }
+}
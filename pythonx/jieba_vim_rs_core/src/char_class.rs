@@ -0,0 +1,718 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
+
+use crate::token::Script;
+
+/// The coarse class a character falls into for word-boundary purposes,
+/// independent of [`categorize_char`](crate::token)'s CJK-specific LeftPunc /
+/// RightPunc / IsolatedPunc distinctions (those only matter for where jieba
+/// inserts implicit spaces around CJK punctuation).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharClass {
+    /// Starts or continues a word, e.g. letters, CJK ideographs, digits, `_`.
+    Word,
+    /// Anything else that isn't whitespace.
+    Punct,
+    /// Unicode `White_Space`.
+    Blank,
+}
+
+/// The full-granularity category [`categorize_char`](crate::token) assigns
+/// internally -- finer than [`CharClass`] since it also distinguishes 汉字
+/// and dictionary-backed scripts from other word chars, and splits
+/// non-word chars into the CJK Left/Right/Isolated punctuation kinds that
+/// decide where jieba inserts implicit whitespace. Returned by a
+/// [`CharClassifier::with_category_hook`] callback to reclassify specific
+/// characters -- even ones the hardcoded CJK tables would otherwise claim
+/// -- at runtime, e.g. from an embedder's Lua or Python config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharCategory {
+    Space,
+    WordHanzi,
+    WordDictionary(Script),
+    WordHiragana,
+    WordKatakana,
+    WordHangul,
+    WordOther,
+    NonWordLeftPunc,
+    NonWordRightPunc,
+    NonWordIsolatedPunc,
+    NonWordOther,
+}
+
+/// Classifies chars into [`CharClass`]es, approximating the UAX #31
+/// identifier rules: a char is `Word` if it would start (`XID_start`) or
+/// continue (`XID_continue`) a Unicode identifier, or if it's a combining
+/// mark that should attach to the preceding base character rather than
+/// stand on its own. Everything `White_Space` is `Blank`, and everything
+/// else is `Punct`.
+///
+/// Constructed once and consulted by every `nmap_*`/`omap_*`/`xmap_*`
+/// motion through [`JiebaPlaceholder::classifier`](crate::token::JiebaPlaceholder::classifier).
+#[derive(Clone, Default)]
+pub struct CharClassifier {
+    /// Ranges that override the default classification below, later entries
+    /// taking priority over earlier ones when ranges overlap.
+    overrides: Vec<(RangeInclusive<u32>, CharClass)>,
+    /// Runs before every other rule in
+    /// [`categorize_char`](crate::token), including the hardcoded CJK/
+    /// punctuation tables, letting an embedder reclassify specific
+    /// characters at runtime without recompiling. See
+    /// [`Self::with_category_hook`].
+    category_hook: Option<Rc<dyn Fn(char) -> Option<CharCategory>>>,
+}
+
+impl fmt::Debug for CharClassifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CharClassifier")
+            .field("overrides", &self.overrides)
+            .field("category_hook", &self.category_hook.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+impl CharClassifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Force every char in `range` to classify as `class`, taking priority
+    /// over both the built-in rules and any override added before this one.
+    pub fn with_override(
+        mut self,
+        range: RangeInclusive<char>,
+        class: CharClass,
+    ) -> Self {
+        self.overrides
+            .push((*range.start() as u32..=*range.end() as u32, class));
+        self
+    }
+
+    /// Run `hook` before every other classification rule -- including the
+    /// hardcoded CJK/punctuation tables in `categorize_char` and
+    /// [`Self::with_override`] ranges -- letting an embedder (e.g. a Neovim
+    /// Lua callback wired up through `mlua`, or this plugin's Python config)
+    /// reclassify specific characters at runtime without recompiling: treat
+    /// `·` as a word char, move a bracket between Left/Right/Isolated
+    /// punctuation, add private-use ideographs to the Hanzi set, etc.
+    /// `hook` returning `None` for a char falls through to the normal
+    /// rules. Replaces any hook installed by an earlier call.
+    pub fn with_category_hook(
+        mut self,
+        hook: impl Fn(char) -> Option<CharCategory> + 'static,
+    ) -> Self {
+        self.category_hook = Some(Rc::new(hook));
+        self
+    }
+
+    /// Classify `c`, consulting overrides before falling back to the default
+    /// UAX #31-derived rule.
+    pub fn classify(&self, c: char) -> CharClass {
+        let cp = c as u32;
+        for (range, class) in self.overrides.iter().rev() {
+            if range.contains(&cp) {
+                return *class;
+            }
+        }
+        default_classify(c)
+    }
+
+    /// Consults the hook installed by [`Self::with_category_hook`], if any.
+    pub(crate) fn category_override(&self, c: char) -> Option<CharCategory> {
+        self.category_hook.as_ref().and_then(|hook| hook(c))
+    }
+
+    /// Whether `c` is a combining mark that should attach to whichever
+    /// token/group precedes it instead of starting one of its own, so that
+    /// `w`/`e`/`b` never stop in the middle of a grapheme. Unaffected by
+    /// overrides: a combining mark forced into `Word` or `Punct` would still
+    /// split graphemes apart.
+    pub fn is_combining(&self, c: char) -> bool {
+        is_combining_mark(c)
+    }
+}
+
+/// Runtime-configurable word-char set for the ASCII/Latin-1 portion of
+/// [`categorize_char`](crate::token)'s table, mirroring Vim's buffer-local
+/// `'iskeyword'` option: the plugin otherwise hardcodes Vim's *default*
+/// `'iskeyword'` (`a-z`, `A-Z`, `0-9`, `_`, `\u{c0}`-`\u{ff}`), so a buffer
+/// that changes it (e.g. adding `-` or removing `_`) would otherwise get
+/// `w`/`b`/`e` boundaries that disagree with Vim's own. Parse a real
+/// `'iskeyword'` string with [`Self::parse_iskeyword`] and pass the result
+/// through [`crate::token::JiebaPlaceholder::char_class_config`]. Kept
+/// separate from [`CharClassifier`] -- which an embedder configures once at
+/// startup via Rust closures -- since `'iskeyword'` is per-buffer Vim state
+/// with its own comma-separated range/negation string syntax.
+#[derive(Debug, Clone)]
+pub struct CharClassConfig {
+    /// `(range, include)` pairs in parse order; [`Self::is_word_char`]
+    /// checks them from the end, so a later item overrides an earlier one
+    /// where they overlap, the same way a later `'iskeyword'` item wins in
+    /// real Vim.
+    word_chars: Vec<(RangeInclusive<u32>, bool)>,
+    /// Whether [`Self::is_unicode_whitespace`] recognizes NBSP and the rest
+    /// of the Unicode blank codepoints beyond Vim's own hardcoded ASCII/CJK
+    /// whitespace table, via [`Self::with_unicode_whitespace`]. Off by
+    /// default, since Vim itself doesn't treat e.g. NBSP as blank.
+    unicode_whitespace: bool,
+    /// Whether [`categorize_char`](crate::token) classifies with
+    /// `general_category_classify` (`General_Category`-derived) instead of
+    /// its own hardcoded table, via
+    /// [`Self::with_general_category_classification`]. Off by default, so
+    /// existing callers keep the table-driven classification they've always
+    /// gotten.
+    general_category_mode: bool,
+}
+
+impl Default for CharClassConfig {
+    /// Vim's default `'iskeyword'` (`@,48-57,_,192-255` in Vim's own
+    /// syntax): ASCII letters, digits, underscore, and the Latin-1
+    /// supplement letters. `unicode_whitespace` defaults to `false`.
+    fn default() -> Self {
+        Self {
+            word_chars: vec![
+                (b'a' as u32..=b'z' as u32, true),
+                (b'A' as u32..=b'Z' as u32, true),
+                (b'0' as u32..=b'9' as u32, true),
+                (b'_' as u32..=b'_' as u32, true),
+                (0xc0..=0xff, true),
+            ],
+            unicode_whitespace: false,
+            general_category_mode: false,
+        }
+    }
+}
+
+/// A malformed item in an `'iskeyword'`-syntax string passed to
+/// [`CharClassConfig::parse_iskeyword`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIskeywordError(String);
+
+impl fmt::Display for ParseIskeywordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid 'iskeyword' item: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseIskeywordError {}
+
+/// A single `'iskeyword'` item's character code, either a decimal number or
+/// a literal single character.
+fn parse_iskeyword_endpoint(s: &str) -> Option<u32> {
+    if let Ok(n) = s.parse::<u32>() {
+        return Some(n);
+    }
+    let mut chars = s.chars();
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c as u32)
+}
+
+/// A single comma-separated `'iskeyword'` item (with any `^` prefix already
+/// stripped) into the codepoint range it denotes: `c1-c2` for a range of
+/// either decimal codes or literal characters, or a single code/character
+/// on its own. The literal item `"-"` is the dash character itself, not an
+/// (invalid, empty-ended) range.
+fn parse_iskeyword_item(item: &str) -> Option<RangeInclusive<u32>> {
+    if item != "-" {
+        if let Some((start, end)) = item.split_once('-') {
+            let start = parse_iskeyword_endpoint(start)?;
+            let end = parse_iskeyword_endpoint(end)?;
+            return Some(start..=end);
+        }
+    }
+    let v = parse_iskeyword_endpoint(item)?;
+    Some(v..=v)
+}
+
+impl CharClassConfig {
+    /// Parse a Vim `'iskeyword'`-syntax string: comma-separated items, each
+    /// a decimal character code, a single literal character, or an `a-z`-
+    /// style range of either, optionally prefixed with `^` to remove that
+    /// codepoint (range) from the set instead of adding it -- e.g.
+    /// `"@,48-57,_,192-255,-,^_"`. `@` on its own adds the ASCII letters;
+    /// written as one side of a range (e.g. `@-@`) it's just the literal
+    /// `@` character, same as any other single-char range endpoint. Vim's
+    /// other character-class keywords beyond `@` aren't supported; an
+    /// unrecognized item is rejected rather than silently dropped, so a
+    /// caller finds out immediately instead of getting silently wrong word
+    /// boundaries.
+    pub fn parse_iskeyword(spec: &str) -> Result<Self, ParseIskeywordError> {
+        let mut word_chars = Vec::new();
+        for item in spec.split(',').filter(|s| !s.is_empty()) {
+            let (item, include) = match item.strip_prefix('^') {
+                Some(rest) => (rest, false),
+                None => (item, true),
+            };
+            if item == "@" {
+                word_chars.push((b'a' as u32..=b'z' as u32, include));
+                word_chars.push((b'A' as u32..=b'Z' as u32, include));
+                continue;
+            }
+            let range = parse_iskeyword_item(item)
+                .ok_or_else(|| ParseIskeywordError(item.to_string()))?;
+            word_chars.push((range, include));
+        }
+        Ok(Self {
+            word_chars,
+            ..Self::default()
+        })
+    }
+
+    /// Whether `c` is in the word-char set this config describes.
+    pub fn is_word_char(&self, c: char) -> bool {
+        let cp = c as u32;
+        self.word_chars
+            .iter()
+            .rev()
+            .find(|(range, _)| range.contains(&cp))
+            .map(|(_, include)| *include)
+            .unwrap_or(false)
+    }
+
+    /// Enable recognizing NBSP and the rest of the Unicode blank codepoints
+    /// as whitespace (see [`Self::is_unicode_whitespace`]), beyond the ASCII
+    /// space/tab and CJK ideographic space/fill [`categorize_char`]
+    /// otherwise hardcodes. Off by default since Vim itself does not treat
+    /// e.g. NBSP as whitespace.
+    ///
+    /// [`categorize_char`]: crate::token
+    pub fn with_unicode_whitespace(mut self, enabled: bool) -> Self {
+        self.unicode_whitespace = enabled;
+        self
+    }
+
+    /// Whether `c` is one of the additional Unicode blank codepoints this
+    /// config recognizes when [`Self::with_unicode_whitespace`] is enabled:
+    /// NBSP, ogham space, the en/em quad block, narrow/medium
+    /// mathematical spaces, the Mongolian vowel separator, and the
+    /// zero-width no-break space. Always `false` when that flag is off.
+    pub fn is_unicode_whitespace(&self, c: char) -> bool {
+        self.unicode_whitespace
+            && matches!(
+                c as u32,
+                0x00a0 // No-break space
+                | 0x1680 // Ogham space mark
+                | 0x2000..=0x200a // En quad .. hair space
+                | 0x180e // Mongolian vowel separator
+                | 0x202f // Narrow no-break space
+                | 0x205f // Medium mathematical space
+                | 0xfeff // Zero-width no-break space
+            )
+    }
+
+    /// Select the `General_Category`-derived classifier
+    /// (`general_category_classify`) in place of
+    /// [`categorize_char`](crate::token)'s hardcoded table. Off by default.
+    /// The curated CJK/fullwidth punctuation exception table is still
+    /// applied on top either way -- see
+    /// `curated_cjk_punctuation` in `crate::token`.
+    pub fn with_general_category_classification(mut self, enabled: bool) -> Self {
+        self.general_category_mode = enabled;
+        self
+    }
+
+    /// Whether [`Self::with_general_category_classification`] is enabled.
+    pub fn general_category_mode(&self) -> bool {
+        self.general_category_mode
+    }
+}
+
+fn default_classify(c: char) -> CharClass {
+    if c.is_whitespace() {
+        CharClass::Blank
+    } else if c.is_alphanumeric() || c == '_' || is_combining_mark(c) {
+        CharClass::Word
+    } else {
+        CharClass::Punct
+    }
+}
+
+/// Approximates the Unicode `Mn`/`Mc`/`Me` (combining mark) general
+/// categories by listing the blocks that are, in practice, almost entirely
+/// made of combining marks.
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0300..=0x036f // Combining Diacritical Marks
+        | 0x1ab0..=0x1aff // Combining Diacritical Marks Extended
+        | 0x1dc0..=0x1dff // Combining Diacritical Marks Supplement
+        | 0x20d0..=0x20ff // Combining Diacritical Marks for Symbols
+        | 0xfe20..=0xfe2f // Combining Half Marks
+    )
+}
+
+/// Approximates Unicode East Asian Width's Wide (W) and Fullwidth (F)
+/// classes by listing the blocks that account for nearly all double-width
+/// rendering in practice: CJK ideographs and their punctuation, Hiragana/
+/// Katakana, Hangul syllables and jamo, and the fullwidth ASCII forms.
+fn is_east_asian_wide(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x115f // Hangul Jamo
+        | 0x2e80..=0x303e // CJK Radicals, Kangxi Radicals, CJK Symbols and Punctuation
+        | 0x3041..=0x33ff // Hiragana, Katakana, Bopomofo, Hangul Compat Jamo, CJK strokes/enclosed
+        | 0x3400..=0x4dbf // CJK Unified Ideographs Extension A
+        | 0x4e00..=0x9fff // CJK Unified Ideographs
+        | 0xa000..=0xa4cf // Yi Syllables and Radicals
+        | 0xac00..=0xd7a3 // Hangul Syllables
+        | 0xf900..=0xfaff // CJK Compatibility Ideographs
+        | 0xfe30..=0xfe4f // CJK Compatibility Forms
+        | 0xff00..=0xff60 // Fullwidth Forms
+        | 0xffe0..=0xffe6 // Fullwidth Signs
+        | 0x20000..=0x3fffd // CJK Unified Ideographs Extension B and beyond, CJK Compatibility Supplement
+    )
+}
+
+/// Approximates Unicode East Asian Width's Ambiguous (A) class by listing
+/// the blocks that account for nearly all of it in practice: Latin-1
+/// punctuation/symbols, Greek, Cyrillic, and box-drawing/block-element
+/// symbols. Whether these render as one cell or two depends on the
+/// terminal/font, which is exactly what Vim's `'ambiwidth'` option -- and
+/// [`WidthConfig::ambiguous_wide`] here -- decide instead of guessing.
+fn is_east_asian_ambiguous(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x00a1..=0x00ff // Latin-1 Supplement punctuation/symbols
+        | 0x0391..=0x03c9 // Greek and Coptic
+        | 0x0401..=0x045f // Cyrillic
+        | 0x2010..=0x2027 // General Punctuation (dashes, quotes, etc.)
+        | 0x2030..=0x205e
+        | 0x2190..=0x2211 // Arrows, mathematical operators
+        | 0x2460..=0x24ff // Enclosed Alphanumerics
+        | 0x2500..=0x25ff // Box Drawing, Block Elements, Geometric Shapes
+    )
+}
+
+/// How wide Vim's `'ambiwidth'` option renders East Asian Width's Ambiguous
+/// class, since (unlike Wide/Fullwidth) that's a terminal/font choice, not
+/// something Unicode itself settles. Threaded through every display-column
+/// conversion in this module; carried by
+/// [`WordMotion`](crate::motion::WordMotion) so an embedder only has to set
+/// it once to match the user's actual `'ambiwidth'`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WidthConfig {
+    /// `true` matches `'ambiwidth'=double`: ambiguous-width chars occupy two
+    /// cells. `false` (Vim's own default) matches `'ambiwidth'=single`: one
+    /// cell.
+    pub ambiguous_wide: bool,
+}
+
+impl Default for WidthConfig {
+    fn default() -> Self {
+        Self {
+            ambiguous_wide: false,
+        }
+    }
+}
+
+/// The screen-cell width `c` occupies, without tab expansion: a combining
+/// mark occupies no cell of its own, an East-Asian wide/fullwidth codepoint
+/// occupies two, an East-Asian ambiguous-width codepoint occupies one or two
+/// per `config.ambiguous_wide`, and everything else occupies one.
+pub fn char_display_width(c: char, config: &WidthConfig) -> usize {
+    if is_combining_mark(c) {
+        0
+    } else if is_east_asian_wide(c) {
+        2
+    } else if config.ambiguous_wide && is_east_asian_ambiguous(c) {
+        2
+    } else {
+        1
+    }
+}
+
+/// The virtual display column of byte offset `byte_col` in `line`: the sum
+/// of [`char_display_width`] over every char strictly before it. Tracks the
+/// display column separately from the byte column a motion already
+/// reports, the same separation a text-reader implementation draws between
+/// a char position and its rendered column. `byte_col == 0` -- including on
+/// an empty line -- always maps to display column `0`.
+pub fn display_col(line: &str, byte_col: usize, config: &WidthConfig) -> usize {
+    line.char_indices()
+        .take_while(|&(i, _)| i < byte_col)
+        .map(|(_, c)| char_display_width(c, config))
+        .sum()
+}
+
+/// The inverse of [`display_col`]: the byte offset of the char occupying
+/// display column `col` in `line`. A `col` that lands in the middle of a
+/// wide char (e.g. column 1 of a leading 汉字) rounds down to that char's
+/// own starting byte, the same way Vim itself snaps the cursor to the start
+/// of the wide cell it's visually inside of. A `col` at or past the line's
+/// total display width returns `line.len()`.
+pub fn byte_col_from_display_col(
+    line: &str,
+    col: usize,
+    config: &WidthConfig,
+) -> usize {
+    let mut display = 0;
+    for (i, c) in line.char_indices() {
+        let width = char_display_width(c, config);
+        if col < display + width.max(1) {
+            return i;
+        }
+        display += width;
+    }
+    line.len()
+}
+
+/// The char index (0-indexed, counted in Unicode scalars) of byte offset
+/// `byte_col` in `line`. Companion to [`char_index_to_byte_col`] and
+/// [`display_col`] for callers that need to move between all three
+/// coordinate systems a Vim cursor position can be expressed in.
+pub fn byte_col_to_char_index(line: &str, byte_col: usize) -> usize {
+    line[..byte_col.min(line.len())].chars().count()
+}
+
+/// The inverse of [`byte_col_to_char_index`]: the byte offset of char index
+/// `char_index` in `line`. A `char_index` at or past the line's char count
+/// returns `line.len()`.
+pub fn char_index_to_byte_col(line: &str, char_index: usize) -> usize {
+    line.char_indices()
+        .nth(char_index)
+        .map(|(i, _)| i)
+        .unwrap_or(line.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_classify() {
+        assert_eq!(default_classify(' '), CharClass::Blank);
+        assert_eq!(default_classify('a'), CharClass::Word);
+        assert_eq!(default_classify('漢'), CharClass::Word);
+        assert_eq!(default_classify('_'), CharClass::Word);
+        assert_eq!(default_classify('!'), CharClass::Punct);
+        assert_eq!(default_classify('\u{0301}'), CharClass::Word);
+    }
+
+    /// `is_alphanumeric` already covers every Unicode Letter (Lu/Ll/Lt/Lm/Lo)
+    /// and Number (Nd/Nl/No) category, not just ASCII and Latin-1, so
+    /// scripts without their own range in `categorize_char` still classify
+    /// as `Word` instead of falling through to `Punct`.
+    #[test]
+    fn test_default_classify_covers_non_latin_scripts() {
+        assert_eq!(default_classify('я'), CharClass::Word); // Cyrillic
+        assert_eq!(default_classify('Ω'), CharClass::Word); // Greek
+        assert_eq!(default_classify('ệ'), CharClass::Word); // Vietnamese
+        assert_eq!(default_classify('क'), CharClass::Word); // Devanagari
+        assert_eq!(default_classify('Ⅷ'), CharClass::Word); // Roman numeral (Nl)
+        assert_eq!(default_classify('①'), CharClass::Word); // Circled digit (No)
+    }
+
+    #[test]
+    fn test_override_takes_priority() {
+        let classifier =
+            CharClassifier::new().with_override('!'..='!', CharClass::Word);
+        assert_eq!(classifier.classify('!'), CharClass::Word);
+        assert_eq!(classifier.classify('?'), CharClass::Punct);
+    }
+
+    #[test]
+    fn test_is_combining_ignores_overrides() {
+        let classifier = CharClassifier::new()
+            .with_override('\u{0301}'..='\u{0301}', CharClass::Punct);
+        assert_eq!(classifier.classify('\u{0301}'), CharClass::Punct);
+        assert!(classifier.is_combining('\u{0301}'));
+    }
+
+    #[test]
+    fn test_category_hook_overrides_specific_chars() {
+        let classifier = CharClassifier::new().with_category_hook(|c| match c {
+            '·' => Some(CharCategory::WordOther),
+            _ => None,
+        });
+        assert_eq!(
+            classifier.category_override('·'),
+            Some(CharCategory::WordOther)
+        );
+        assert_eq!(classifier.category_override('a'), None);
+    }
+
+    #[test]
+    fn test_category_hook_replaced_by_later_call() {
+        let classifier = CharClassifier::new()
+            .with_category_hook(|_| Some(CharCategory::WordOther))
+            .with_category_hook(|_| Some(CharCategory::NonWordOther));
+        assert_eq!(
+            classifier.category_override('x'),
+            Some(CharCategory::NonWordOther)
+        );
+    }
+
+    #[test]
+    fn test_char_display_width() {
+        let config = WidthConfig::default();
+        assert_eq!(char_display_width('a', &config), 1);
+        assert_eq!(char_display_width('中', &config), 2);
+        assert_eq!(char_display_width('\u{0301}', &config), 0);
+    }
+
+    #[test]
+    fn test_char_display_width_ambiguous_follows_config() {
+        // Greek "α" is East Asian Width Ambiguous: one cell under Vim's
+        // default `'ambiwidth'=single`, two under `=double`.
+        assert_eq!(
+            char_display_width('α', &WidthConfig { ambiguous_wide: false }),
+            1
+        );
+        assert_eq!(
+            char_display_width('α', &WidthConfig { ambiguous_wide: true }),
+            2
+        );
+    }
+
+    #[test]
+    fn test_display_col_at_line_start_is_zero() {
+        let config = WidthConfig::default();
+        assert_eq!(display_col("", 0, &config), 0);
+        assert_eq!(display_col("中文", 0, &config), 0);
+    }
+
+    #[test]
+    fn test_display_col_counts_wide_chars_as_two_cells() {
+        // "中文ab": byte offset 6 is right after the 2 CJK chars (3 bytes
+        // each), so it should report 4 display cells (2 + 2), not 2 bytes'
+        // worth of chars.
+        let config = WidthConfig::default();
+        assert_eq!(display_col("中文ab", 6, &config), 4);
+        assert_eq!(display_col("中文ab", 7, &config), 5);
+    }
+
+    #[test]
+    fn test_byte_col_from_display_col_round_trips() {
+        let config = WidthConfig::default();
+        let line = "中文ab";
+        for byte_col in [0, 3, 6, 7, 8] {
+            let col = display_col(line, byte_col, &config);
+            assert_eq!(byte_col_from_display_col(line, col, &config), byte_col);
+        }
+    }
+
+    #[test]
+    fn test_byte_col_from_display_col_snaps_into_wide_char() {
+        // Display column 1 lands in the middle of the leading 汉字 (which
+        // occupies columns 0 and 1); it should snap back to the char's own
+        // starting byte 0, not round up to the next char.
+        let config = WidthConfig::default();
+        assert_eq!(byte_col_from_display_col("中文", 1, &config), 0);
+        assert_eq!(byte_col_from_display_col("中文", 2, &config), 3);
+    }
+
+    #[test]
+    fn test_byte_col_from_display_col_past_end_clamps_to_line_len() {
+        let config = WidthConfig::default();
+        assert_eq!(byte_col_from_display_col("ab", 10, &config), 2);
+    }
+
+    #[test]
+    fn test_byte_col_char_index_round_trip() {
+        let line = "中文ab";
+        for byte_col in [0, 3, 6, 7, 8] {
+            let idx = byte_col_to_char_index(line, byte_col);
+            assert_eq!(char_index_to_byte_col(line, idx), byte_col);
+        }
+    }
+
+    #[test]
+    fn test_char_index_to_byte_col_past_end_clamps_to_line_len() {
+        assert_eq!(char_index_to_byte_col("ab", 10), 2);
+    }
+
+    #[test]
+    fn test_char_class_config_default_matches_vim_default_iskeyword() {
+        let config = CharClassConfig::default();
+        assert!(config.is_word_char('a'));
+        assert!(config.is_word_char('Z'));
+        assert!(config.is_word_char('5'));
+        assert!(config.is_word_char('_'));
+        assert!(config.is_word_char('\u{c0}'));
+        assert!(config.is_word_char('\u{ff}'));
+        assert!(!config.is_word_char('-'));
+        assert!(!config.is_word_char(' '));
+    }
+
+    #[test]
+    fn test_parse_iskeyword_numeric_and_char_ranges() {
+        let config = CharClassConfig::parse_iskeyword("48-57,a-z,_").unwrap();
+        assert!(config.is_word_char('5'));
+        assert!(config.is_word_char('m'));
+        assert!(config.is_word_char('_'));
+        assert!(!config.is_word_char('A'));
+    }
+
+    #[test]
+    fn test_parse_iskeyword_negation_removes_earlier_item() {
+        let config = CharClassConfig::parse_iskeyword("a-z,^x").unwrap();
+        assert!(config.is_word_char('a'));
+        assert!(!config.is_word_char('x'));
+    }
+
+    #[test]
+    fn test_parse_iskeyword_at_adds_ascii_letters() {
+        let config = CharClassConfig::parse_iskeyword("@").unwrap();
+        assert!(config.is_word_char('a'));
+        assert!(config.is_word_char('Z'));
+        assert!(!config.is_word_char('5'));
+    }
+
+    #[test]
+    fn test_parse_iskeyword_literal_dash_item() {
+        let config = CharClassConfig::parse_iskeyword("-").unwrap();
+        assert!(config.is_word_char('-'));
+    }
+
+    #[test]
+    fn test_parse_iskeyword_rejects_unsupported_item() {
+        assert!(CharClassConfig::parse_iskeyword("##").is_err());
+    }
+
+    #[test]
+    fn test_unicode_whitespace_off_by_default() {
+        let config = CharClassConfig::default();
+        assert!(!config.is_unicode_whitespace('\u{00a0}'));
+    }
+
+    #[test]
+    fn test_unicode_whitespace_recognizes_extra_blanks() {
+        let config = CharClassConfig::default().with_unicode_whitespace(true);
+        assert!(config.is_unicode_whitespace('\u{00a0}')); // NBSP
+        assert!(config.is_unicode_whitespace('\u{1680}')); // Ogham space mark
+        assert!(config.is_unicode_whitespace('\u{2003}')); // Em space
+        assert!(config.is_unicode_whitespace('\u{180e}')); // Mongolian vowel separator
+        assert!(config.is_unicode_whitespace('\u{202f}')); // Narrow no-break space
+        assert!(config.is_unicode_whitespace('\u{205f}')); // Medium mathematical space
+        assert!(config.is_unicode_whitespace('\u{feff}')); // Zero-width no-break space
+        assert!(!config.is_unicode_whitespace('a'));
+    }
+
+    #[test]
+    fn test_general_category_mode_off_by_default() {
+        let config = CharClassConfig::default();
+        assert!(!config.general_category_mode());
+    }
+
+    #[test]
+    fn test_general_category_mode_enabled_by_builder() {
+        let config =
+            CharClassConfig::default().with_general_category_classification(true);
+        assert!(config.general_category_mode());
+    }
+}
@@ -0,0 +1,174 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use encoding_rs::Encoding;
+
+use crate::token::{parse_str, Col, Granularity, JiebaPlaceholder, Token};
+
+/// The source byte span `[start, end)` one decoded UTF-8 char occupied in
+/// the original, un-decoded buffer. Parallel to the chars of the `String`
+/// [`decode_with_offsets`] produces.
+#[derive(Debug, Clone, Copy)]
+struct SourceSpan {
+    start: usize,
+    end: usize,
+}
+
+/// Decode `bytes` (encoded as `encoding`, e.g. GBK/GB18030/Big5) into UTF-8,
+/// recording the source byte span of every decoded char along the way.
+///
+/// Feeds the decoder one source byte at a time so each output char -- which
+/// for these encodings can be anywhere from one to four source bytes --
+/// can be attributed to the exact range it came from. A source byte
+/// sequence the decoder can't map to a char comes back from `encoding_rs` as
+/// a single U+FFFD replacement char; the span recorded for it still covers
+/// every source byte consumed to produce it, so spans stay contiguous and
+/// gapless across the whole buffer even when it contains decoding errors.
+fn decode_with_offsets(bytes: &[u8], encoding: &'static Encoding) -> (String, Vec<SourceSpan>) {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let mut decoded = String::new();
+    let mut spans = Vec::new();
+    let mut span_start = 0;
+
+    for i in 0..bytes.len() {
+        let is_last_byte = i + 1 == bytes.len();
+        let before = decoded.len();
+        let _ = decoder.decode_to_string(&bytes[i..=i], &mut decoded, is_last_byte);
+        let new_chars = decoded[before..].chars().count();
+        if new_chars > 0 {
+            // Almost always exactly one char per fed-in byte that finishes
+            // a sequence. In the rare case a single step flushes more than
+            // one (e.g. a buffered error's replacement alongside the next
+            // char), attribute the whole consumed span to each -- they
+            // still chain contiguously either way.
+            let span = SourceSpan {
+                start: span_start,
+                end: i + 1,
+            };
+            spans.extend(std::iter::repeat(span).take(new_chars));
+            span_start = i + 1;
+        }
+    }
+
+    (decoded, spans)
+}
+
+/// Translate a UTF-8 byte offset into `decoded` (as found in a [`Col`]
+/// [`parse_str`] produced) to the matching offset in the original source
+/// bytes, via the per-char `spans` [`decode_with_offsets`] recorded.
+/// `utf8_offset` must land on a char boundary of `decoded`, which every
+/// `Col` field does by construction.
+fn source_offset(decoded: &str, spans: &[SourceSpan], utf8_offset: usize) -> usize {
+    let char_idx = decoded[..utf8_offset].chars().count();
+    match spans.get(char_idx) {
+        Some(span) => span.start,
+        // One past the last char, e.g. an `excl_end_byte_index` at EOL.
+        None => spans.last().map_or(0, |span| span.end),
+    }
+}
+
+fn remap_col(col: Col, decoded: &str, spans: &[SourceSpan]) -> Col {
+    Col {
+        start_byte_index: source_offset(decoded, spans, col.start_byte_index),
+        incl_end_byte_index: source_offset(decoded, spans, col.incl_end_byte_index),
+        excl_end_byte_index: source_offset(decoded, spans, col.excl_end_byte_index),
+    }
+}
+
+/// Like [`parse_str`], but for a buffer encoded as something other than
+/// UTF-8 (e.g. GBK, GB18030, or Big5 -- common for Chinese text in legacy
+/// files Vim opens with a non-UTF-8 `'fileencoding'`). `bytes` is decoded to
+/// UTF-8 for segmentation, then every resulting [`Token`]'s [`Col`] is
+/// translated back to byte offsets in `bytes` itself, so callers never see
+/// offsets into a transcoded copy that doesn't exist in the real buffer.
+pub(crate) fn parse_bytes<C: JiebaPlaceholder>(
+    bytes: &[u8],
+    encoding: &'static Encoding,
+    jieba: &C,
+    into_word: bool,
+) -> Vec<Token> {
+    let (decoded, spans) = decode_with_offsets(bytes, encoding);
+    parse_str(&decoded, jieba, Granularity::from(into_word))
+        .into_iter()
+        .map(|token| Token {
+            col: remap_col(token.col, &decoded, &spans),
+            ty: token.ty,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::OnceCell;
+    use proptest::prelude::*;
+
+    use jieba_rs::Jieba;
+
+    static JIEBA: OnceCell<Jieba> = OnceCell::new();
+
+    #[ctor::ctor]
+    fn init() {
+        JIEBA.get_or_init(|| Jieba::new());
+    }
+
+    fn parse_bytes_test(bytes: &[u8], encoding: &'static Encoding) -> Vec<Token> {
+        parse_bytes(bytes, encoding, JIEBA.get().unwrap(), true)
+    }
+
+    #[test]
+    fn test_parse_bytes_ascii_matches_parse_str() {
+        // GB18030 (like GBK/Big5) is a strict superset of ASCII for the
+        // 0x00-0x7f range, so a pure-ASCII buffer decodes byte-for-byte
+        // identically to UTF-8 and every `Col` should come out unchanged.
+        let bytes = b"hello world";
+        let tokens = parse_bytes_test(bytes, encoding_rs::GB18030);
+        let expected = parse_str("hello world", JIEBA.get().unwrap(), Granularity::Word);
+        assert_eq!(tokens, expected);
+    }
+
+    #[test]
+    fn test_parse_bytes_undecodable_byte_keeps_its_own_span() {
+        // 0x80 is not a valid GB18030 lead byte on its own; it should fall
+        // back to one U+FFFD token whose `Col` still spans exactly the one
+        // source byte that caused the error.
+        let bytes = [b'a', 0x80, b'b'];
+        let tokens = parse_bytes_test(&bytes, encoding_rs::GB18030);
+        let mut start = 0;
+        for tok in &tokens {
+            assert_eq!(tok.col.start_byte_index, start);
+            assert!(tok.col.start_byte_index <= tok.col.incl_end_byte_index);
+            assert!(tok.col.incl_end_byte_index < tok.col.excl_end_byte_index);
+            start = tok.col.excl_end_byte_index;
+        }
+        assert_eq!(start, bytes.len());
+    }
+
+    proptest! {
+        #[test]
+        fn parse_bytes_tokens_are_contiguous_in_source_bytes(
+            bytes in prop::collection::vec(any::<u8>(), 0..64)
+        ) {
+            let tokens = parse_bytes_test(&bytes, encoding_rs::GB18030);
+            let mut start = 0;
+            for tok in &tokens {
+                prop_assert_eq!(tok.col.start_byte_index, start);
+                prop_assert!(tok.col.start_byte_index <= tok.col.incl_end_byte_index);
+                prop_assert!(tok.col.incl_end_byte_index < tok.col.excl_end_byte_index);
+                start = tok.col.excl_end_byte_index;
+            }
+            prop_assert_eq!(start, bytes.len());
+        }
+    }
+}
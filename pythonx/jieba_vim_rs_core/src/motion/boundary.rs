@@ -0,0 +1,140 @@
+use super::BufferLike;
+use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
+
+use super::WordMotion;
+
+/// The kind of a [`Boundary`] segment. Collapses [`TokenType`] down to the
+/// kinds a motion ever stops on or skips over -- [`TokenType::Sentence`]
+/// doesn't arise from [`WordMotion::tokens`] and has no [`BoundaryKind`]
+/// counterpart. [`TokenType::Punctuation`] only appears once a
+/// [`WordMotion`] is built with [`WordMotion::with_classifier`]; it's its
+/// own kind rather than folded into `Word` so a caller can tell a
+/// keyword run from a punctuation run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoundaryKind {
+    Word,
+    Punctuation,
+    Space,
+}
+
+/// One token's column span within a line, as produced by
+/// [`WordMotion::segment_line`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Boundary {
+    pub kind: BoundaryKind,
+    /// The byte column of the first character in the token.
+    pub first_char: usize,
+    /// The byte column of the last character in the token.
+    pub last_char: usize,
+}
+
+impl<C: JiebaPlaceholder> WordMotion<C> {
+    /// Segment line `lnum` of `buffer` into `word`s (if `word` is `true`) or
+    /// `WORD`s (if `word` is `false`), returning every token's column span in
+    /// one pass. Reuses the same per-line cache as the motion functions, so
+    /// calling this before or after e.g. `nmap_w` on the same, unchanged line
+    /// doesn't re-segment it.
+    ///
+    /// This is the shared primitive the `w`/`e`/`b`/`ge` motions each replay
+    /// piecemeal; callers that want every stop position on a line at once
+    /// (a picker, textobject highlighting, a boundaries preview) should use
+    /// this instead of driving a motion function to enumerate them.
+    pub fn segment_line<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        lnum: usize,
+        word: bool,
+    ) -> Result<Vec<Boundary>, B::Error> {
+        let tokens = self.tokens(buffer, lnum, word)?;
+        Ok(tokens
+            .into_iter()
+            .map(|token| Boundary {
+                kind: match token.ty {
+                    TokenType::Word => BoundaryKind::Word,
+                    TokenType::Punctuation => BoundaryKind::Punctuation,
+                    TokenType::Space => BoundaryKind::Space,
+                    TokenType::Sentence => unreachable!(
+                        "segment_line sees tokens from token::parse_str, \
+                         which never emits Sentence"
+                    ),
+                },
+                first_char: token.first_char(),
+                last_char: token.last_char(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Boundary, BoundaryKind};
+    use crate::motion::WORD_MOTION;
+
+    #[test]
+    fn segment_line_splits_words_and_spaces() {
+        let buffer: Vec<&'static str> = vec!["aaaa  bbbb"];
+        let boundaries = WORD_MOTION.segment_line(&buffer, 1, true).unwrap();
+        assert_eq!(
+            boundaries,
+            vec![
+                Boundary {
+                    kind: BoundaryKind::Word,
+                    first_char: 0,
+                    last_char: 3,
+                },
+                Boundary {
+                    kind: BoundaryKind::Space,
+                    first_char: 4,
+                    last_char: 5,
+                },
+                Boundary {
+                    kind: BoundaryKind::Word,
+                    first_char: 6,
+                    last_char: 9,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn segment_line_on_empty_line_is_empty() {
+        let buffer: Vec<&'static str> = vec![""];
+        let boundaries = WORD_MOTION.segment_line(&buffer, 1, true).unwrap();
+        assert!(boundaries.is_empty());
+    }
+
+    #[test]
+    fn segment_line_reuses_cached_tokens() {
+        let buffer: Vec<&'static str> = vec!["aaaa bbbb"];
+        let before = WORD_MOTION.segment_line(&buffer, 1, true).unwrap();
+        let after = WORD_MOTION.segment_line(&buffer, 1, true).unwrap();
+        assert_eq!(before, after);
+    }
+
+    /// `Boundary::last_char` already exposes the inclusive end-of-word
+    /// column `nmap_e`/`nmap_ge` land on, so a caller can derive every `e`/
+    /// `ge` stop on a line from one `segment_line` call instead of driving
+    /// those motions one count at a time.
+    #[test]
+    fn segment_line_last_char_matches_nmap_e_landing_columns() {
+        let buffer: Vec<&'static str> = vec!["aaaa  bbbb cc"];
+        let boundaries = WORD_MOTION.segment_line(&buffer, 1, true).unwrap();
+        let word_ends: Vec<usize> = boundaries
+            .into_iter()
+            .filter(|b| b.kind == BoundaryKind::Word)
+            .map(|b| b.last_char)
+            .collect();
+        assert_eq!(word_ends, vec![3, 9, 12]);
+
+        let mut cursor = (1, 0);
+        for expected_end in word_ends {
+            cursor = WORD_MOTION
+                .nmap_e(&buffer, cursor, 1, true)
+                .unwrap()
+                .new_cursor_pos;
+            assert_eq!(cursor, (1, expected_end));
+        }
+    }
+}
@@ -0,0 +1,445 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use super::BufferLike;
+use crate::token::{self, AtomMatcher, Granularity, JiebaPlaceholder, Token};
+
+/// Default number of distinct `(lnum, word)` entries [`TokenCache`]
+/// remembers before evicting the least recently used one.
+pub(super) const DEFAULT_CAPACITY: usize = 256;
+
+/// A line's tokens, tagged with a hash of the content they were parsed from
+/// so a stale entry can be detected without eagerly invalidating the whole
+/// cache on every edit, and with the logical time it was last read so the
+/// least recently used entry can be found on eviction.
+struct CacheEntry {
+    hash: u64,
+    tokens: Vec<Token>,
+    last_used: u64,
+}
+
+/// Per-line cache of [`Token`]s, keyed by `(lnum, word)` since `word` and
+/// `WORD` tokenize the same line differently. Repeated motions and preview
+/// steps over an unchanged line reuse the cached tokens instead of re-running
+/// [`token::parse_str_with_atoms`] (char classification, combining-mark
+/// grouping, the jieba cut it wraps, and any registered atom spans). Bounded
+/// to [`Self::capacity`] entries, evicting the least recently used one once
+/// full, so an embedder driving motions over a huge buffer doesn't grow this
+/// cache without limit.
+///
+/// Coherence invariant: an entry is only ever checked against the content
+/// hash of the line at its own `lnum`, so [`Self::get_or_parse`] transparently
+/// reparses a line that was edited in place. It does *not* notice a line
+/// being inserted or removed elsewhere in the buffer, which shifts every
+/// following line's `lnum` without changing its content -- that would read
+/// back a neighbor's stale tokens under the wrong key. Embedders must call
+/// [`Self::invalidate`] for an edit confined to one line (no shift) and
+/// [`Self::clear`] for anything that inserts or removes lines.
+pub(super) struct TokenCache {
+    capacity: usize,
+    entries: RefCell<HashMap<(usize, bool), CacheEntry>>,
+    clock: RefCell<u64>,
+}
+
+impl TokenCache {
+    pub(super) fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Like [`Self::new`], but remembering at most `capacity` entries
+    /// instead of [`DEFAULT_CAPACITY`].
+    pub(super) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: RefCell::new(HashMap::new()),
+            clock: RefCell::new(0),
+        }
+    }
+
+    /// Return the tokens for line `lnum` of `buffer`, parsing and caching
+    /// them on a miss. A cached entry is reused only if `buffer.getline`
+    /// still returns the exact content it was parsed from. `atoms`, if
+    /// given, carves its registered spans out as single tokens before the
+    /// rest of the line is segmented -- see [`token::parse_str_with_atoms`].
+    pub(super) fn get_or_parse<B, C>(
+        &self,
+        buffer: &B,
+        jieba: &C,
+        lnum: usize,
+        word: bool,
+        atoms: Option<&AtomMatcher>,
+    ) -> Result<Vec<Token>, B::Error>
+    where
+        B: BufferLike + ?Sized,
+        C: JiebaPlaceholder,
+    {
+        let line = buffer.getline_ref(lnum)?;
+        let hash = hash_line(&line);
+        let now = self.tick();
+        if let Some(entry) = self.entries.borrow_mut().get_mut(&(lnum, word)) {
+            if entry.hash == hash {
+                entry.last_used = now;
+                return Ok(entry.tokens.clone());
+            }
+        }
+        let tokens =
+            token::parse_str_with_atoms(line, jieba, Granularity::from(word), atoms);
+        self.evict_if_full();
+        self.entries.borrow_mut().insert(
+            (lnum, word),
+            CacheEntry {
+                hash,
+                tokens: tokens.clone(),
+                last_used: now,
+            },
+        );
+        Ok(tokens)
+    }
+
+    fn tick(&self) -> u64 {
+        let mut clock = self.clock.borrow_mut();
+        *clock += 1;
+        *clock
+    }
+
+    fn evict_if_full(&self) {
+        let mut entries = self.entries.borrow_mut();
+        if entries.len() < self.capacity {
+            return;
+        }
+        if let Some(lru_key) = entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| *key)
+        {
+            entries.remove(&lru_key);
+        }
+    }
+
+    /// Drop the cached entries (`word` and `WORD` alike) for line `lnum`.
+    /// Cheaper than [`Self::clear`] for an edit known to be confined to a
+    /// single line that hasn't shifted any other line up or down.
+    pub(super) fn invalidate(&self, lnum: usize) {
+        let mut entries = self.entries.borrow_mut();
+        entries.remove(&(lnum, true));
+        entries.remove(&(lnum, false));
+    }
+
+    /// Like [`Self::invalidate`], but for every line in `start..=end`.
+    /// Cheaper than [`Self::clear`] for a multi-line edit (e.g. a visual
+    /// block change or a multi-line paste) that's still confined to a known
+    /// span and hasn't shifted any line outside it up or down.
+    pub(super) fn invalidate_range(&self, start: usize, end: usize) {
+        let mut entries = self.entries.borrow_mut();
+        for lnum in start..=end {
+            entries.remove(&(lnum, true));
+            entries.remove(&(lnum, false));
+        }
+    }
+
+    /// Drop every cached entry. The Vim side calls this when the buffer is
+    /// edited, since a cached entry's hash only guards against that one line
+    /// changing, not lines shifting up or down around an insertion/deletion.
+    pub(super) fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+}
+
+fn hash_line(line: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    line.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::motion::WordMotion;
+
+    /// A [`JiebaPlaceholder`] that counts its `cut_hmm` calls instead of
+    /// actually segmenting, so a test can assert a cache hit skips
+    /// segmentation entirely rather than just checking the returned tokens
+    /// match.
+    #[derive(Default)]
+    struct CountingCutter {
+        calls: RefCell<usize>,
+    }
+
+    impl JiebaPlaceholder for CountingCutter {
+        fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+            *self.calls.borrow_mut() += 1;
+            vec![sentence]
+        }
+    }
+
+    #[test]
+    fn repeated_get_or_parse_on_unchanged_line_hits_cache() {
+        let cutter = CountingCutter::default();
+        let cache = TokenCache::new();
+        let buffer: Vec<&'static str> = vec!["你好世界"];
+
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        assert_eq!(*cutter.calls.borrow(), 1);
+
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        assert_eq!(
+            *cutter.calls.borrow(),
+            1,
+            "second parse of an unchanged line should hit the cache"
+        );
+    }
+
+    #[test]
+    fn get_or_parse_reparses_after_line_content_changes() {
+        let cutter = CountingCutter::default();
+        let cache = TokenCache::new();
+
+        let before: Vec<&'static str> = vec!["你好世界"];
+        cache.get_or_parse(&before, &cutter, 1, true, None).unwrap();
+        assert_eq!(*cutter.calls.borrow(), 1);
+
+        let after: Vec<&'static str> = vec!["再见世界"];
+        cache.get_or_parse(&after, &cutter, 1, true, None).unwrap();
+        assert_eq!(
+            *cutter.calls.borrow(),
+            2,
+            "changed line content should invalidate the cached entry"
+        );
+    }
+
+    #[test]
+    fn invalidate_forces_reparse_of_only_that_line() {
+        let cutter = CountingCutter::default();
+        let cache = TokenCache::new();
+        let buffer: Vec<&'static str> = vec!["你好", "世界"];
+
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        cache.get_or_parse(&buffer, &cutter, 2, true, None).unwrap();
+        assert_eq!(*cutter.calls.borrow(), 2);
+
+        cache.invalidate(1);
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        assert_eq!(
+            *cutter.calls.borrow(),
+            3,
+            "invalidating line 1 should force it to be reparsed"
+        );
+
+        cache.get_or_parse(&buffer, &cutter, 2, true, None).unwrap();
+        assert_eq!(
+            *cutter.calls.borrow(),
+            3,
+            "line 2 was never invalidated and should still hit the cache"
+        );
+    }
+
+    #[test]
+    fn invalidate_range_forces_reparse_of_only_that_range() {
+        let cutter = CountingCutter::default();
+        let cache = TokenCache::new();
+        let buffer: Vec<&'static str> = vec!["你好", "世界", "再见"];
+
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        cache.get_or_parse(&buffer, &cutter, 2, true, None).unwrap();
+        cache.get_or_parse(&buffer, &cutter, 3, true, None).unwrap();
+        assert_eq!(*cutter.calls.borrow(), 3);
+
+        cache.invalidate_range(1, 2);
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        cache.get_or_parse(&buffer, &cutter, 2, true, None).unwrap();
+        assert_eq!(
+            *cutter.calls.borrow(),
+            5,
+            "both lines in the invalidated range should be reparsed"
+        );
+
+        cache.get_or_parse(&buffer, &cutter, 3, true, None).unwrap();
+        assert_eq!(
+            *cutter.calls.borrow(),
+            5,
+            "line 3 is outside the invalidated range and should still hit the cache"
+        );
+    }
+
+    #[test]
+    fn invalidate_drops_both_word_and_word_entries() {
+        let cutter = CountingCutter::default();
+        let cache = TokenCache::new();
+        let buffer: Vec<&'static str> = vec!["你好"];
+
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        cache.get_or_parse(&buffer, &cutter, 1, false, None).unwrap();
+        assert_eq!(*cutter.calls.borrow(), 2);
+
+        cache.invalidate(1);
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        cache.get_or_parse(&buffer, &cutter, 1, false, None).unwrap();
+        assert_eq!(*cutter.calls.borrow(), 4);
+    }
+
+    #[test]
+    fn get_or_parse_keeps_a_registered_atom_as_one_token() {
+        let cutter = CountingCutter::default();
+        let buffer: Vec<&'static str> = vec!["a::b"];
+
+        // Without an atom registered, "a", "::", "b" are three separate
+        // major-class groups. Two distinct caches, since an `atoms` set is
+        // fixed for a cache's whole lifetime in real use (it comes from the
+        // owning `WordMotion`, not from a per-call argument) -- reusing one
+        // cache across the two configurations below would just replay its
+        // first, now-stale entry instead of re-parsing.
+        let without_atoms = TokenCache::new();
+        let tokens = without_atoms
+            .get_or_parse(&buffer, &cutter, 1, true, None)
+            .unwrap();
+        assert_eq!(tokens.len(), 3);
+
+        // Registering "a::b" as an atom carves it out whole before the
+        // char-class pipeline ever sees it.
+        let atoms = AtomMatcher::new(["a::b"]);
+        let with_atoms = TokenCache::new();
+        let tokens = with_atoms
+            .get_or_parse(&buffer, &cutter, 1, true, Some(&atoms))
+            .unwrap();
+        assert_eq!(tokens.len(), 1);
+    }
+
+    #[test]
+    fn large_count_motion_only_segments_each_touched_line_once() {
+        let wm = WordMotion::new(CountingCutter::default());
+        let buffer: Vec<&'static str> = vec!["aaaa bbbb cccc dddd eeee"];
+
+        // `b` walks backwards one word per count step, so a count far larger
+        // than the number of words on the line forces the iterator to
+        // revisit the same line over and over. The cache should make that
+        // cost independent of `count`: one segmentation for the line, no
+        // matter how many steps land on it.
+        wm.nmap_b(&buffer, (1, 23), 10293949403, true).unwrap();
+        assert_eq!(
+            *wm.jieba().calls.borrow(),
+            1,
+            "a large-count motion over a single unchanged line should still \
+             segment it only once"
+        );
+    }
+
+    #[test]
+    fn cached_motions_over_many_long_cjk_lines_stay_fast() {
+        use jieba_vim_rs_test::assert_elapsed::AssertElapsed;
+
+        // Many long CJK lines, so a real jieba segmentation of all of them
+        // up front is itself non-trivial -- the budget below only holds if
+        // the thousands of `w`/`b` steps below reuse the cache instead of
+        // re-segmenting one of these lines on every step.
+        let line: String = "我们今天去公园散步看见了很多好看的花".repeat(20);
+        let buffer: Vec<String> = (0..200).map(|_| line.clone()).collect();
+        let wm = WordMotion::new(jieba_rs::Jieba::new());
+
+        // Touch every line once so its tokens are cached both ways.
+        for lnum in 1..=buffer.lines().unwrap() {
+            wm.nmap_w(&buffer, (lnum, 0), 1, true).unwrap();
+        }
+
+        let timer = AssertElapsed::tic(500);
+        let mut cursor = (1, 0);
+        for _ in 0..5000 {
+            let out = wm.nmap_w(&buffer, cursor, 1, true).unwrap();
+            cursor = if out.new_cursor_pos.0 >= buffer.len() {
+                (1, 0)
+            } else {
+                out.new_cursor_pos
+            };
+        }
+        timer.toc();
+    }
+
+    #[test]
+    fn get_or_parse_keys_word_and_word_separately() {
+        let cutter = CountingCutter::default();
+        let cache = TokenCache::new();
+        let buffer: Vec<&'static str> = vec!["你好世界"];
+
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        cache.get_or_parse(&buffer, &cutter, 1, false, None).unwrap();
+        assert_eq!(
+            *cutter.calls.borrow(),
+            2,
+            "`word` and `WORD` tokenize the same line differently and must not share a cache entry"
+        );
+    }
+
+    #[test]
+    fn with_capacity_evicts_the_least_recently_used_entry_once_full() {
+        let cutter = CountingCutter::default();
+        let cache = TokenCache::with_capacity(2);
+        let buffer: Vec<&'static str> = vec!["aaaa", "bbbb", "cccc"];
+
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        cache.get_or_parse(&buffer, &cutter, 2, true, None).unwrap();
+        // Touch line 1 again so line 2, not line 1, is the least recently
+        // used entry once a third distinct line forces an eviction.
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        assert_eq!(*cutter.calls.borrow(), 2);
+
+        cache.get_or_parse(&buffer, &cutter, 3, true, None).unwrap();
+        assert_eq!(*cutter.calls.borrow(), 3);
+
+        cache.get_or_parse(&buffer, &cutter, 1, true, None).unwrap();
+        assert_eq!(
+            *cutter.calls.borrow(),
+            3,
+            "line 1 was touched most recently and should survive eviction"
+        );
+
+        cache.get_or_parse(&buffer, &cutter, 2, true, None).unwrap();
+        assert_eq!(
+            *cutter.calls.borrow(),
+            4,
+            "line 2 was the least recently used entry and should have been evicted"
+        );
+    }
+
+    #[test]
+    fn bidirectional_cursor_scan_over_the_same_lines_only_segments_once() {
+        use super::super::token_iter::TokenCursor;
+
+        let wm = WordMotion::new(CountingCutter::default());
+        let buffer: Vec<&'static str> = vec!["aaaa bbbb", "cccc dddd"];
+
+        // Walk forward across both lines, then back over the same ground --
+        // the `e`/`ge` "peek ahead then back up" pattern the cache exists
+        // for -- and confirm each line is still only segmented once.
+        let mut cursor =
+            TokenCursor::new_forward(&buffer, &wm, 1, 0, true).unwrap();
+        let forward: Vec<_> =
+            (&mut cursor).take(5).map(Result::unwrap).collect();
+        assert_eq!(*wm.jieba().calls.borrow(), 2);
+
+        for expected in forward.iter().rev() {
+            let item = cursor.next_back().unwrap().unwrap();
+            assert_eq!(item.token, expected.token);
+        }
+        assert_eq!(
+            *wm.jieba().calls.borrow(),
+            2,
+            "re-scanning the same two lines backward should hit the cache, \
+             not re-segment them"
+        );
+    }
+}
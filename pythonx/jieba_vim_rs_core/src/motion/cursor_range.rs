@@ -0,0 +1,81 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+/// Which of Vim's three visual-selection shapes a [`CursorRange`] extends:
+/// `v` (charwise), `V` (linewise), or `<c-v>` (blockwise).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisualKind {
+    Char,
+    Line,
+    Block,
+}
+
+/// A visual-mode selection, anchored where the selection started (`anchor`,
+/// Vim's own `o`) and extending to where the motion just landed (`head`).
+/// `inclusive` says whether `head`'s own character is covered by the
+/// selection, the visual-mode counterpart of `MotionOutput`'s operator-mode
+/// exclusive/inclusive split.
+///
+/// A [`VisualKind::Block`] selection's left/right virtual columns are
+/// `anchor.1`/`head.1` themselves -- there is no separate column field,
+/// since a block selection is already exactly the rectangle those two
+/// corners describe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CursorRange {
+    pub anchor: (usize, usize),
+    pub head: (usize, usize),
+    pub inclusive: bool,
+}
+
+impl CursorRange {
+    /// Extend a selection of shape `kind` from `anchor` to `new_head`, the
+    /// raw landing spot a motion computed as if it were running in
+    /// charwise mode. `inclusive` is the motion's own inclusivity (e.g.
+    /// `ge`/`gE` is inclusive, `w`/`W` is not).
+    ///
+    /// - [`VisualKind::Char`] keeps `new_head` as given.
+    /// - [`VisualKind::Line`] drops `new_head`'s column -- linewise
+    ///   selections cover whole lines, so the column never reaches the
+    ///   caller -- and is always inclusive (the landed line is always
+    ///   covered).
+    /// - [`VisualKind::Block`] keeps `head`'s original column and only
+    ///   moves the row, preserving the block's left/right virtual columns
+    ///   the way `<c-v>` selections do for every other motion that isn't
+    ///   itself a horizontal one (`$`, `0`, ...).
+    pub fn extend(
+        kind: VisualKind,
+        anchor: (usize, usize),
+        head: (usize, usize),
+        new_head: (usize, usize),
+        inclusive: bool,
+    ) -> Self {
+        match kind {
+            VisualKind::Char => Self {
+                anchor,
+                head: new_head,
+                inclusive,
+            },
+            VisualKind::Line => Self {
+                anchor,
+                head: (new_head.0, 0),
+                inclusive: true,
+            },
+            VisualKind::Block => Self {
+                anchor,
+                head: (new_head.0, head.1),
+                inclusive,
+            },
+        }
+    }
+}
@@ -12,8 +12,8 @@
 // License for the specific language governing permissions and limitations
 // under the License.
 
-use super::{index_tokens, BufferLike};
-use crate::token::{self, JiebaPlaceholder, TokenLike, TokenType};
+use super::{index_tokens, BufferLike, WordMotion};
+use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
 
 /// Check if current motion satisfies d-special case. See
 /// https://vimhelp.org/change.txt.html#d-special.
@@ -22,7 +22,7 @@ use crate::token::{self, JiebaPlaceholder, TokenLike, TokenType};
 /// after motion.
 pub fn is_d_special<B: BufferLike + ?Sized, C: JiebaPlaceholder>(
     buffer: &B,
-    jieba: &C,
+    wm: &WordMotion<C>,
     cursor_pos: (usize, usize),
     new_cursor_pos: (usize, usize),
     word: bool,
@@ -34,39 +34,45 @@ pub fn is_d_special<B: BufferLike + ?Sized, C: JiebaPlaceholder>(
         return Ok(false);
     }
 
-    let tokens_cursor_line =
-        token::parse_str(buffer.getline(lnum)?, jieba, word);
+    let tokens_cursor_line = wm.tokens(buffer, lnum, word)?;
     if !tokens_cursor_line.is_empty() {
         let i = index_tokens(&tokens_cursor_line, col).unwrap();
         if tokens_cursor_line[..i].iter().any(|tok| match tok.ty {
             TokenType::Space => false,
-            TokenType::Word => true,
+            TokenType::Word | TokenType::Punctuation => true,
+            TokenType::Sentence => unreachable!(
+                "tokens here come from WordMotion::tokens, which never \
+                 emits Sentence"
+            ),
         }) {
             return Ok(false);
         }
         let cursor_token = &tokens_cursor_line[i];
-        if let TokenType::Word = cursor_token.ty {
+        if matches!(cursor_token.ty, TokenType::Word | TokenType::Punctuation) {
             if col > cursor_token.first_char() {
                 return Ok(false);
             }
         }
     }
 
-    let tokens_new_cursor_line =
-        token::parse_str(buffer.getline(new_lnum)?, jieba, word);
+    let tokens_new_cursor_line = wm.tokens(buffer, new_lnum, word)?;
     if !tokens_new_cursor_line.is_empty() {
         let j = index_tokens(&tokens_new_cursor_line, new_col).unwrap();
         if tokens_new_cursor_line[j + 1..]
             .iter()
             .any(|tok| match tok.ty {
                 TokenType::Space => false,
-                TokenType::Word => true,
+                TokenType::Word | TokenType::Punctuation => true,
+                TokenType::Sentence => unreachable!(
+                    "tokens here come from WordMotion::tokens, which never \
+                     emits Sentence"
+                ),
             })
         {
             return Ok(false);
         }
         let new_cursor_token = &tokens_new_cursor_line[j];
-        if let TokenType::Word = new_cursor_token.ty {
+        if matches!(new_cursor_token.ty, TokenType::Word | TokenType::Punctuation) {
             if new_col < new_cursor_token.last_char() {
                 return Ok(false);
             }
@@ -0,0 +1,164 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+//! Serde-serializable views into jieba's segmentation, for editors and
+//! tooling that want to inspect *why* a motion landed where it did rather
+//! than just where it landed.
+
+use super::token_iter::{TokenCursor, TokenIteratorItem};
+use super::{BufferLike, WordMotion};
+use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
+use serde::Serialize;
+
+/// Whether a [`TokenSpan`] is a run of word/WORD characters, whitespace, or
+/// one of the finer-grained kinds a [`crate::token::classify::Classifier`]
+/// can produce. Mirrors [`super::stops::StopKind`], except this one is
+/// serde-gated for JSON export rather than meant for a caller driving a
+/// custom motion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenKind {
+    Word,
+    Space,
+    Punctuation,
+}
+
+impl From<TokenType> for TokenKind {
+    fn from(ty: TokenType) -> Self {
+        match ty {
+            TokenType::Word => TokenKind::Word,
+            TokenType::Space => TokenKind::Space,
+            TokenType::Punctuation => TokenKind::Punctuation,
+            TokenType::Sentence => unreachable!(
+                "token_stream sees tokens from WordMotion::tokens, which \
+                 never emits Sentence"
+            ),
+        }
+    }
+}
+
+/// One jieba-segmented token on a single line, as reported by
+/// [`WordMotion::token_stream`]. `start_byte`/`end_byte` are byte offsets
+/// into the line's UTF-8 bytes (`end_byte` exclusive); `col` is the Vim
+/// column (0-indexed) of the token's first character, i.e. `start_byte`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TokenSpan {
+    pub kind: TokenKind,
+    pub col: usize,
+    pub start_byte: usize,
+    pub end_byte: usize,
+}
+
+/// One token boundary [`WordMotion::explain_omap_b`] stepped over on its way
+/// to the destination. `stopped` is `true` for the boundary the motion
+/// actually counted against `count` (i.e. landed on, possibly only
+/// momentarily before continuing for a larger count); `false` for
+/// whitespace boundaries skipped over on the way there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MotionStep {
+    pub lnum: usize,
+    pub col: usize,
+    pub stopped: bool,
+}
+
+/// The full trace of a `b`/`B` motion: every token boundary stepped over, in
+/// the order visited, and the final destination (same as what `omap_b`
+/// alone would have returned).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MotionExplanation {
+    pub steps: Vec<MotionStep>,
+    pub destination: (usize, usize),
+}
+
+/// Same stoppability rule as `nmap_b`'s `is_stoppable`: a word token, or the
+/// empty-line placeholder, is always stoppable; a run of whitespace never
+/// is. Duplicated here (rather than shared) since that helper is private to
+/// `nmap_b`'s module, same as `omap_b`'s own vcases duplicate `nmap_b`'s.
+fn is_stoppable(item: &TokenIteratorItem) -> bool {
+    match item.token {
+        None => true,
+        Some(token) => match token.ty {
+            TokenType::Word | TokenType::Punctuation => true,
+            TokenType::Space => false,
+            TokenType::Sentence => unreachable!(
+                "is_stoppable only sees tokens from WordMotion::tokens, \
+                 which never emits Sentence"
+            ),
+        },
+    }
+}
+
+impl<C: JiebaPlaceholder> WordMotion<C> {
+    /// The full jieba token stream for line `lnum` of `buffer`, cut into
+    /// `word`s if `word` is `true` or `WORD`s otherwise. Unlike the private
+    /// `tokens` this crate's motions use internally, this exposes byte
+    /// spans and word/space classification in a serde-serializable shape,
+    /// for callers that want to inspect jieba's segmentation directly
+    /// rather than just a motion's landing position.
+    pub fn token_stream<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        lnum: usize,
+        word: bool,
+    ) -> Result<Vec<TokenSpan>, B::Error> {
+        Ok(self
+            .tokens(buffer, lnum, word)?
+            .into_iter()
+            .map(|tok| TokenSpan {
+                kind: tok.ty.into(),
+                col: tok.col.start_byte_index,
+                start_byte: tok.col.start_byte_index,
+                end_byte: tok.col.excl_end_byte_index,
+            })
+            .collect())
+    }
+
+    /// Like [`Self::omap_b`], but instead of only the destination, returns
+    /// the ordered list of token boundaries the motion stepped through to
+    /// get there. This is `nmap_b`'s algorithm (which `omap_b` delegates to
+    /// verbatim) duplicated with bookkeeping added, rather than
+    /// instrumenting it in place, so the hot path the real motions take
+    /// stays exactly as before.
+    pub fn explain_omap_b<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        mut count: u64,
+        word: bool,
+    ) -> Result<MotionExplanation, B::Error> {
+        let (mut lnum, mut col) = cursor_pos;
+        let mut steps = Vec::new();
+        let mut it =
+            TokenCursor::new_backward(buffer, self, lnum, col, word)?
+                .rev()
+                .peekable();
+        while count > 0 && it.peek().is_some() {
+            let item = it.next().unwrap()?;
+            if !is_stoppable(&item) {
+                lnum = item.lnum;
+                col = item.token.first_char();
+                steps.push(MotionStep { lnum, col, stopped: false });
+            } else if !(item.cursor && col == item.token.first_char()) {
+                lnum = item.lnum;
+                col = item.token.first_char();
+                steps.push(MotionStep { lnum, col, stopped: true });
+                count -= 1;
+            }
+        }
+        Ok(MotionExplanation {
+            steps,
+            destination: (lnum, col),
+        })
+    }
+}
@@ -0,0 +1,335 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+//! Lets a caller redefine what counts as a single motion target by
+//! regrouping the raw tokens [`super::stops::ForwardStops`]/
+//! [`super::stops::BackwardStops`] already walk, via `make`-style glob
+//! patterns (a single `%` wildcard) matched against each token's text --
+//! e.g. treating a whole `%_%` snake_case run, or a `http%` URL-ish run, as
+//! one jump target instead of jieba's own per-token boundaries.
+
+use super::token_iter::{TokenCursor, TokenIteratorItem};
+use super::{BufferLike, JiebaPlaceholder, WordMotion};
+use crate::token::{Token, TokenLike};
+
+/// Whether `word` matches `pattern`. `pattern` is split on a single `%`
+/// wildcard: with no `%` it's an exact match; otherwise `word` must start
+/// with the prefix before `%` and end with the suffix after it, with
+/// `prefix.len() + suffix.len() <= word.len()` so the wildcard's own stem
+/// is never negative (i.e. the prefix and suffix may not overlap).
+pub fn glob_match(pattern: &str, word: &str) -> bool {
+    match pattern.split_once('%') {
+        None => pattern == word,
+        Some((prefix, suffix)) => {
+            prefix.len() + suffix.len() <= word.len()
+                && word.starts_with(prefix)
+                && word.ends_with(suffix)
+        }
+    }
+}
+
+fn matches_any(patterns: &[String], word: &str) -> bool {
+    patterns.iter().any(|pattern| glob_match(pattern, word))
+}
+
+fn token_text<B: BufferLike + ?Sized>(
+    buffer: &B,
+    lnum: usize,
+    token: Token,
+) -> Result<String, B::Error> {
+    let line = buffer.getline_ref(lnum)?;
+    Ok(line[token.col.start_byte_index..token.col.excl_end_byte_index].to_string())
+}
+
+/// One merged run of [`TokenIteratorItem`]s, as yielded by
+/// [`ForwardGlobStops`]/[`BackwardGlobStops`]. `first_col`/`last_col` widen
+/// to cover every merged token; `cursor`/`eol` are `true` if any merged
+/// token carried that flag, preserving them across the merge the same way
+/// [`super::stops::Stop::cursor`] is preserved verbatim when no merging
+/// occurs. Both are `0`/`false` (besides `cursor`) for the empty-line
+/// placeholder, which is never merged with anything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobStop {
+    pub lnum: usize,
+    pub first_col: usize,
+    pub last_col: usize,
+    pub cursor: bool,
+    pub eol: bool,
+}
+
+impl GlobStop {
+    fn singleton(item: TokenIteratorItem) -> Self {
+        Self {
+            lnum: item.lnum,
+            first_col: item.token.map(|tok| tok.first_char()).unwrap_or(0),
+            last_col: item.token.map(|tok| tok.last_char()).unwrap_or(0),
+            cursor: item.cursor,
+            eol: item.eol,
+        }
+    }
+
+    fn absorb(&mut self, item: TokenIteratorItem) {
+        let tok = item.token.expect("caller only merges Some(token) items");
+        self.first_col = self.first_col.min(tok.first_char());
+        self.last_col = self.last_col.max(tok.last_char());
+        self.cursor |= item.cursor;
+        self.eol |= item.eol;
+    }
+}
+
+/// Forward stream of [`GlobStop`]s, from [`WordMotion::forward_glob_stops`].
+pub struct ForwardGlobStops<'b, 'p, B: ?Sized, C> {
+    buffer: &'b B,
+    inner: TokenCursor<'b, 'p, B, C>,
+    patterns: Vec<String>,
+    peeked: Option<Result<TokenIteratorItem, <B as BufferLike>::Error>>,
+}
+
+impl<'b, 'p, B, C> Iterator for ForwardGlobStops<'b, 'p, B, C>
+where
+    B: BufferLike + ?Sized,
+    C: JiebaPlaceholder,
+{
+    type Item = Result<GlobStop, B::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.peeked.take().or_else(|| self.inner.next()) {
+            None => return None,
+            Some(Err(e)) => return Some(Err(e)),
+            Some(Ok(item)) => item,
+        };
+        let Some(first_tok) = first.token else {
+            return Some(Ok(GlobStop::singleton(first)));
+        };
+        let first_text = match token_text(self.buffer, first.lnum, first_tok) {
+            Ok(text) => text,
+            Err(e) => return Some(Err(e)),
+        };
+        let group_matches = matches_any(&self.patterns, &first_text);
+        let mut span = GlobStop::singleton(first);
+        loop {
+            let next = match self.inner.next() {
+                None => break,
+                Some(Err(e)) => return Some(Err(e)),
+                Some(Ok(item)) => item,
+            };
+            let Some(next_tok) = next.token else {
+                self.peeked = Some(Ok(next));
+                break;
+            };
+            if next.lnum != span.lnum {
+                self.peeked = Some(Ok(next));
+                break;
+            }
+            let next_text = match token_text(self.buffer, next.lnum, next_tok) {
+                Ok(text) => text,
+                Err(e) => return Some(Err(e)),
+            };
+            if matches_any(&self.patterns, &next_text) != group_matches {
+                self.peeked = Some(Ok(next));
+                break;
+            }
+            span.absorb(next);
+        }
+        Some(Ok(span))
+    }
+}
+
+/// Backward stream of [`GlobStop`]s, from
+/// [`WordMotion::backward_glob_stops`].
+pub struct BackwardGlobStops<'b, 'p, B: ?Sized, C> {
+    buffer: &'b B,
+    inner: TokenCursor<'b, 'p, B, C>,
+    patterns: Vec<String>,
+    peeked: Option<Result<TokenIteratorItem, <B as BufferLike>::Error>>,
+}
+
+impl<'b, 'p, B, C> Iterator for BackwardGlobStops<'b, 'p, B, C>
+where
+    B: BufferLike + ?Sized,
+    C: JiebaPlaceholder,
+{
+    type Item = Result<GlobStop, B::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let first = match self.peeked.take().or_else(|| self.inner.next_back()) {
+            None => return None,
+            Some(Err(e)) => return Some(Err(e)),
+            Some(Ok(item)) => item,
+        };
+        let Some(first_tok) = first.token else {
+            return Some(Ok(GlobStop::singleton(first)));
+        };
+        let first_text = match token_text(self.buffer, first.lnum, first_tok) {
+            Ok(text) => text,
+            Err(e) => return Some(Err(e)),
+        };
+        let group_matches = matches_any(&self.patterns, &first_text);
+        let mut span = GlobStop::singleton(first);
+        loop {
+            let next = match self.inner.next_back() {
+                None => break,
+                Some(Err(e)) => return Some(Err(e)),
+                Some(Ok(item)) => item,
+            };
+            let Some(next_tok) = next.token else {
+                self.peeked = Some(Ok(next));
+                break;
+            };
+            if next.lnum != span.lnum {
+                self.peeked = Some(Ok(next));
+                break;
+            }
+            let next_text = match token_text(self.buffer, next.lnum, next_tok) {
+                Ok(text) => text,
+                Err(e) => return Some(Err(e)),
+            };
+            if matches_any(&self.patterns, &next_text) != group_matches {
+                self.peeked = Some(Ok(next));
+                break;
+            }
+            span.absorb(next);
+        }
+        Some(Ok(span))
+    }
+}
+
+impl<C: JiebaPlaceholder> WordMotion<C> {
+    /// Forward stream of [`GlobStop`]s, starting from the token (or empty
+    /// line) the cursor `(lnum, col)` lies in. Adjacent tokens whose text
+    /// all match, or all fail, every pattern in `patterns` (see
+    /// [`glob_match`]) are merged into a single [`GlobStop`], never
+    /// crossing a line boundary.
+    pub fn forward_glob_stops<'b, 'p, B: BufferLike + ?Sized>(
+        &'p self,
+        buffer: &'b B,
+        cursor_pos: (usize, usize),
+        word: bool,
+        patterns: Vec<String>,
+    ) -> Result<ForwardGlobStops<'b, 'p, B, C>, B::Error> {
+        let (lnum, col) = cursor_pos;
+        Ok(ForwardGlobStops {
+            buffer,
+            inner: TokenCursor::new_forward(buffer, self, lnum, col, word)?,
+            patterns,
+            peeked: None,
+        })
+    }
+
+    /// Backward counterpart of [`Self::forward_glob_stops`].
+    pub fn backward_glob_stops<'b, 'p, B: BufferLike + ?Sized>(
+        &'p self,
+        buffer: &'b B,
+        cursor_pos: (usize, usize),
+        word: bool,
+        patterns: Vec<String>,
+    ) -> Result<BackwardGlobStops<'b, 'p, B, C>, B::Error> {
+        let (lnum, col) = cursor_pos;
+        Ok(BackwardGlobStops {
+            buffer,
+            inner: TokenCursor::new_backward(buffer, self, lnum, col, word)?,
+            patterns,
+            peeked: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_match, GlobStop};
+
+    #[test]
+    fn test_glob_match_exact() {
+        assert!(glob_match("foo", "foo"));
+        assert!(!glob_match("foo", "foobar"));
+    }
+
+    #[test]
+    fn test_glob_match_wildcard() {
+        assert!(glob_match("foo%", "foobar"));
+        assert!(glob_match("%bar", "foobar"));
+        assert!(glob_match("f%r", "foobar"));
+        assert!(!glob_match("foo%", "barfoo"));
+    }
+
+    #[test]
+    fn test_glob_match_wildcard_non_overlapping_stem() {
+        // "prefix.len() + suffix.len() <= word.len()" -- "fo%oo" can't match
+        // "foo" since the prefix and suffix would have to overlap.
+        assert!(!glob_match("fo%oo", "foo"));
+        assert!(glob_match("fo%oo", "fooo"));
+    }
+
+    #[test]
+    fn test_forward_glob_stops_merges_the_non_matching_run() {
+        // "foo", ".", "bar" are three separate Word tokens ('.' is
+        // punctuation, so it never merges with "foo"/"bar" during jieba's
+        // own segmentation); only "foo" matches "foo%", so "." and "bar"
+        // merge into a single non-matching span.
+        let buffer = vec!["foo.bar"];
+        let stops = crate::motion::WORD_MOTION
+            .forward_glob_stops(&buffer, (1, 0), true, vec!["foo%".to_string()])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            stops,
+            vec![
+                GlobStop {
+                    lnum: 1,
+                    first_col: 0,
+                    last_col: 2,
+                    cursor: true,
+                    eol: false,
+                },
+                GlobStop {
+                    lnum: 1,
+                    first_col: 3,
+                    last_col: 6,
+                    cursor: false,
+                    eol: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_backward_glob_stops_merges_the_non_matching_run() {
+        let buffer = vec!["foo.bar"];
+        let stops = crate::motion::WORD_MOTION
+            .backward_glob_stops(&buffer, (1, 6), true, vec!["foo%".to_string()])
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            stops,
+            vec![
+                GlobStop {
+                    lnum: 1,
+                    first_col: 3,
+                    last_col: 6,
+                    cursor: true,
+                    eol: false,
+                },
+                GlobStop {
+                    lnum: 1,
+                    first_col: 0,
+                    last_col: 2,
+                    cursor: false,
+                    eol: false,
+                },
+            ]
+        );
+    }
+}
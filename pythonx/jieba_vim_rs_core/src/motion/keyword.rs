@@ -0,0 +1,261 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+//! Rank the non-`Space` tokens of a buffer by importance and surface their
+//! positions, so a Vim mapping can hop between the semantically salient
+//! words instead of stepping through every `w`/`b`.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use super::{BufferLike, WordMotion};
+use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
+
+/// Which scoring pass [`WordMotion::keyword_positions`] runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordAlgorithm {
+    /// A PageRank pass over a graph where each distinct word is a node and
+    /// edges are weighted by how often two words fall within `window`
+    /// tokens of each other. Needs no external data -- unlike `TfIdf`, it
+    /// has no notion of which words are common across documents vs.
+    /// specific to this one -- but still favors words that sit at the hub
+    /// of this buffer's co-occurrences.
+    TextRank {
+        /// Size of the sliding co-occurrence window, in tokens.
+        window: usize,
+    },
+    /// Term frequency in this buffer times [`JiebaPlaceholder::idf`]. With
+    /// the default `idf` (which returns `1.0` for every word), this
+    /// degrades to plain term-frequency ranking.
+    TfIdf,
+}
+
+/// One occurrence of a non-`Space` token, collected across the whole
+/// buffer, for keyword scoring.
+struct Occurrence {
+    lnum: usize,
+    col: usize,
+    word: String,
+}
+
+impl<C: JiebaPlaceholder> WordMotion<C> {
+    /// Every non-`Space` token of `buffer`, cut into `word`s if `word` is
+    /// `true` or `WORD`s otherwise, in buffer order.
+    fn collect_occurrences<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        word: bool,
+    ) -> Result<Vec<Occurrence>, B::Error> {
+        let mut occurrences = Vec::new();
+        for lnum in 1..=buffer.lines()? {
+            let line = buffer.getline_ref(lnum)?;
+            for tok in self.tokens(buffer, lnum, word)? {
+                if tok.ty == TokenType::Space {
+                    continue;
+                }
+                occurrences.push(Occurrence {
+                    lnum,
+                    col: tok.first_char(),
+                    word: line
+                        [tok.col.start_byte_index..tok.col.excl_end_byte_index]
+                        .to_string(),
+                });
+            }
+        }
+        Ok(occurrences)
+    }
+
+    /// Rank the words of `buffer` with `algorithm` and return the
+    /// `(lnum, col)` position of every occurrence of the top `top_k` of
+    /// them, in buffer order.
+    pub fn keyword_positions<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        word: bool,
+        algorithm: KeywordAlgorithm,
+        top_k: usize,
+    ) -> Result<Vec<(usize, usize)>, B::Error> {
+        let occurrences = self.collect_occurrences(buffer, word)?;
+        let scores = match algorithm {
+            KeywordAlgorithm::TextRank { window } => {
+                text_rank(&occurrences, window)
+            }
+            KeywordAlgorithm::TfIdf => tf_idf(&occurrences, self.jieba()),
+        };
+        let top = top_words(&scores, top_k);
+        Ok(occurrences
+            .into_iter()
+            .filter(|occ| top.contains(&occ.word))
+            .map(|occ| (occ.lnum, occ.col))
+            .collect())
+    }
+}
+
+/// Term frequency (raw occurrence count) times [`JiebaPlaceholder::idf`],
+/// for every distinct word in `occurrences`.
+fn tf_idf<C: JiebaPlaceholder>(
+    occurrences: &[Occurrence],
+    jieba: &C,
+) -> HashMap<String, f64> {
+    let mut counts: HashMap<&str, f64> = HashMap::new();
+    for occ in occurrences {
+        *counts.entry(occ.word.as_str()).or_insert(0.0) += 1.0;
+    }
+    counts
+        .into_iter()
+        .map(|(word, tf)| (word.to_string(), tf * jieba.idf(word)))
+        .collect()
+}
+
+/// TextRank: build a co-occurrence graph over a sliding `window` of tokens,
+/// weight edges by co-occurrence count, then iterate the weighted PageRank
+/// recurrence `score(v) = (1-d) + d * sum_{u->v} w(u,v)/out_weight(u) *
+/// score(u)` to convergence.
+fn text_rank(
+    occurrences: &[Occurrence],
+    window: usize,
+) -> HashMap<String, f64> {
+    let words: Vec<&str> =
+        occurrences.iter().map(|occ| occ.word.as_str()).collect();
+
+    let mut edges: HashMap<&str, HashMap<&str, f64>> = HashMap::new();
+    for i in 0..words.len() {
+        for j in (i + 1)..words.len().min(i + window) {
+            if words[i] == words[j] {
+                continue;
+            }
+            *edges
+                .entry(words[i])
+                .or_default()
+                .entry(words[j])
+                .or_insert(0.0) += 1.0;
+            *edges
+                .entry(words[j])
+                .or_default()
+                .entry(words[i])
+                .or_insert(0.0) += 1.0;
+        }
+    }
+
+    let mut nodes: Vec<&str> = words;
+    nodes.sort_unstable();
+    nodes.dedup();
+
+    let out_weight: HashMap<&str, f64> = edges
+        .iter()
+        .map(|(&u, neighbors)| (u, neighbors.values().sum()))
+        .collect();
+
+    const DAMPING: f64 = 0.85;
+    const MAX_ITERATIONS: usize = 50;
+    const EPSILON: f64 = 1e-4;
+
+    let mut scores: HashMap<&str, f64> =
+        nodes.iter().map(|&w| (w, 1.0)).collect();
+
+    for _ in 0..MAX_ITERATIONS {
+        let mut max_delta: f64 = 0.0;
+        let mut next = HashMap::with_capacity(nodes.len());
+        for &v in &nodes {
+            let incoming: f64 = edges
+                .get(v)
+                .into_iter()
+                .flatten()
+                .map(|(u, w_uv)| w_uv / out_weight[u] * scores[u])
+                .sum();
+            let score = (1.0 - DAMPING) + DAMPING * incoming;
+            max_delta = max_delta.max((score - scores[v]).abs());
+            next.insert(v, score);
+        }
+        scores = next;
+        if max_delta < EPSILON {
+            break;
+        }
+    }
+
+    scores
+        .into_iter()
+        .map(|(w, s)| (w.to_string(), s))
+        .collect()
+}
+
+/// The `top_k` words of `scores`, ranked by descending score. Ties break on
+/// the word itself so the result is deterministic.
+fn top_words(scores: &HashMap<String, f64>, top_k: usize) -> Vec<String> {
+    let mut words: Vec<&String> = scores.keys().collect();
+    words.sort_by(|a, b| {
+        scores[*b]
+            .partial_cmp(&scores[*a])
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.cmp(b))
+    });
+    words.truncate(top_k);
+    words.into_iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::WORD_MOTION;
+    use super::KeywordAlgorithm;
+
+    #[test]
+    fn test_keyword_positions_text_rank_favors_the_hub_word() {
+        // "a" sits next to every other word, so it's the hub of the
+        // co-occurrence graph and should outrank the singletons.
+        let buffer = vec!["a b a c a d"];
+        let result = WORD_MOTION
+            .keyword_positions(
+                &buffer,
+                true,
+                KeywordAlgorithm::TextRank { window: 2 },
+                1,
+            )
+            .unwrap();
+        assert_eq!(result, vec![(1, 0), (1, 4), (1, 8)]);
+    }
+
+    #[test]
+    fn test_keyword_positions_tf_idf_favors_the_most_frequent_word() {
+        // With the default `idf` (always `1.0`), this is plain term
+        // frequency: "x" appears three times, "y" and "z" once each.
+        let buffer = vec!["x y x z x"];
+        let result = WORD_MOTION
+            .keyword_positions(&buffer, true, KeywordAlgorithm::TfIdf, 1)
+            .unwrap();
+        assert_eq!(result, vec![(1, 0), (1, 4), (1, 8)]);
+    }
+
+    #[test]
+    fn test_keyword_positions_empty_buffer() {
+        let buffer = vec![""];
+        let result = WORD_MOTION
+            .keyword_positions(
+                &buffer,
+                true,
+                KeywordAlgorithm::TextRank { window: 2 },
+                5,
+            )
+            .unwrap();
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn test_keyword_positions_top_k_zero_selects_nothing() {
+        let buffer = vec!["a b a c a d"];
+        let result = WORD_MOTION
+            .keyword_positions(&buffer, true, KeywordAlgorithm::TfIdf, 0)
+            .unwrap();
+        assert!(result.is_empty());
+    }
+}
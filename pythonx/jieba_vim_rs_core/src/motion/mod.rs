@@ -12,27 +12,46 @@
 // License for the specific language governing permissions and limitations
 // under the License.
 
-use crate::token::{JiebaPlaceholder, Token};
+use crate::char_class::WidthConfig;
+use crate::token::{AtomMatcher, JiebaPlaceholder, Token};
 #[cfg(test)]
 use jieba_vim_rs_test::verified_case::cases::MotionOutput as TestMotionOutput;
 use std::cmp::Ordering;
 
+mod boundary;
+mod cache;
+mod cursor_range;
 mod d_special;
+#[cfg(feature = "serde")]
+mod explain;
+mod glob_stops;
+mod keyword;
 mod nmap_b;
 mod nmap_e;
 mod nmap_ge;
 mod nmap_w;
 mod omap_b;
 mod omap_c_w;
-mod omap_d_e;
 mod omap_e;
+mod omap_ge;
 mod omap_w;
+mod stops;
+mod text_object;
 mod token_iter;
 mod xmap_b;
 mod xmap_e;
 mod xmap_ge;
 mod xmap_w;
 
+pub use boundary::{Boundary, BoundaryKind};
+pub use cursor_range::{CursorRange, VisualKind};
+#[cfg(feature = "serde")]
+pub use explain::{MotionExplanation, MotionStep, TokenKind, TokenSpan};
+pub use glob_stops::{glob_match, BackwardGlobStops, ForwardGlobStops, GlobStop};
+pub use keyword::KeywordAlgorithm;
+pub use stops::{BackwardStops, ForwardStops, Stop, StopKind};
+pub use text_object::TextObjectOutput;
+
 /// Any type that resembles a Vim buffer.
 pub trait BufferLike {
     type Error;
@@ -40,12 +59,22 @@ pub trait BufferLike {
     /// Get the line at line number `lnum` (1-indexed).
     fn getline(&self, lnum: usize) -> Result<String, Self::Error>;
 
+    /// Like [`Self::getline`], but lets an implementation backed by a rope
+    /// or a `Vec<String>` hand back a borrow instead of cloning. Defaults to
+    /// wrapping [`Self::getline`]'s owned `String`; override this when
+    /// borrowing is possible, since every motion reads a line through this
+    /// method rather than [`Self::getline`] directly.
+    fn getline_ref(&self, lnum: usize) -> Result<std::borrow::Cow<'_, str>, Self::Error> {
+        Ok(std::borrow::Cow::Owned(self.getline(lnum)?))
+    }
+
     /// Get the total number of lines in the buffer.
     fn lines(&self) -> Result<usize, Self::Error>;
 }
 
 /// The motion return type.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MotionOutput {
     /// The new cursor position after the motion.
     pub new_cursor_pos: (usize, usize),
@@ -66,6 +95,56 @@ impl PartialEq<TestMotionOutput> for MotionOutput {
     }
 }
 
+/// The exact span of text an `omap_e`/`omap_ge` motion determined it
+/// should act on, so a caller can delete/yank/change it directly instead of
+/// re-deriving the `d-special` linewise promotion
+/// (https://vimhelp.org/change.txt.html#d-special) and `e`/`E`/`ge`/`gE`'s
+/// inclusive-via-`o_v` exclusivity flip from `new_cursor_pos` alone.
+/// `start`/`end` are always ordered so `start <= end`, regardless of
+/// whether the underlying motion moved forward (`e`/`E`) or backward
+/// (`ge`/`gE`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OperatorRange {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+    pub linewise: bool,
+    pub inclusive: bool,
+}
+
+/// Build the [`OperatorRange`] an `omap_e`/`omap_ge` pair of positions
+/// describes. When `linewise` (i.e. `d_special`) takes effect, the range is
+/// promoted to a linewise span running from the earlier position's line up
+/// to, but excluding, the later position's line -- the same exclusion
+/// `d-special` already encodes by requiring nothing but blanks follow the
+/// later position on its own line.
+fn operator_range(
+    cursor_pos: (usize, usize),
+    new_cursor_pos: (usize, usize),
+    linewise: bool,
+) -> OperatorRange {
+    let (start, end) = if cursor_pos <= new_cursor_pos {
+        (cursor_pos, new_cursor_pos)
+    } else {
+        (new_cursor_pos, cursor_pos)
+    };
+    if linewise {
+        OperatorRange {
+            start: (start.0, 0),
+            end: (end.0 - 1, 0),
+            linewise: true,
+            inclusive: true,
+        }
+    } else {
+        OperatorRange {
+            start,
+            end,
+            linewise: false,
+            inclusive: true,
+        }
+    }
+}
+
 /// Get the index of the token in `tokens` that covers `col`. Return `None` if
 /// `col` is to the right of the last token.
 fn index_tokens(tokens: &[Token], col: usize) -> Option<usize> {
@@ -82,13 +161,219 @@ fn index_tokens(tokens: &[Token], col: usize) -> Option<usize> {
         .ok()
 }
 
+/// Like [`index_tokens`], but `col` is a virtual display column (as returned
+/// by [`crate::char_class::display_col`]) rather than a byte offset --
+/// converted to one via [`crate::char_class::byte_col_from_display_col`]
+/// before delegating, so a caller driving the cursor off screen-cell
+/// position (e.g. a mouse click, or a motion that has to stay on the same
+/// visual column across lines) doesn't have to do that conversion itself.
+fn index_tokens_at_display_col(
+    tokens: &[Token],
+    line: &str,
+    col: usize,
+    width_config: &WidthConfig,
+) -> Option<usize> {
+    let byte_col = crate::char_class::byte_col_from_display_col(line, col, width_config);
+    index_tokens(tokens, byte_col)
+}
+
 pub struct WordMotion<C> {
     jieba: C,
+    cache: cache::TokenCache,
+    classifier: crate::token::classify::Classifier,
+    width_config: WidthConfig,
+    atoms: Option<AtomMatcher>,
 }
 
 impl<C: JiebaPlaceholder> WordMotion<C> {
     pub fn new(jieba: C) -> Self {
-        Self { jieba }
+        Self {
+            jieba,
+            cache: cache::TokenCache::new(),
+            classifier: crate::token::classify::Classifier::default(),
+            width_config: WidthConfig::default(),
+            atoms: None,
+        }
+    }
+
+    /// Like [`Self::new`], but reclassifying every jieba `Word` token
+    /// through `classifier` before any motion or iterator sees it, so e.g.
+    /// `xmap_w` can stop at keyword/punctuation transitions instead of only
+    /// at jieba's word/space boundaries. `Classifier::default()` reproduces
+    /// [`Self::new`]'s plain two-class behavior.
+    pub fn with_classifier(
+        jieba: C,
+        classifier: crate::token::classify::Classifier,
+    ) -> Self {
+        Self {
+            jieba,
+            cache: cache::TokenCache::new(),
+            classifier,
+            width_config: WidthConfig::default(),
+            atoms: None,
+        }
+    }
+
+    /// Set the [`WidthConfig`] this [`WordMotion`] reports display columns
+    /// through (e.g. [`xmap_ge::xmap_ge_vcol`](Self::xmap_ge_vcol)),
+    /// matching the embedder's actual `'ambiwidth'` setting.
+    /// `WidthConfig::default()` is used otherwise.
+    pub fn with_width_config(mut self, width_config: WidthConfig) -> Self {
+        self.width_config = width_config;
+        self
+    }
+
+    /// Register `atoms` -- URLs, paths, operators like `::`/`=>`, emoji
+    /// clusters, or anything else that must stay one `word`/`WORD` token --
+    /// so every motion's tokenization treats a matching span as a single
+    /// token no matter how jieba's own cutters would otherwise split it.
+    /// Unset by default, i.e. no spans are protected.
+    pub fn with_atoms(mut self, atoms: AtomMatcher) -> Self {
+        self.atoms = Some(atoms);
+        self
+    }
+
+    /// Override the per-line token cache's default capacity (how many
+    /// distinct `(lnum, word)` entries are remembered before the least
+    /// recently used one is evicted). [`cache::DEFAULT_CAPACITY`] is used
+    /// otherwise.
+    pub fn with_cache_capacity(mut self, capacity: usize) -> Self {
+        self.cache = cache::TokenCache::with_capacity(capacity);
+        self
+    }
+
+    /// Access the underlying [`JiebaPlaceholder`], e.g. to reach an
+    /// implementation-specific segmentation cache.
+    pub fn jieba(&self) -> &C {
+        &self.jieba
+    }
+
+    /// Tokens for line `lnum` of `buffer`, cut into `word`s if `word` is
+    /// `true` or `WORD`s otherwise, with any [`Self::with_atoms`] span kept
+    /// whole, then reclassified by this [`WordMotion`]'s
+    /// [`crate::token::classify::Classifier`]. Reuses the cached parse from
+    /// a previous call over the same, unchanged line.
+    fn tokens<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        lnum: usize,
+        word: bool,
+    ) -> Result<Vec<Token>, B::Error> {
+        let tokens = self.cache.get_or_parse(
+            buffer,
+            &self.jieba,
+            lnum,
+            word,
+            self.atoms.as_ref(),
+        )?;
+        let line = buffer.getline_ref(lnum)?;
+        Ok(crate::token::classify::reclassify(
+            tokens,
+            &line,
+            &self.classifier,
+        ))
+    }
+
+    /// The index into [`Self::tokens`] of the token covering virtual display
+    /// column `vcol` of line `lnum` -- for a caller that only has a Vim
+    /// `virtcol()` (screen-cell offset) rather than a byte `col`, e.g.
+    /// preserving the visual column of a `j`/`k` motion across lines of
+    /// differing CJK/narrow content. `None` if `vcol` is to the right of the
+    /// last token, mirroring [`index_tokens`].
+    pub fn token_index_at_display_col<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        lnum: usize,
+        vcol: usize,
+        word: bool,
+    ) -> Result<Option<usize>, B::Error> {
+        let line = buffer.getline_ref(lnum)?;
+        let tokens = self.tokens(buffer, lnum, word)?;
+        Ok(index_tokens_at_display_col(
+            &tokens,
+            &line,
+            vcol,
+            &self.width_config,
+        ))
+    }
+
+    /// Drop the cached parse of line `lnum`, `word` and `WORD` alike. Call
+    /// this instead of [`Self::clear_cache`] when an edit is known to be
+    /// confined to that one line's text and hasn't shifted any other line up
+    /// or down -- e.g. a single-line change notification from the editor.
+    pub fn invalidate_cache(&self, lnum: usize) {
+        self.cache.invalidate(lnum);
+    }
+
+    /// Like [`Self::invalidate_cache`], but for every line in `start..=end`.
+    /// Cheaper than [`Self::clear_cache`] for a multi-line edit (e.g. a
+    /// visual block change or a multi-line paste) known not to have shifted
+    /// any line outside that range up or down.
+    pub fn invalidate_range(&self, start: usize, end: usize) {
+        self.cache.invalidate_range(start, end);
+    }
+
+    /// Drop every cached line parse. Call this when the buffer is edited,
+    /// since cached tokens are only valid for the exact line content they
+    /// were computed from.
+    pub fn clear_cache(&self) {
+        self.cache.clear();
+    }
+}
+
+/// Which Vim word motion [`WordMotion::omap`] should run. `word` vs `WORD`
+/// stays the separate `word: bool` parameter every other `nmap_*`/`omap_*`
+/// method already takes, so this only picks the motion key, not the
+/// word-class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    W,
+    E,
+    B,
+    Ge,
+}
+
+/// Which operator [`WordMotion::omap`] is running under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Change,
+    Delete,
+    Yank,
+}
+
+impl<C: JiebaPlaceholder> WordMotion<C> {
+    /// Generic operator-pending entry point, dispatching to the `omap_*`
+    /// family the same way `motion`/`operator` name it -- e.g.
+    /// `omap(buf, pos, count, Motion::E, Operator::Change, word)` runs the
+    /// same motion as `ce`/`cE`. `Motion::W` additionally special-cases
+    /// [`Operator::Change`] as `cw`/`cW` ([`Self::omap_c_w`]), mirroring
+    /// Vim's own `cw` == `ce` exception. [`Self::omap_b`] has no
+    /// `d_special`/`prevent_change` of its own to report, so its bare
+    /// cursor position is wrapped with both `false`.
+    pub fn omap<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        count: u64,
+        motion: Motion,
+        operator: Operator,
+        word: bool,
+    ) -> Result<MotionOutput, B::Error> {
+        match motion {
+            Motion::W if operator == Operator::Change => {
+                self.omap_c_w(buffer, cursor_pos, count, word)
+            }
+            Motion::W => self.omap_w(buffer, cursor_pos, count, word),
+            Motion::E => self.omap_e(buffer, cursor_pos, count, word),
+            Motion::B => self
+                .omap_b(buffer, cursor_pos, count, word)
+                .map(|new_cursor_pos| MotionOutput {
+                    new_cursor_pos,
+                    d_special: false,
+                    prevent_change: false,
+                }),
+            Motion::Ge => self.omap_ge(buffer, cursor_pos, count, word),
+        }
     }
 }
 
@@ -115,6 +400,12 @@ impl BufferLike for Vec<&'static str> {
         self.get(lnum - 1).map(|s| s.to_string()).ok_or(())
     }
 
+    fn getline_ref(&self, lnum: usize) -> Result<std::borrow::Cow<'_, str>, Self::Error> {
+        self.get(lnum - 1)
+            .map(|s| std::borrow::Cow::Borrowed(*s))
+            .ok_or(())
+    }
+
     fn lines(&self) -> Result<usize, Self::Error> {
         Ok(self.len())
     }
@@ -128,6 +419,12 @@ impl BufferLike for Vec<String> {
         self.get(lnum - 1).map(|s| s.to_string()).ok_or(())
     }
 
+    fn getline_ref(&self, lnum: usize) -> Result<std::borrow::Cow<'_, str>, Self::Error> {
+        self.get(lnum - 1)
+            .map(|s| std::borrow::Cow::Borrowed(s.as_str()))
+            .ok_or(())
+    }
+
     fn lines(&self) -> Result<usize, Self::Error> {
         Ok(self.len())
     }
@@ -135,10 +432,115 @@ impl BufferLike for Vec<String> {
 
 #[cfg(test)]
 mod tests {
-    use super::index_tokens;
+    use super::{index_tokens, Motion, Operator, WordMotion, WORD_MOTION};
 
     #[test]
     fn test_index_tokens() {
         assert_eq!(index_tokens(&[], 0), None);
     }
+
+    #[test]
+    fn test_token_index_at_display_col_accounts_for_wide_chars() {
+        // "中文ab": the CJK run occupies display columns 0-3, so vcol 3
+        // (the second half of "文"'s cell) must still resolve to that
+        // token, not "ab" which starts at display column 4.
+        let buffer: Vec<&'static str> = vec!["中文ab"];
+        let word_token = WORD_MOTION
+            .token_index_at_display_col(&buffer, 1, 3, true)
+            .unwrap();
+        let tokens = WORD_MOTION.tokens(&buffer, 1, true).unwrap();
+        assert_eq!(word_token, Some(0));
+        assert_eq!(tokens[0].col.start_byte_index, 0);
+
+        let ab_token = WORD_MOTION
+            .token_index_at_display_col(&buffer, 1, 4, true)
+            .unwrap();
+        assert_eq!(ab_token, Some(1));
+    }
+
+    #[test]
+    fn test_with_width_config_affects_display_col_lookup() {
+        // "α" is East Asian Width Ambiguous: one cell under Vim's default
+        // `'ambiwidth'=single`, so display column 1 already lands on the
+        // following space token; under `ambiguous_wide` (`=double`), it's
+        // two cells, so column 1 still lands inside "α" itself.
+        let buffer: Vec<&'static str> = vec!["α ab"];
+
+        let default_idx = WORD_MOTION
+            .token_index_at_display_col(&buffer, 1, 1, true)
+            .unwrap();
+        let tokens = WORD_MOTION.tokens(&buffer, 1, true).unwrap();
+        assert_eq!(default_idx, Some(1));
+        assert_eq!(tokens[1].ty, crate::token::TokenType::Space);
+
+        let wm = WordMotion::new(jieba_rs::Jieba::new()).with_width_config(
+            crate::char_class::WidthConfig {
+                ambiguous_wide: true,
+            },
+        );
+        let wide_idx = wm.token_index_at_display_col(&buffer, 1, 1, true).unwrap();
+        assert_eq!(wide_idx, Some(0));
+        assert_eq!(tokens[0].ty, crate::token::TokenType::Word);
+    }
+
+    #[test]
+    fn test_with_atoms_keeps_an_operator_as_one_word_across_a_motion() {
+        // "=>" is two `NonWord` chars of the same subtype, so it's already
+        // one token even without an atom registered -- "a=>b" instead mixes
+        // `Word` and `NonWord` chars, which jieba's own char-class pipeline
+        // always splits into separate groups.
+        let buffer: Vec<&'static str> = vec!["a=>b c"];
+
+        let plain = WordMotion::new(jieba_rs::Jieba::new());
+        let plain_out = plain.nmap_w(&buffer, (1, 0), 1, true).unwrap();
+        assert_eq!(
+            plain_out.new_cursor_pos,
+            (1, 1),
+            "without an atom, \"a\" and \"=>b\" are separate words"
+        );
+
+        let wm = WordMotion::new(jieba_rs::Jieba::new())
+            .with_atoms(crate::token::AtomMatcher::new(["a=>b"]));
+        let atom_out = wm.nmap_w(&buffer, (1, 0), 1, true).unwrap();
+        assert_eq!(
+            atom_out.new_cursor_pos,
+            (1, 5),
+            "\"a=>b\" is registered as one atom, so `w` jumps straight past it to \"c\""
+        );
+    }
+
+    #[test]
+    fn test_omap_dispatches_to_the_matching_omap_fn() {
+        let buffer: Vec<&'static str> = vec!["aaaa bbbb"];
+
+        let direct = WORD_MOTION.omap_c_w(&buffer, (1, 0), 1, true).unwrap();
+        let dispatched = WORD_MOTION
+            .omap(&buffer, (1, 0), 1, Motion::W, Operator::Change, true)
+            .unwrap();
+        assert_eq!(dispatched.new_cursor_pos, direct.new_cursor_pos);
+
+        let direct = WORD_MOTION.omap_w(&buffer, (1, 0), 1, true).unwrap();
+        let dispatched = WORD_MOTION
+            .omap(&buffer, (1, 0), 1, Motion::W, Operator::Delete, true)
+            .unwrap();
+        assert_eq!(dispatched.new_cursor_pos, direct.new_cursor_pos);
+
+        let direct = WORD_MOTION.omap_e(&buffer, (1, 0), 1, true).unwrap();
+        let dispatched = WORD_MOTION
+            .omap(&buffer, (1, 0), 1, Motion::E, Operator::Yank, true)
+            .unwrap();
+        assert_eq!(dispatched.new_cursor_pos, direct.new_cursor_pos);
+
+        let direct = WORD_MOTION.omap_b(&buffer, (1, 5), 1, true).unwrap();
+        let dispatched = WORD_MOTION
+            .omap(&buffer, (1, 5), 1, Motion::B, Operator::Delete, true)
+            .unwrap();
+        assert_eq!(dispatched.new_cursor_pos, direct);
+
+        let direct = WORD_MOTION.omap_ge(&buffer, (1, 5), 1, true).unwrap();
+        let dispatched = WORD_MOTION
+            .omap(&buffer, (1, 5), 1, Motion::Ge, Operator::Delete, true)
+            .unwrap();
+        assert_eq!(dispatched.new_cursor_pos, direct.new_cursor_pos);
+    }
 }
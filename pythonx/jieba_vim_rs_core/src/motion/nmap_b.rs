@@ -1,4 +1,4 @@
-use super::token_iter::{BackwardTokenIterator, TokenIteratorItem};
+use super::token_iter::{TokenCursor, TokenIteratorItem};
 use super::{BufferLike, WordMotion};
 use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
 
@@ -7,8 +7,12 @@ fn is_stoppable(item: &TokenIteratorItem) -> bool {
     match item.token {
         None => true,
         Some(token) => match token.ty {
-            TokenType::Word => true,
+            TokenType::Word | TokenType::Punctuation => true,
             TokenType::Space => false,
+            TokenType::Sentence => unreachable!(
+                "is_stoppable only sees tokens from WordMotion::tokens, \
+                 which never emits Sentence"
+            ),
         },
     }
 }
@@ -44,7 +48,8 @@ impl<C: JiebaPlaceholder> WordMotion<C> {
     ) -> Result<(usize, usize), B::Error> {
         let (mut lnum, mut col) = cursor_pos;
         let mut it =
-            BackwardTokenIterator::new(buffer, &self.jieba, lnum, col, word)?
+            TokenCursor::new_backward(buffer, self, lnum, col, word)?
+                .rev()
                 .peekable();
         while count > 0 && it.peek().is_some() {
             let item = it.next().unwrap()?;
@@ -122,4 +127,44 @@ mod tests {
     #[vcase(name = "large_unnecessary_count", buffer = ["}{"], count = 10293949403)]
     #[vcase(name = "large_unnecessary_count", buffer = ["}aaa  aaa{aa"], count = 10293949403)]
     mod motion_nmap_b {}
+
+    // Property-based differential test against a live Vim oracle, covering
+    // far more of the input space than the hand-written `vcase`s above.
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_nmap_b_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask, MotionCase, MotionKind, OracleQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust_cursor = crate::motion::WORD_MOTION
+                    .nmap_b(&case.buffer, case.cursor, case.count, word)
+                    .unwrap();
+                let vim_cursor = ask(OracleQuery {
+                    buffer: case.buffer.clone(),
+                    cursor: case.cursor,
+                    count: case.count,
+                    word,
+                    motion: MotionKind::B,
+                    operator: None,
+                    visual: None,
+                })
+                .cursor;
+                if rust_cursor != vim_cursor {
+                    return TestResult::error(format!(
+                        "nmap_b(word={}) on {:?} from {:?}: rust landed on {:?}, vim on {:?}",
+                        word, case.buffer, case.cursor, rust_cursor, vim_cursor,
+                    ));
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
 }
@@ -12,7 +12,7 @@
 // License for the specific language governing permissions and limitations
 // under the License.
 
-use super::token_iter::{ForwardTokenIterator, TokenIteratorItem};
+use super::token_iter::{TokenCursor, TokenIteratorItem};
 use super::{BufferLike, MotionOutput, WordMotion};
 use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
 
@@ -21,8 +21,12 @@ fn is_stoppable(item: &TokenIteratorItem) -> bool {
     match item.token {
         None => false,
         Some(token) => match token.ty {
-            TokenType::Word => true,
+            TokenType::Word | TokenType::Punctuation => true,
             TokenType::Space => false,
+            TokenType::Sentence => unreachable!(
+                "is_stoppable only sees tokens from WordMotion::tokens, \
+                 which never emits Sentence"
+            ),
         },
     }
 }
@@ -60,7 +64,7 @@ impl<C: JiebaPlaceholder> WordMotion<C> {
     ) -> Result<MotionOutput, B::Error> {
         let (mut lnum, mut col) = cursor_pos;
         let mut it =
-            ForwardTokenIterator::new(buffer, &self.jieba, lnum, col, word)?
+            TokenCursor::new_forward(buffer, self, lnum, col, word)?
                 .peekable();
         while count > 0 && it.peek().is_some() {
             let item = it.next().unwrap()?;
@@ -137,4 +141,45 @@ mod tests {
     #[vcase(name = "large_unnecessary_count", buffer = ["a{aa aaa}a"], count = 10293949403)]
     #[vcase(name = "large_unnecessary_count", buffer = ["aaa {aaa}a"], count = 10293949403)]
     mod motion_nmap_e {}
+
+    // Property-based differential test against a live Vim oracle, covering
+    // far more of the input space than the hand-written `vcase`s above.
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_nmap_e_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask, MotionCase, MotionKind, OracleQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust_cursor = crate::motion::WORD_MOTION
+                    .nmap_e(&case.buffer, case.cursor, case.count, word)
+                    .unwrap()
+                    .new_cursor_pos;
+                let vim_cursor = ask(OracleQuery {
+                    buffer: case.buffer.clone(),
+                    cursor: case.cursor,
+                    count: case.count,
+                    word,
+                    motion: MotionKind::E,
+                    operator: None,
+                    visual: None,
+                })
+                .cursor;
+                if rust_cursor != vim_cursor {
+                    return TestResult::error(format!(
+                        "nmap_e(word={}) on {:?} from {:?}: rust landed on {:?}, vim on {:?}",
+                        word, case.buffer, case.cursor, rust_cursor, vim_cursor,
+                    ));
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
 }
@@ -1,56 +1,28 @@
-// Copyright 2024 Kaiwen Wu. All Rights Reserved.
-//
-// Licensed under the Apache License, Version 2.0 (the "License"); you may not
-// use this file except in compliance with the License. You may obtain a copy
-// of the License at
-//
-//     http://www.apache.org/licenses/LICENSE-2.0
-//
-// Unless required by applicable law or agreed to in writing, software
-// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
-// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
-// License for the specific language governing permissions and limitations
-// under the License.
-
-use super::token_iter::{BackwardTokenIterator, TokenIteratorItem};
-use super::{d_special, BufferLike, MotionOutput, WordMotion};
+use super::token_iter::{TokenCursor, TokenIteratorItem};
+use super::{BufferLike, WordMotion};
 use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
 
-/// Test if a token is stoppable for `omap_d_ge`.
+/// Test if a token is stoppable for `nmap_ge`.
 fn is_stoppable(item: &TokenIteratorItem) -> bool {
     match item.token {
-        None => true,
-        Some(token) => match token.ty {
-            TokenType::Word => true,
-            TokenType::Space => false,
-        },
+        None => false,
+        Some(token) => {
+            matches!(token.ty, TokenType::Word | TokenType::Punctuation)
+        }
     }
 }
 
 impl<C: JiebaPlaceholder> WordMotion<C> {
-    /// Vim motion `ge` (if `word` is `true`) or `gE` (if `word` is `false`) in
-    /// operator-pending mode while used with operator `d`. Since Vim's help
-    /// states in section "exclusive-linewise" that:
-    ///
-    /// > When using ":" any motion becomes characterwise exclusive,
-    ///
-    /// But since `ge`/`gE` is itself inclusive, and `o_v`
-    /// (https://vimhelp.org/motion.txt.html#o_v) can be used to invert
-    /// exclusiveness to inclusiveness, we may prefix the colon command with
-    /// it and reuse most code from `nmap ge`. Note also the special case
-    /// `d-special` (https://vimhelp.org/change.txt.html#d-special), where we
-    /// have to postprocess the buffer.
-    ///
-    /// Take in current `cursor_pos` (lnum, col), and return the new cursor
-    /// position. Also return a bool indicating if `d-special` takes effect.
-    /// Note that `lnum` is 1-indexed, and `col` is 0-indexed. We denote both
-    /// `word` and `WORD` with the English word "word" below.
+    /// Vim motion `ge` (if `word` is `true`) or `gE` (if `word` is `false`)
+    /// in normal mode. Take in `cursor_pos` (lnum, col), and return the new
+    /// cursor position. Note that `lnum` is 1-indexed, and `col` is 0-indexed.
+    /// We denote both `word` and `WORD` with the English word "word" below.
     ///
     /// # Basics
     ///
-    /// `ge`/`gE` jumps to the last character of previous word. Empty line is
-    /// considered as a word. If there's no previous word except for the empty
-    /// line, issue `prevent_change` flag.
+    /// `ge`/`gE` jumps to the last character of previous word. Unlike `b`/`B`,
+    /// an empty line is *not* considered a word -- it is skipped over, never
+    /// landed on.
     ///
     /// # Edge cases
     ///
@@ -63,48 +35,30 @@ impl<C: JiebaPlaceholder> WordMotion<C> {
     ///
     /// - If current cursor `col` is to the right of the last token in current
     ///   line of the buffer.
-    pub fn omap_d_ge<B: BufferLike + ?Sized>(
+    pub fn nmap_ge<B: BufferLike + ?Sized>(
         &self,
         buffer: &B,
         cursor_pos: (usize, usize),
         mut count: u64,
         word: bool,
-    ) -> Result<MotionOutput, B::Error> {
+    ) -> Result<(usize, usize), B::Error> {
         let (mut lnum, mut col) = cursor_pos;
-        let mut prevent_change = lnum == 1 && col == 0 && count > 0;
         let mut it =
-            BackwardTokenIterator::new(buffer, &self.jieba, lnum, col, word)?
+            TokenCursor::new_backward(buffer, self, lnum, col, word)?
+                .rev()
                 .peekable();
         while count > 0 && it.peek().is_some() {
             let item = it.next().unwrap()?;
-            if !is_stoppable(&item) || item.cursor {
+            if !is_stoppable(&item) {
                 lnum = item.lnum;
-                col = item.token.first_char();
-            } else {
+                col = item.token.last_char();
+            } else if !(item.cursor && col == item.token.last_char()) {
                 lnum = item.lnum;
                 col = item.token.last_char();
                 count -= 1;
-                if it.peek().is_none() && count > 0 {
-                    col = item.token.first_char();
-                    count -= 1;
-                    if let None = item.token {
-                        prevent_change = true;
-                    }
-                }
             }
         }
-        let d_special = d_special::is_d_special(
-            buffer,
-            &self.jieba,
-            (lnum, col),
-            cursor_pos,
-            word,
-        )?;
-        Ok(MotionOutput {
-            new_cursor_pos: (lnum, col),
-            d_special,
-            prevent_change,
-        })
+        Ok((lnum, col))
     }
 }
 
@@ -116,23 +70,21 @@ mod tests {
     use jieba_vim_rs_test_macro::verified_cases_dry_run as verified_cases;
 
     #[verified_cases(
-        mode = "o",
-        operator = "d",
+        mode = "n",
         motion = "ge",
         timeout = 50,
         backend_path = "crate::motion::WORD_MOTION"
     )]
-    #[vcase(name = "empty", buffer = ["}{"], prevent_change)]
-    #[vcase(name = "space", buffer = ["}{ "], prevent_change)]
+    #[vcase(name = "empty", buffer = ["}{"])]
+    #[vcase(name = "space", buffer = ["}{ "])]
     #[vcase(name = "space", buffer = ["}   { "])]
-    #[vcase(name = "newline_newline", buffer = ["}", "{"], d_special)]
-    #[vcase(name = "newline_newline", buffer = ["}", "{"], count = 2, d_special, prevent_change)]
-    #[vcase(name = "newline_space_newline", buffer = ["}  ", "{"], d_special)]
-    #[vcase(name = "newline_space_newline", buffer = ["  ", "}", "{"], d_special)]
-    #[vcase(name = "newline_space_newline", buffer = ["}  ", "  ", "{"], d_special)]
-    #[vcase(name = "newline_space_newline", buffer = ["}  ", "   {  "], d_special)]
-    #[vcase(name = "newline_space_newline", buffer = ["  ", "}", "   {  "], d_special)]
-    #[vcase(name = "one_word", buffer = ["}{aaaa"], prevent_change)]
+    #[vcase(name = "newline_newline", buffer = ["}", "{"])]
+    #[vcase(name = "newline_space_newline", buffer = ["}  ", "{"])]
+    #[vcase(name = "newline_space_newline", buffer = ["  ", "}", "{"])]
+    #[vcase(name = "newline_space_newline", buffer = ["}  ", "  ", "{"])]
+    #[vcase(name = "newline_space_newline", buffer = ["}  ", "   {  "])]
+    #[vcase(name = "newline_space_newline", buffer = ["  ", "}", "   {  "])]
+    #[vcase(name = "one_word", buffer = ["}{aaaa"])]
     #[vcase(name = "one_word", buffer = ["}aa{aa"])]
     #[vcase(name = "one_word", buffer = ["}aaa{a"])]
     #[vcase(name = "one_word", buffer = ["}aaa{a"], count = 2)]
@@ -149,32 +101,70 @@ mod tests {
     #[vcase(name = "space_one_word_space", buffer = ["   aaa}a{   "])]
     #[vcase(name = "space_one_word_space", buffer = ["}   aaaa{   "], count = 2)]
     #[vcase(name = "one_word_newline", buffer = ["aaa}a", "{"])]
-    #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"], d_special)]
-    #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"], count = 2, d_special, prevent_change)]
-    #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"], count = 3, d_special, prevent_change)]
+    #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"])]
+    #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"], count = 2)]
     #[vcase(name = "one_word_space_newline", buffer = ["aaa}a    ", "{"])]
     #[vcase(name = "two_words_space_newline", buffer = ["aaaa aa}a    ", "  ", "{"])]
     #[vcase(name = "two_words_space_newline", buffer = ["aaaa aa}a    ", "  ", "  { "])]
-    #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaa{a"], d_special)]
-    #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaa{a"], count = 2, d_special, prevent_change)]
+    #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaa{a"])]
+    #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaa{a"], count = 2)]
     #[vcase(name = "newline_space_one_word", buffer = ["}", "   {aaaa"])]
     #[vcase(name = "newline_space_one_word", buffer = ["}", "  { aaaa"])]
     #[vcase(name = "newline_space_one_word", buffer = ["", "   aaa}a  { "])]
-    #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaaa  { "], count = 2, d_special)]
-    #[vcase(name = "space_newline_one_word", buffer = ["}     ", "aaa{a"], d_special)]
-    #[vcase(name = "space_newline_one_word", buffer = ["}     ", "", "aaa{a"], count = 2, d_special)]
-    #[vcase(name = "space_newline_one_word", buffer = ["     ", "}", "", "aaa{a"], count = 2, d_special)]
-    #[vcase(name = "space_newline_one_word", buffer = ["}     ", "", "", "aaa{a"], count = 3, d_special)]
-    #[vcase(name = "space_newline_one_word", buffer = ["}     ", " ", " ", "aaa{a"], d_special)]
+    #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaaa  { "], count = 2)]
+    #[vcase(name = "space_newline_one_word", buffer = ["}     ", "aaa{a"])]
+    #[vcase(name = "space_newline_one_word", buffer = ["}     ", "", "aaa{a"], count = 2)]
+    #[vcase(name = "space_newline_one_word", buffer = ["     ", "}", "", "aaa{a"], count = 2)]
+    #[vcase(name = "space_newline_one_word", buffer = ["}     ", "", "", "aaa{a"], count = 3)]
+    #[vcase(name = "space_newline_one_word", buffer = ["}     ", " ", " ", "aaa{a"])]
     #[vcase(name = "two_words_newline_space_newline", buffer = ["aaa aaa}a", " ", "  ", "{"])]
     #[vcase(name = "two_words_newline_space_newline", buffer = ["aa}a aaaa", " ", "  ", "{"], count = 2)]
-    #[vcase(name = "two_words_newline_space_newline", buffer = ["aaa aaaa", "}", "  ", "{"], d_special)]
+    #[vcase(name = "two_words_newline_space_newline", buffer = ["aaa aaaa", "}", "  ", "{"])]
     #[vcase(name = "two_words_newline_space_newline", buffer = ["aaa aaa}a", "", "  ", "{"], count = 2)]
-    #[vcase(name = "newline_space_newline_one_word", buffer = ["", "  ", "}", "aa{a"], d_special)]
-    #[vcase(name = "newline_space_newline_one_word", buffer = ["}", "  ", "", "aa{a"], count = 2, d_special)]
-    #[vcase(name = "newline_space_newline_one_word", buffer = ["}", "  ", "", "aa{a"], count = 3, d_special, prevent_change)]
+    #[vcase(name = "newline_space_newline_one_word", buffer = ["", "  ", "}", "aa{a"])]
+    #[vcase(name = "newline_space_newline_one_word", buffer = ["}", "  ", "", "aa{a"], count = 2)]
     #[vcase(name = "two_words_newline_one_word", buffer = ["aaaa aa}a", "", "  ", "{aaa"], count = 2)]
-    #[vcase(name = "large_unnecessary_count", buffer = ["}{"], count = 10293949403, prevent_change)]
+    #[vcase(name = "large_unnecessary_count", buffer = ["}{"], count = 10293949403)]
     #[vcase(name = "large_unnecessary_count", buffer = ["}aaa  aaa{aa"], count = 10293949403)]
-    mod motion_omap_d_ge {}
-}
\ No newline at end of file
+    mod motion_nmap_ge {}
+
+    // Property-based differential test against a live Vim oracle, covering
+    // far more of the input space than the hand-written `vcase`s above.
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_nmap_ge_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask, MotionCase, MotionKind, OracleQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust_cursor = crate::motion::WORD_MOTION
+                    .nmap_ge(&case.buffer, case.cursor, case.count, word)
+                    .unwrap();
+                let vim_cursor = ask(OracleQuery {
+                    buffer: case.buffer.clone(),
+                    cursor: case.cursor,
+                    count: case.count,
+                    word,
+                    motion: MotionKind::Ge,
+                    operator: None,
+                    visual: None,
+                })
+                .cursor;
+                if rust_cursor != vim_cursor {
+                    return TestResult::error(format!(
+                        "nmap_ge(word={}) on {:?} from {:?}: rust landed on {:?}, vim on {:?}",
+                        word, case.buffer, case.cursor, rust_cursor, vim_cursor,
+                    ));
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
+}
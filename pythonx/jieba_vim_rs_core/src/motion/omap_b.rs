@@ -215,4 +215,56 @@ mod tests {
     #[vcase(name = "large_unnecessary_count", buffer = ["}{"], count = 10293949403)]
     #[vcase(name = "large_unnecessary_count", buffer = ["}aaa  aaa{aa"], count = 10293949403)]
     mod motion_omap_y_b {}
+
+    // Property-based differential test against a live Vim oracle, covering
+    // far more of the input space than the hand-written `vcase`s above. For
+    // each operator, checks that replaying it in Vim from the cursor
+    // `omap_b` predicts reproduces exactly what Vim's own `d`/`c`/`y` + `b`/`B`
+    // does.
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_omap_b_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask, ask_replay, render_diff, MotionCase, MotionKind, OracleQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust_cursor = crate::motion::WORD_MOTION
+                    .omap_b(&case.buffer, case.cursor, case.count, word)
+                    .unwrap();
+                for operator in ['d', 'c', 'y'] {
+                    let query = OracleQuery {
+                        buffer: case.buffer.clone(),
+                        cursor: case.cursor,
+                        count: case.count,
+                        word,
+                        motion: MotionKind::B,
+                        operator: Some(operator),
+                        visual: None,
+                    };
+                    let groundtruth = ask(query.clone());
+                    let replay = ask_replay(query, rust_cursor);
+                    if groundtruth != replay {
+                        return TestResult::error(format!(
+                            "{}b(word={}) on {:?} from {:?}: rust lands on {:?}, vim on {:?}\n{}",
+                            operator,
+                            word,
+                            case.buffer,
+                            case.cursor,
+                            rust_cursor,
+                            groundtruth.cursor,
+                            render_diff(&replay.buffer, &groundtruth.buffer),
+                        ));
+                    }
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
 }
@@ -1,4 +1,4 @@
-use super::token_iter::{ForwardTokenIterator, TokenIteratorItem};
+use super::token_iter::{TokenCursor, TokenIteratorItem};
 use super::{BufferLike, MotionOutput, WordMotion};
 use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
 
@@ -7,8 +7,12 @@ fn is_stoppable(item: &TokenIteratorItem) -> bool {
     match item.token {
         None => true,
         Some(token) => match token.ty {
-            TokenType::Word => true,
+            TokenType::Word | TokenType::Punctuation => true,
             TokenType::Space => false,
+            TokenType::Sentence => unreachable!(
+                "is_stoppable only sees tokens from WordMotion::tokens, \
+                 which never emits Sentence"
+            ),
         },
     }
 }
@@ -18,8 +22,12 @@ fn is_stoppable_ce_mode(item: &TokenIteratorItem) -> bool {
     match item.token {
         None => false,
         Some(token) => match token.ty {
-            TokenType::Word => true,
+            TokenType::Word | TokenType::Punctuation => true,
             TokenType::Space => false,
+            TokenType::Sentence => unreachable!(
+                "is_stoppable_ce_mode only sees tokens from WordMotion::tokens, \
+                 which never emits Sentence"
+            ),
         },
     }
 }
@@ -69,7 +77,7 @@ impl<C: JiebaPlaceholder> WordMotion<C> {
         // ["{abcd}  "], 1;
         let (mut lnum, mut col) = cursor_pos;
         let mut it =
-            ForwardTokenIterator::new(buffer, &self.jieba, lnum, col, word)?
+            TokenCursor::new_forward(buffer, self, lnum, col, word)?
                 .peekable();
         let mut cursor_starts_at_word: Option<bool> = None;
         while count > 0 && it.peek().is_some() {
@@ -214,4 +222,51 @@ mod tests {
     #[vcase(name = "word_newline_counts", buffer = ["ab{cd  efg", " ", "  hij}   ", ""], count = 3)]
     #[vcase(name = "word_newline_counts", buffer = ["ab{cd  efg", " ", "  ", "  ", "  hij}  ", "  ", ""], count = 3)]
     mod motion_omap_c_w {}
+
+    // Property-based differential test against a live Vim oracle, covering
+    // far more of the input space than the hand-written `vcase`s above.
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_omap_c_w_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask, ask_replay, render_diff, MotionCase, MotionKind, OracleQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust_cursor = crate::motion::WORD_MOTION
+                    .omap_c_w(&case.buffer, case.cursor, case.count, word)
+                    .unwrap()
+                    .new_cursor_pos;
+                let query = OracleQuery {
+                    buffer: case.buffer.clone(),
+                    cursor: case.cursor,
+                    count: case.count,
+                    word,
+                    motion: MotionKind::W,
+                    operator: Some('c'),
+                    visual: None,
+                };
+                let groundtruth = ask(query.clone());
+                let replay = ask_replay(query, rust_cursor);
+                if groundtruth != replay {
+                    return TestResult::error(format!(
+                        "cw(word={}) on {:?} from {:?}: rust lands on {:?}, vim on {:?}\n{}",
+                        word,
+                        case.buffer,
+                        case.cursor,
+                        rust_cursor,
+                        groundtruth.cursor,
+                        render_diff(&replay.buffer, &groundtruth.buffer),
+                    ));
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
 }
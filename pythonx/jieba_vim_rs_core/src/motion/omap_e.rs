@@ -12,20 +12,28 @@
 // License for the specific language governing permissions and limitations
 // under the License.
 
-use super::{BufferLike, WordMotion};
-use crate::token::JiebaPlaceholder;
+use super::{index_tokens, operator_range, BufferLike, MotionOutput, OperatorRange, WordMotion};
+use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
 
 impl<C: JiebaPlaceholder> WordMotion<C> {
     /// Vim motion `e` (if `word` is `true`) or `E` (if `word` is `false`) in
-    /// operator-pending mode while used with operator `d`. Since Vim's help
-    /// states in section "exclusive-linewise" that:
+    /// operator-pending mode, shared by all three operators (`c`/`d`/`y`).
+    /// Since Vim's help states in section "exclusive-linewise" that:
     ///
     /// > When using ":" any motion becomes characterwise exclusive,
     ///
     /// But since `e`/`E` is itself inclusive, and `o_v`
     /// (https://vimhelp.org/motion.txt.html#o_v) can be used to invert
     /// exclusiveness to inclusiveness, we may use prefix the colon command
-    /// with it and reuse most code from `nmap e`.
+    /// with it and reuse most code from `nmap e`. Note also the special case
+    /// `d-special` (https://vimhelp.org/change.txt.html#d-special), which
+    /// only `d`/`y` care about -- `c`'s caller simply ignores
+    /// [`MotionOutput::d_special`].
+    ///
+    /// Take in current `cursor_pos` (lnum, col), and return the new cursor
+    /// position together with whether `d-special` takes effect. Note that
+    /// `lnum` is 1-indexed, and `col` is 0-indexed. We denote both `word` and
+    /// `WORD` with the English word "word" below.
     ///
     /// # Basics
     ///
@@ -50,8 +58,104 @@ impl<C: JiebaPlaceholder> WordMotion<C> {
         cursor_pos: (usize, usize),
         count: u64,
         word: bool,
-    ) -> Result<(usize, usize), B::Error> {
-        self.nmap_e(buffer, cursor_pos, count, word)
+    ) -> Result<MotionOutput, B::Error> {
+        let new_cursor_pos = self.nmap_e(buffer, cursor_pos, count, word)?;
+        let (lnum, col) = cursor_pos;
+        let (new_lnum, new_col) = new_cursor_pos;
+
+        if lnum == new_lnum {
+            return Ok(MotionOutput {
+                new_cursor_pos,
+                d_special: false,
+                prevent_change: false,
+            });
+        }
+
+        let tokens_cursor_line = self.tokens(buffer, lnum, word)?;
+        if !tokens_cursor_line.is_empty() {
+            let i = index_tokens(&tokens_cursor_line, col).unwrap();
+            if tokens_cursor_line[..i].iter().any(|tok| match tok.ty {
+                TokenType::Space => false,
+                TokenType::Word | TokenType::Punctuation => true,
+                TokenType::Sentence => unreachable!(
+                    "tokens here come from WordMotion::tokens, which never \
+                     emits Sentence"
+                ),
+            }) {
+                return Ok(MotionOutput {
+                    new_cursor_pos,
+                    d_special: false,
+                    prevent_change: false,
+                });
+            }
+            let cursor_token = &tokens_cursor_line[i];
+            if matches!(cursor_token.ty, TokenType::Word | TokenType::Punctuation) {
+                if col > cursor_token.first_char() {
+                    return Ok(MotionOutput {
+                        new_cursor_pos,
+                        d_special: false,
+                        prevent_change: false,
+                    });
+                }
+            }
+        }
+
+        let tokens_new_cursor_line = self.tokens(buffer, new_lnum, word)?;
+        if !tokens_new_cursor_line.is_empty() {
+            let j = index_tokens(&tokens_new_cursor_line, new_col).unwrap();
+            if tokens_new_cursor_line[j + 1..]
+                .iter()
+                .any(|tok| match tok.ty {
+                    TokenType::Space => false,
+                    TokenType::Word | TokenType::Punctuation => true,
+                    TokenType::Sentence => unreachable!(
+                        "tokens here come from WordMotion::tokens, which \
+                         never emits Sentence"
+                    ),
+                })
+            {
+                return Ok(MotionOutput {
+                    new_cursor_pos,
+                    d_special: false,
+                    prevent_change: false,
+                });
+            }
+            let new_cursor_token = &tokens_new_cursor_line[j];
+            if matches!(
+                new_cursor_token.ty,
+                TokenType::Word | TokenType::Punctuation
+            ) {
+                if new_col < new_cursor_token.last_char() {
+                    return Ok(MotionOutput {
+                        new_cursor_pos,
+                        d_special: false,
+                        prevent_change: false,
+                    });
+                }
+            }
+        }
+
+        Ok(MotionOutput {
+            new_cursor_pos,
+            d_special: true,
+            prevent_change: false,
+        })
+    }
+
+    /// Like [`Self::omap_e`], but also returns the [`OperatorRange`] the
+    /// motion determined should be acted on, so a caller can delete/yank/
+    /// change exactly that span without re-deriving the `d-special` linewise
+    /// promotion itself.
+    pub fn omap_e_range<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        count: u64,
+        word: bool,
+    ) -> Result<(MotionOutput, OperatorRange), B::Error> {
+        let output = self.omap_e(buffer, cursor_pos, count, word)?;
+        let range = operator_range(cursor_pos, output.new_cursor_pos, output.d_special);
+        Ok((output, range))
     }
 }
 
@@ -62,130 +166,216 @@ mod tests {
     #[cfg(not(feature = "verifiable_case"))]
     use jieba_vim_rs_test_macro::verified_cases_dry_run as verified_cases;
 
-    #[verified_cases(
-        mode = "o",
-        operator = "c",
-        motion = "e",
-        timeout = 50,
-        backend_path = "crate::motion::WORD_MOTION"
-    )]
-    #[vcase(name = "empty", buffer = ["{}"])]
-    #[vcase(name = "one_word", buffer = ["abc{}d"])]
-    #[vcase(name = "one_word", buffer = ["abc{}d"], count = 2)]
-    #[vcase(name = "one_word", buffer = ["a{bc}d"])]
-    #[vcase(name = "one_word", buffer = ["a{bc}d"], count = 2)]
-    #[vcase(name = "one_word_space", buffer = ["a{bc}d    "])]
-    #[vcase(name = "one_word_space", buffer = ["a{bcd   } "], count = 2)]
-    #[vcase(name = "one_word_space", buffer = ["abc{d   } "])]
-    #[vcase(name = "one_word_space", buffer = ["abc{d   } "], count = 2)]
-    #[vcase(name = "one_word_space", buffer = ["abcd {  } "])]
-    #[vcase(name = "one_word_space", buffer = ["abcd {  } "], count = 2)]
-    #[vcase(name = "space_word", buffer = ["{    ab}c"])]
-    #[vcase(name = "space_word", buffer = [" {   ab}c"])]
-    #[vcase(name = "space_word", buffer = ["{    ab}c  def"])]
-    #[vcase(name = "space_word", buffer = ["{    abc  de}f"], count = 2)]
-    #[vcase(name = "space_word", buffer = ["{    abc  de}f"], count = 3)]
-    #[vcase(name = "two_words", buffer = ["a{bc}d  efg"])]
-    #[vcase(name = "two_words", buffer = ["a{bcd  ef}g"], count = 2)]
-    #[vcase(name = "two_words", buffer = ["a{bcd  ef}g"], count = 3)]
-    #[vcase(name = "two_words", buffer = ["abc{d ef}g"])]
-    #[vcase(name = "two_words", buffer = ["abc{d ef}g"], count = 2)]
-    #[vcase(name = "two_words", buffer = ["abc{d efg  } "], count = 3)]
-    #[vcase(name = "one_word_newline", buffer = ["a{bc}d", ""])]
-    #[vcase(name = "one_word_newline", buffer = ["a{bcd", "}"], count = 2)]
-    #[vcase(name = "one_word_newline", buffer = ["abc{d", "}"])]
-    #[vcase(name = "newline_one_word", buffer = ["{", "", "abc}d"])]
-    #[vcase(name = "newline_one_word", buffer = ["{", "  ", "abc}d"])]
-    #[vcase(name = "newline_two_words", buffer = ["{", "", "abc}d", "efg"])]
-    #[vcase(name = "newline_one_word_space", buffer = ["{", "", "abc}d    "])]
-    #[vcase(name = "newline_one_word_space_word", buffer = ["{", "", "abc}d    e"])]
-    #[vcase(name = "word_newline_newline", buffer = ["abcd", "{   ", "  } "])]
-    #[vcase(name = "word_newline_newline", buffer = ["abcd", "{   ", "  } "], count = 2)]
-    #[vcase(name = "one_word_space_newline", buffer = ["a{bc}d    ", ""])]
-    #[vcase(name = "one_word_space_newline", buffer = ["abc{d     ", "}"])]
-    #[vcase(name = "one_word_space_newline", buffer = ["abcd{    ", "}"])]
-    #[vcase(name = "one_word_space_newline", buffer = ["abcd {   ", "}"])]
-    #[vcase(name = "one_word_newline_space", buffer = ["abc{d", "   } "])]
-    #[vcase(name = "one_word_newline_space", buffer = ["abc{d", "  ", "   } "])]
-    #[vcase(name = "one_word_newline_space", buffer = ["abcd", "{  ", "   } "])]
-    #[vcase(name = "one_word_newline_space", buffer = ["abc{d", "", "   } "])]
-    #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", " ", "}"])]
-    #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", " ", " ", "}"])]
-    #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", "", " ", "}"])]
-    #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", " ", "", "}"])]
-    #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", "", "", "}"])]
-    #[vcase(name = "word_newline_word", buffer = ["a{bc}d", "", " ", "", "efg"])]
-    #[vcase(name = "word_newline_word", buffer = ["abc{d", "", " ", "", "ef}g  "])]
-    #[vcase(name = "word_newline_word", buffer = ["abc{d", "  ", "", " ", "efg}h"])]
-    #[vcase(name = "word_newline_word", buffer = ["abc{d", "", "ef}g", "", "efgh"])]
-    #[vcase(name = "word_newline_word", buffer = ["abc{d", "", "efg", "", "efg}h"], count = 2)]
-    #[vcase(name = "word_newline_word", buffer = ["abc{d", "", "efg", "", "efg}h  "], count = 2)]
-    #[vcase(name = "large_unnecessary_count", buffer = ["{}"], count = 10293949403)]
-    #[vcase(name = "large_unnecessary_count", buffer = ["a{bc def}g"], count = 10293949403)]
-    #[vcase(name = "large_unnecessary_count", buffer = ["abc {def}g"], count = 10293949403)]
-    mod motion_omap_c_e {}
-
-    // Copied from omap_c_e above.
-    #[verified_cases(
-        mode = "o",
-        operator = "y",
-        motion = "e",
-        timeout = 50,
-        backend_path = "crate::motion::WORD_MOTION"
-    )]
-    #[vcase(name = "empty", buffer = ["{}"])]
-    #[vcase(name = "one_word", buffer = ["abc{}d"])]
-    #[vcase(name = "one_word", buffer = ["abc{}d"], count = 2)]
-    #[vcase(name = "one_word", buffer = ["a{bc}d"])]
-    #[vcase(name = "one_word", buffer = ["a{bc}d"], count = 2)]
-    #[vcase(name = "one_word_space", buffer = ["a{bc}d    "])]
-    #[vcase(name = "one_word_space", buffer = ["a{bcd   } "], count = 2)]
-    #[vcase(name = "one_word_space", buffer = ["abc{d   } "])]
-    #[vcase(name = "one_word_space", buffer = ["abc{d   } "], count = 2)]
-    #[vcase(name = "one_word_space", buffer = ["abcd {  } "])]
-    #[vcase(name = "one_word_space", buffer = ["abcd {  } "], count = 2)]
-    #[vcase(name = "space_word", buffer = ["{    ab}c"])]
-    #[vcase(name = "space_word", buffer = [" {   ab}c"])]
-    #[vcase(name = "space_word", buffer = ["{    ab}c  def"])]
-    #[vcase(name = "space_word", buffer = ["{    abc  de}f"], count = 2)]
-    #[vcase(name = "space_word", buffer = ["{    abc  de}f"], count = 3)]
-    #[vcase(name = "two_words", buffer = ["a{bc}d  efg"])]
-    #[vcase(name = "two_words", buffer = ["a{bcd  ef}g"], count = 2)]
-    #[vcase(name = "two_words", buffer = ["a{bcd  ef}g"], count = 3)]
-    #[vcase(name = "two_words", buffer = ["abc{d ef}g"])]
-    #[vcase(name = "two_words", buffer = ["abc{d ef}g"], count = 2)]
-    #[vcase(name = "two_words", buffer = ["abc{d efg  } "], count = 3)]
-    #[vcase(name = "one_word_newline", buffer = ["a{bc}d", ""])]
-    #[vcase(name = "one_word_newline", buffer = ["a{bcd", "}"], count = 2)]
-    #[vcase(name = "one_word_newline", buffer = ["abc{d", "}"])]
-    #[vcase(name = "newline_one_word", buffer = ["{", "", "abc}d"])]
-    #[vcase(name = "newline_one_word", buffer = ["{", "  ", "abc}d"])]
-    #[vcase(name = "newline_two_words", buffer = ["{", "", "abc}d", "efg"])]
-    #[vcase(name = "newline_one_word_space", buffer = ["{", "", "abc}d    "])]
-    #[vcase(name = "newline_one_word_space_word", buffer = ["{", "", "abc}d    e"])]
-    #[vcase(name = "word_newline_newline", buffer = ["abcd", "{   ", "  } "])]
-    #[vcase(name = "word_newline_newline", buffer = ["abcd", "{   ", "  } "], count = 2)]
-    #[vcase(name = "one_word_space_newline", buffer = ["a{bc}d    ", ""])]
-    #[vcase(name = "one_word_space_newline", buffer = ["abc{d     ", "}"])]
-    #[vcase(name = "one_word_space_newline", buffer = ["abcd{    ", "}"])]
-    #[vcase(name = "one_word_space_newline", buffer = ["abcd {   ", "}"])]
-    #[vcase(name = "one_word_newline_space", buffer = ["abc{d", "   } "])]
-    #[vcase(name = "one_word_newline_space", buffer = ["abc{d", "  ", "   } "])]
-    #[vcase(name = "one_word_newline_space", buffer = ["abcd", "{  ", "   } "])]
-    #[vcase(name = "one_word_newline_space", buffer = ["abc{d", "", "   } "])]
-    #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", " ", "}"])]
-    #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", " ", " ", "}"])]
-    #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", "", " ", "}"])]
-    #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", " ", "", "}"])]
-    #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", "", "", "}"])]
-    #[vcase(name = "word_newline_word", buffer = ["a{bc}d", "", " ", "", "efg"])]
-    #[vcase(name = "word_newline_word", buffer = ["abc{d", "", " ", "", "ef}g  "])]
-    #[vcase(name = "word_newline_word", buffer = ["abc{d", "  ", "", " ", "efg}h"])]
-    #[vcase(name = "word_newline_word", buffer = ["abc{d", "", "ef}g", "", "efgh"])]
-    #[vcase(name = "word_newline_word", buffer = ["abc{d", "", "efg", "", "efg}h"], count = 2)]
-    #[vcase(name = "word_newline_word", buffer = ["abc{d", "", "efg", "", "efg}h  "], count = 2)]
-    #[vcase(name = "large_unnecessary_count", buffer = ["{}"], count = 10293949403)]
-    #[vcase(name = "large_unnecessary_count", buffer = ["a{bc def}g"], count = 10293949403)]
-    #[vcase(name = "large_unnecessary_count", buffer = ["abc {def}g"], count = 10293949403)]
-    mod motion_omap_y_e {}
+    // The three operators (`c`/`d`/`y`) share every `vcase` except the
+    // handful that exercise `d_special`, which only `d` is allowed to
+    // annotate (the `#[vcase(d_special)]` flag is a compile error under any
+    // other operator -- see `motion_reads_d_special` in
+    // `jieba_vim_rs_test_macro`). `omap_e_vcases!` takes those two
+    // differing groups as arguments so the rest of the table is written
+    // once and shared by all three `mod` blocks below, instead of being
+    // copy-pasted per operator.
+    macro_rules! omap_e_vcases {
+        (
+            $modname:ident,
+            $op:literal,
+            { $(#[vcase($($newline_args:tt)*)])* },
+            { $(#[vcase($($wnl_args:tt)*)])* }
+        ) => {
+            #[verified_cases(
+                mode = "o",
+                operator = $op,
+                motion = "e",
+                timeout = 50,
+                backend_path = "crate::motion::WORD_MOTION"
+            )]
+            #[vcase(name = "empty", buffer = ["{}"])]
+            #[vcase(name = "one_word", buffer = ["abc{}d"])]
+            #[vcase(name = "one_word", buffer = ["abc{}d"], count = 2)]
+            #[vcase(name = "one_word", buffer = ["a{bc}d"])]
+            #[vcase(name = "one_word", buffer = ["a{bc}d"], count = 2)]
+            #[vcase(name = "one_word_space", buffer = ["a{bc}d    "])]
+            #[vcase(name = "one_word_space", buffer = ["a{bcd   } "], count = 2)]
+            #[vcase(name = "one_word_space", buffer = ["abc{d   } "])]
+            #[vcase(name = "one_word_space", buffer = ["abc{d   } "], count = 2)]
+            #[vcase(name = "one_word_space", buffer = ["abcd {  } "])]
+            #[vcase(name = "one_word_space", buffer = ["abcd {  } "], count = 2)]
+            #[vcase(name = "space_word", buffer = ["{    ab}c"])]
+            #[vcase(name = "space_word", buffer = [" {   ab}c"])]
+            #[vcase(name = "space_word", buffer = ["{    ab}c  def"])]
+            #[vcase(name = "space_word", buffer = ["{    abc  de}f"], count = 2)]
+            #[vcase(name = "space_word", buffer = ["{    abc  de}f"], count = 3)]
+            #[vcase(name = "two_words", buffer = ["a{bc}d  efg"])]
+            #[vcase(name = "two_words", buffer = ["a{bcd  ef}g"], count = 2)]
+            #[vcase(name = "two_words", buffer = ["a{bcd  ef}g"], count = 3)]
+            #[vcase(name = "two_words", buffer = ["abc{d ef}g"])]
+            #[vcase(name = "two_words", buffer = ["abc{d ef}g"], count = 2)]
+            #[vcase(name = "two_words", buffer = ["abc{d efg  } "], count = 3)]
+            #[vcase(name = "one_word_newline", buffer = ["a{bc}d", ""])]
+            #[vcase(name = "one_word_newline", buffer = ["a{bcd", "}"], count = 2)]
+            #[vcase(name = "one_word_newline", buffer = ["abc{d", "}"])]
+            $(#[vcase($($newline_args)*)])*
+            #[vcase(name = "newline_one_word_space", buffer = ["{", "", "abc}d    "])]
+            #[vcase(name = "newline_one_word_space_word", buffer = ["{", "", "abc}d    e"])]
+            $(#[vcase($($wnl_args)*)])*
+            #[vcase(name = "one_word_space_newline", buffer = ["a{bc}d    ", ""])]
+            #[vcase(name = "one_word_space_newline", buffer = ["abc{d     ", "}"])]
+            #[vcase(name = "one_word_space_newline", buffer = ["abcd{    ", "}"])]
+            #[vcase(name = "one_word_space_newline", buffer = ["abcd {   ", "}"])]
+            #[vcase(name = "one_word_newline_space", buffer = ["abc{d", "   } "])]
+            #[vcase(name = "one_word_newline_space", buffer = ["abc{d", "  ", "   } "])]
+            #[vcase(name = "one_word_newline_space", buffer = ["abcd", "{  ", "   } "])]
+            #[vcase(name = "one_word_newline_space", buffer = ["abc{d", "", "   } "])]
+            #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", " ", "}"])]
+            #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", " ", " ", "}"])]
+            #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", "", " ", "}"])]
+            #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", " ", "", "}"])]
+            #[vcase(name = "one_word_newline_space_newline", buffer = ["abc{d", "", "", "}"])]
+            #[vcase(name = "word_newline_word", buffer = ["a{bc}d", "", " ", "", "efg"])]
+            #[vcase(name = "word_newline_word", buffer = ["abc{d", "", " ", "", "ef}g  "])]
+            #[vcase(name = "word_newline_word", buffer = ["abc{d", "  ", "", " ", "efg}h"])]
+            #[vcase(name = "word_newline_word", buffer = ["abc{d", "", "ef}g", "", "efgh"])]
+            #[vcase(name = "word_newline_word", buffer = ["abc{d", "", "efg", "", "efg}h"], count = 2)]
+            #[vcase(name = "word_newline_word", buffer = ["abc{d", "", "efg", "", "efg}h  "], count = 2)]
+            #[vcase(name = "large_unnecessary_count", buffer = ["{}"], count = 10293949403)]
+            #[vcase(name = "large_unnecessary_count", buffer = ["a{bc def}g"], count = 10293949403)]
+            #[vcase(name = "large_unnecessary_count", buffer = ["abc {def}g"], count = 10293949403)]
+            mod $modname {}
+        };
+    }
+
+    omap_e_vcases!(
+        motion_omap_c_e,
+        "c",
+        {
+            #[vcase(name = "newline_one_word", buffer = ["{", "", "abc}d"])]
+            #[vcase(name = "newline_one_word", buffer = ["{", "  ", "abc}d"])]
+            #[vcase(name = "newline_two_words", buffer = ["{", "", "abc}d", "efg"])]
+        },
+        {
+            #[vcase(name = "word_newline_newline", buffer = ["abcd", "{   ", "  } "])]
+            #[vcase(name = "word_newline_newline", buffer = ["abcd", "{   ", "  } "], count = 2)]
+        }
+    );
+
+    omap_e_vcases!(
+        motion_omap_y_e,
+        "y",
+        {
+            #[vcase(name = "newline_one_word", buffer = ["{", "", "abc}d"])]
+            #[vcase(name = "newline_one_word", buffer = ["{", "  ", "abc}d"])]
+            #[vcase(name = "newline_two_words", buffer = ["{", "", "abc}d", "efg"])]
+        },
+        {
+            #[vcase(name = "word_newline_newline", buffer = ["abcd", "{   ", "  } "])]
+            #[vcase(name = "word_newline_newline", buffer = ["abcd", "{   ", "  } "], count = 2)]
+        }
+    );
+
+    omap_e_vcases!(
+        motion_omap_d_e,
+        "d",
+        {
+            #[vcase(name = "newline_one_word", buffer = ["{", "abc}d"], d_special)]
+            #[vcase(name = "newline_one_word", buffer = ["{", "", "abc}d"], d_special)]
+            #[vcase(name = "newline_one_word", buffer = ["{", "  ", "abc}d"], d_special)]
+            #[vcase(name = "newline_two_words", buffer = ["{", "", "abc}d", "efg"], d_special)]
+        },
+        {
+            #[vcase(name = "word_newline_newline", buffer = ["abcd", "{   ", "  } "], d_special)]
+            #[vcase(name = "word_newline_newline", buffer = ["abcd", "{   ", "  } "], count = 2, d_special)]
+        }
+    );
+
+    // Property-based differential test against a live Vim oracle, covering
+    // far more of the input space than the hand-written `vcase`s above.
+    // Checks all three operators, since `omap_e` now computes `d_special`
+    // for every caller rather than only the `d`/`y` paths.
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_omap_e_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask, ask_replay, render_diff, MotionCase, MotionKind, OracleQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust_output = crate::motion::WORD_MOTION
+                    .omap_e(&case.buffer, case.cursor, case.count, word)
+                    .unwrap();
+                for operator in ['c', 'd', 'y'] {
+                    let query = OracleQuery {
+                        buffer: case.buffer.clone(),
+                        cursor: case.cursor,
+                        count: case.count,
+                        word,
+                        motion: MotionKind::E,
+                        operator: Some(operator),
+                        visual: None,
+                    };
+                    let groundtruth = ask(query.clone());
+                    let replay = ask_replay(query, rust_output.new_cursor_pos);
+                    if groundtruth != replay {
+                        return TestResult::error(format!(
+                            "{}e(word={}) on {:?} from {:?}: rust lands on {:?}, vim on {:?}\n{}",
+                            operator,
+                            word,
+                            case.buffer,
+                            case.cursor,
+                            rust_output.new_cursor_pos,
+                            groundtruth.cursor,
+                            render_diff(&replay.buffer, &groundtruth.buffer),
+                        ));
+                    }
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
+
+    #[test]
+    fn omap_e_range_is_inclusive_charwise_without_d_special() {
+        use super::super::OperatorRange;
+
+        let buffer: Vec<&'static str> = vec!["abc def"];
+        let (output, range) = crate::motion::WORD_MOTION
+            .omap_e_range(&buffer, (1, 0), 1, true)
+            .unwrap();
+        assert_eq!(output.new_cursor_pos, (1, 2));
+        assert!(!output.d_special);
+        assert_eq!(
+            range,
+            OperatorRange {
+                start: (1, 0),
+                end: (1, 2),
+                linewise: false,
+                inclusive: true,
+            }
+        );
+    }
+
+    #[test]
+    fn omap_e_range_is_linewise_excluding_last_line_on_d_special() {
+        use super::super::OperatorRange;
+
+        let buffer: Vec<&'static str> = vec!["", "", "abcd"];
+        let (output, range) = crate::motion::WORD_MOTION
+            .omap_e_range(&buffer, (1, 0), 1, true)
+            .unwrap();
+        assert_eq!(output.new_cursor_pos, (3, 3));
+        assert!(output.d_special);
+        assert_eq!(
+            range,
+            OperatorRange {
+                start: (1, 0),
+                end: (2, 0),
+                linewise: true,
+                inclusive: true,
+            }
+        );
+    }
 }
@@ -0,0 +1,433 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use super::token_iter::{TokenCursor, TokenIteratorItem};
+use super::{d_special, operator_range, BufferLike, MotionOutput, OperatorRange, WordMotion};
+use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
+
+/// Test if a token is stoppable for `omap_ge`. Unlike [`super::nmap_ge`]'s
+/// own `is_stoppable`, an empty line *is* stoppable here -- see the doc
+/// comment on [`WordMotion::omap_ge`] below.
+fn is_stoppable(item: &TokenIteratorItem) -> bool {
+    match item.token {
+        None => true,
+        Some(token) => match token.ty {
+            TokenType::Word | TokenType::Punctuation => true,
+            TokenType::Space => false,
+            TokenType::Sentence => unreachable!(
+                "is_stoppable only sees tokens from WordMotion::tokens, \
+                 which never emits Sentence"
+            ),
+        },
+    }
+}
+
+impl<C: JiebaPlaceholder> WordMotion<C> {
+    /// Vim motion `ge` (if `word` is `true`) or `gE` (if `word` is `false`)
+    /// in operator-pending mode, shared by all three operators (`c`/`d`/`y`).
+    /// Since Vim's help states in section "exclusive-linewise" that:
+    ///
+    /// > When using ":" any motion becomes characterwise exclusive,
+    ///
+    /// But since `ge`/`gE` is itself inclusive, and `o_v`
+    /// (https://vimhelp.org/motion.txt.html#o_v) can be used to invert
+    /// exclusiveness to inclusiveness, we may prefix the colon command with
+    /// it and reuse most code from `nmap ge`. Note also the special case
+    /// `d-special` (https://vimhelp.org/change.txt.html#d-special), which
+    /// only `d`/`y` care about -- `c`'s caller simply ignores
+    /// [`MotionOutput::d_special`].
+    ///
+    /// Take in current `cursor_pos` (lnum, col), and return the new cursor
+    /// position together with whether `d-special` takes effect. Note that
+    /// `lnum` is 1-indexed, and `col` is 0-indexed. We denote both `word` and
+    /// `WORD` with the English word "word" below.
+    ///
+    /// # Basics
+    ///
+    /// `ge`/`gE` jumps to the last character of previous word. Empty line is
+    /// considered as a word. If there's no previous word except for the
+    /// empty line, issue `prevent_change` flag.
+    ///
+    /// # Edge cases
+    ///
+    /// - If current cursor is on the first character of the first token in the
+    ///   buffer, no further jump should be made.
+    /// - If there is no previous word to the left of current cursor, jump to
+    ///   the first character of the first token in the buffer.
+    ///
+    /// # Panics
+    ///
+    /// - If current cursor `col` is to the right of the last token in current
+    ///   line of the buffer.
+    pub fn omap_ge<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        mut count: u64,
+        word: bool,
+    ) -> Result<MotionOutput, B::Error> {
+        let (mut lnum, mut col) = cursor_pos;
+        let mut prevent_change = lnum == 1 && col == 0 && count > 0;
+        let mut it = TokenCursor::new_backward(buffer, self, lnum, col, word)?
+            .rev()
+            .peekable();
+        while count > 0 && it.peek().is_some() {
+            let item = it.next().unwrap()?;
+            if !is_stoppable(&item) || item.cursor {
+                lnum = item.lnum;
+                col = item.token.first_char();
+            } else {
+                lnum = item.lnum;
+                col = item.token.last_char();
+                count -= 1;
+                if it.peek().is_none() && count > 0 {
+                    col = item.token.first_char();
+                    count -= 1;
+                    if let None = item.token {
+                        prevent_change = true;
+                    }
+                }
+            }
+        }
+        let d_special = d_special::is_d_special(buffer, self, (lnum, col), cursor_pos, word)?;
+        Ok(MotionOutput {
+            new_cursor_pos: (lnum, col),
+            d_special,
+            prevent_change,
+        })
+    }
+
+    /// Like [`Self::omap_ge`], but also returns the [`OperatorRange`] the
+    /// motion determined should be acted on, so a caller can delete/yank/
+    /// change exactly that span without re-deriving the `d-special` linewise
+    /// promotion itself.
+    pub fn omap_ge_range<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        count: u64,
+        word: bool,
+    ) -> Result<(MotionOutput, OperatorRange), B::Error> {
+        let output = self.omap_ge(buffer, cursor_pos, count, word)?;
+        let range = operator_range(cursor_pos, output.new_cursor_pos, output.d_special);
+        Ok((output, range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "verifiable_case")]
+    use jieba_vim_rs_test_macro::verified_cases;
+    #[cfg(not(feature = "verifiable_case"))]
+    use jieba_vim_rs_test_macro::verified_cases_dry_run as verified_cases;
+
+    // The three operators (`c`/`d`/`y`) share every `vcase` except the
+    // handful that exercise `d_special`, which only `d` is allowed to
+    // annotate (the `#[vcase(d_special)]` flag is a compile error under any
+    // other operator -- see `motion_reads_d_special` in
+    // `jieba_vim_rs_test_macro`), plus the `d`-only `count` variants that
+    // only matter once `d_special`'s linewise bookkeeping is in play.
+    // `omap_ge_vcases!` takes those differing groups as arguments so the
+    // rest of the table is written once and shared by all three `mod`
+    // blocks below, instead of being copy-pasted per operator.
+    macro_rules! omap_ge_vcases {
+        (
+            $modname:ident,
+            $op:literal,
+            { $(#[vcase($($group1:tt)*)])* },
+            { $(#[vcase($($group2:tt)*)])* },
+            { $(#[vcase($($group3:tt)*)])* },
+            { $(#[vcase($($group4:tt)*)])* },
+            { $(#[vcase($($group5:tt)*)])* },
+            { $(#[vcase($($group6:tt)*)])* },
+            { $(#[vcase($($group7:tt)*)])* },
+            { $(#[vcase($($group8:tt)*)])* }
+        ) => {
+            #[verified_cases(
+                mode = "o",
+                operator = $op,
+                motion = "ge",
+                timeout = 50,
+                backend_path = "crate::motion::WORD_MOTION"
+            )]
+            $(#[vcase($($group1)*)])*
+            #[vcase(name = "space", buffer = ["}   { "])]
+            $(#[vcase($($group2)*)])*
+            #[vcase(name = "one_word", buffer = ["}aa{aa"])]
+            #[vcase(name = "one_word", buffer = ["}aaa{a"])]
+            #[vcase(name = "one_word", buffer = ["}aaa{a"], count = 2)]
+            #[vcase(name = "one_word_space", buffer = ["aaa}a{   "])]
+            #[vcase(name = "one_word_space", buffer = ["aaa}a  { "])]
+            #[vcase(name = "space_one_word", buffer = ["}   aaa{a"])]
+            #[vcase(name = "space_one_word", buffer = ["}   aaa{a"], count = 2)]
+            #[vcase(name = "space_one_word", buffer = ["}   {aaaa"])]
+            #[vcase(name = "two_words", buffer = ["aaa}a  {aaa"])]
+            #[vcase(name = "two_words", buffer = ["aaa}a  aa{a"])]
+            #[vcase(name = "two_words", buffer = ["}aaaa  aa{a"], count = 2)]
+            #[vcase(name = "space_one_word_space", buffer = ["   aaa}a  { "])]
+            #[vcase(name = "space_one_word_space", buffer = ["}   aaaa  { "], count = 2)]
+            #[vcase(name = "space_one_word_space", buffer = ["   aaa}a{   "])]
+            #[vcase(name = "space_one_word_space", buffer = ["}   aaaa{   "], count = 2)]
+            #[vcase(name = "one_word_newline", buffer = ["aaa}a", "{"])]
+            $(#[vcase($($group3)*)])*
+            #[vcase(name = "one_word_space_newline", buffer = ["aaa}a    ", "{"])]
+            #[vcase(name = "two_words_space_newline", buffer = ["aaaa aa}a    ", "  ", "{"])]
+            #[vcase(name = "two_words_space_newline", buffer = ["aaaa aa}a    ", "  ", "  { "])]
+            $(#[vcase($($group4)*)])*
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "   {aaaa"])]
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "  { aaaa"])]
+            #[vcase(name = "newline_space_one_word", buffer = ["", "   aaa}a  { "])]
+            $(#[vcase($($group5)*)])*
+            #[vcase(name = "two_words_newline_space_newline", buffer = ["aaa aaa}a", " ", "  ", "{"])]
+            #[vcase(name = "two_words_newline_space_newline", buffer = ["aa}a aaaa", " ", "  ", "{"], count = 2)]
+            $(#[vcase($($group6)*)])*
+            #[vcase(name = "two_words_newline_space_newline", buffer = ["aaa aaa}a", "", "  ", "{"], count = 2)]
+            $(#[vcase($($group7)*)])*
+            #[vcase(name = "two_words_newline_one_word", buffer = ["aaaa aa}a", "", "  ", "{aaa"], count = 2)]
+            $(#[vcase($($group8)*)])*
+            #[vcase(name = "large_unnecessary_count", buffer = ["}aaa  aaa{aa"], count = 10293949403)]
+            mod $modname {}
+        };
+    }
+
+    omap_ge_vcases!(
+        motion_omap_c_ge,
+        "c",
+        {
+            #[vcase(name = "empty", buffer = ["}{"])]
+            #[vcase(name = "space", buffer = ["}{ "])]
+        },
+        {
+            #[vcase(name = "newline_newline", buffer = ["}", "{"])]
+            #[vcase(name = "newline_space_newline", buffer = ["}  ", "{"])]
+            #[vcase(name = "newline_space_newline", buffer = ["  ", "}", "{"])]
+            #[vcase(name = "newline_space_newline", buffer = ["}  ", "  ", "{"])]
+            #[vcase(name = "newline_space_newline", buffer = ["}  ", "   {  "])]
+            #[vcase(name = "newline_space_newline", buffer = ["  ", "}", "   {  "])]
+            #[vcase(name = "one_word", buffer = ["}{aaaa"])]
+        },
+        {
+            #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"])]
+            #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"], count = 2)]
+        },
+        {
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaa{a"])]
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaa{a"], count = 2)]
+        },
+        {
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaaa  { "], count = 2)]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", "aaa{a"])]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", "", "aaa{a"], count = 2)]
+            #[vcase(name = "space_newline_one_word", buffer = ["     ", "}", "", "aaa{a"], count = 2)]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", "", "", "aaa{a"], count = 3)]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", " ", " ", "aaa{a"])]
+        },
+        {
+            #[vcase(name = "two_words_newline_space_newline", buffer = ["aaa aaaa", "}", "  ", "{"])]
+        },
+        {
+            #[vcase(name = "newline_space_newline_one_word", buffer = ["", "  ", "}", "aa{a"])]
+            #[vcase(name = "newline_space_newline_one_word", buffer = ["}", "  ", "", "aa{a"], count = 2)]
+        },
+        {
+            #[vcase(name = "large_unnecessary_count", buffer = ["}{"], count = 10293949403)]
+        }
+    );
+
+    omap_ge_vcases!(
+        motion_omap_y_ge,
+        "y",
+        {
+            #[vcase(name = "empty", buffer = ["}{"])]
+            #[vcase(name = "space", buffer = ["}{ "])]
+        },
+        {
+            #[vcase(name = "newline_newline", buffer = ["}", "{"])]
+            #[vcase(name = "newline_space_newline", buffer = ["}  ", "{"])]
+            #[vcase(name = "newline_space_newline", buffer = ["  ", "}", "{"])]
+            #[vcase(name = "newline_space_newline", buffer = ["}  ", "  ", "{"])]
+            #[vcase(name = "newline_space_newline", buffer = ["}  ", "   {  "])]
+            #[vcase(name = "newline_space_newline", buffer = ["  ", "}", "   {  "])]
+            #[vcase(name = "one_word", buffer = ["}{aaaa"])]
+        },
+        {
+            #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"])]
+            #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"], count = 2)]
+        },
+        {
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaa{a"])]
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaa{a"], count = 2)]
+        },
+        {
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaaa  { "], count = 2)]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", "aaa{a"])]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", "", "aaa{a"], count = 2)]
+            #[vcase(name = "space_newline_one_word", buffer = ["     ", "}", "", "aaa{a"], count = 2)]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", "", "", "aaa{a"], count = 3)]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", " ", " ", "aaa{a"])]
+        },
+        {
+            #[vcase(name = "two_words_newline_space_newline", buffer = ["aaa aaaa", "}", "  ", "{"])]
+        },
+        {
+            #[vcase(name = "newline_space_newline_one_word", buffer = ["", "  ", "}", "aa{a"])]
+            #[vcase(name = "newline_space_newline_one_word", buffer = ["}", "  ", "", "aa{a"], count = 2)]
+        },
+        {
+            #[vcase(name = "large_unnecessary_count", buffer = ["}{"], count = 10293949403)]
+        }
+    );
+
+    omap_ge_vcases!(
+        motion_omap_d_ge,
+        "d",
+        {
+            #[vcase(name = "empty", buffer = ["}{"], prevent_change)]
+            #[vcase(name = "space", buffer = ["}{ "], prevent_change)]
+        },
+        {
+            #[vcase(name = "newline_newline", buffer = ["}", "{"], d_special)]
+            #[vcase(name = "newline_newline", buffer = ["}", "{"], count = 2, d_special, prevent_change)]
+            #[vcase(name = "newline_space_newline", buffer = ["}  ", "{"], d_special)]
+            #[vcase(name = "newline_space_newline", buffer = ["  ", "}", "{"], d_special)]
+            #[vcase(name = "newline_space_newline", buffer = ["}  ", "  ", "{"], d_special)]
+            #[vcase(name = "newline_space_newline", buffer = ["}  ", "   {  "], d_special)]
+            #[vcase(name = "newline_space_newline", buffer = ["  ", "}", "   {  "], d_special)]
+            #[vcase(name = "one_word", buffer = ["}{aaaa"], prevent_change)]
+        },
+        {
+            #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"], d_special)]
+            #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"], count = 2, d_special, prevent_change)]
+            #[vcase(name = "newline_one_word", buffer = ["}", "aaa{a"], count = 3, d_special, prevent_change)]
+        },
+        {
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaa{a"], d_special)]
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaa{a"], count = 2, d_special, prevent_change)]
+        },
+        {
+            #[vcase(name = "newline_space_one_word", buffer = ["}", "   aaaa  { "], count = 2, d_special)]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", "aaa{a"], d_special)]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", "", "aaa{a"], count = 2, d_special)]
+            #[vcase(name = "space_newline_one_word", buffer = ["     ", "}", "", "aaa{a"], count = 2, d_special)]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", "", "", "aaa{a"], count = 3, d_special)]
+            #[vcase(name = "space_newline_one_word", buffer = ["}     ", " ", " ", "aaa{a"], d_special)]
+        },
+        {
+            #[vcase(name = "two_words_newline_space_newline", buffer = ["aaa aaaa", "}", "  ", "{"], d_special)]
+        },
+        {
+            #[vcase(name = "newline_space_newline_one_word", buffer = ["", "  ", "}", "aa{a"], d_special)]
+            #[vcase(name = "newline_space_newline_one_word", buffer = ["}", "  ", "", "aa{a"], count = 2, d_special)]
+            #[vcase(name = "newline_space_newline_one_word", buffer = ["}", "  ", "", "aa{a"], count = 3, d_special, prevent_change)]
+        },
+        {
+            #[vcase(name = "large_unnecessary_count", buffer = ["}{"], count = 10293949403, prevent_change)]
+        }
+    );
+
+    // Property-based differential test against a live Vim oracle, covering
+    // far more of the input space than the hand-written `vcase`s above,
+    // checked against all three operators since `omap_ge` now computes the
+    // same cursor position (and `d_special`/`prevent_change`) regardless of
+    // which operator is asking.
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_omap_ge_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask, ask_replay, render_diff, MotionCase, MotionKind, OracleQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust_cursor = crate::motion::WORD_MOTION
+                    .omap_ge(&case.buffer, case.cursor, case.count, word)
+                    .unwrap()
+                    .new_cursor_pos;
+                for operator in ['c', 'd', 'y'] {
+                    let query = OracleQuery {
+                        buffer: case.buffer.clone(),
+                        cursor: case.cursor,
+                        count: case.count,
+                        word,
+                        motion: MotionKind::Ge,
+                        operator: Some(operator),
+                        visual: None,
+                    };
+                    let groundtruth = ask(query.clone());
+                    let replay = ask_replay(query, rust_cursor);
+                    if groundtruth != replay {
+                        return TestResult::error(format!(
+                            "{}ge(word={}) on {:?} from {:?}: rust lands on {:?}, vim on {:?}\n{}",
+                            operator,
+                            word,
+                            case.buffer,
+                            case.cursor,
+                            rust_cursor,
+                            groundtruth.cursor,
+                            render_diff(&replay.buffer, &groundtruth.buffer),
+                        ));
+                    }
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
+
+    #[test]
+    fn omap_ge_range_is_inclusive_charwise_without_d_special() {
+        use super::super::OperatorRange;
+
+        let buffer: Vec<&'static str> = vec!["aaaa"];
+        let (output, range) = crate::motion::WORD_MOTION
+            .omap_ge_range(&buffer, (1, 2), 1, true)
+            .unwrap();
+        assert_eq!(output.new_cursor_pos, (1, 0));
+        assert!(!output.d_special);
+        assert_eq!(
+            range,
+            OperatorRange {
+                start: (1, 0),
+                end: (1, 2),
+                linewise: false,
+                inclusive: true,
+            }
+        );
+    }
+
+    #[test]
+    fn omap_ge_range_is_linewise_excluding_cursor_line_on_d_special() {
+        use super::super::OperatorRange;
+
+        let buffer: Vec<&'static str> = vec!["", ""];
+        let (output, range) = crate::motion::WORD_MOTION
+            .omap_ge_range(&buffer, (2, 0), 1, true)
+            .unwrap();
+        assert_eq!(output.new_cursor_pos, (1, 0));
+        assert!(output.d_special);
+        assert_eq!(
+            range,
+            OperatorRange {
+                start: (1, 0),
+                end: (1, 0),
+                linewise: true,
+                inclusive: true,
+            }
+        );
+    }
+}
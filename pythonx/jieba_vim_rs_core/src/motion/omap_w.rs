@@ -12,7 +12,7 @@
 // License for the specific language governing permissions and limitations
 // under the License.
 
-use super::token_iter::{ForwardTokenIterator, TokenIteratorItem};
+use super::token_iter::{TokenCursor, TokenIteratorItem};
 use super::{BufferLike, MotionOutput, WordMotion};
 use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
 
@@ -21,8 +21,12 @@ fn is_stoppable(item: &TokenIteratorItem) -> bool {
     match item.token {
         None => true,
         Some(token) => match token.ty {
-            TokenType::Word => true,
+            TokenType::Word | TokenType::Punctuation => true,
             TokenType::Space => false,
+            TokenType::Sentence => unreachable!(
+                "is_stoppable only sees tokens from WordMotion::tokens, \
+                 which never emits Sentence"
+            ),
         },
     }
 }
@@ -68,6 +72,9 @@ impl<C: JiebaPlaceholder> WordMotion<C> {
     ///   combination with an operator and the last word moved over is at the
     ///   end of a line, the end of that word becomes the end of the operated
     ///   text, not the first word in the next line."
+    /// - `cw`/`cW` do *not* go through this function: Vim special-cases them
+    ///   to behave like `ce`/`cE` when the cursor starts on a non-blank. See
+    ///   [`Self::omap_c_w`] for that path.
     ///
     /// # Panics
     ///
@@ -82,7 +89,7 @@ impl<C: JiebaPlaceholder> WordMotion<C> {
     ) -> Result<MotionOutput, B::Error> {
         let (mut lnum, mut col) = cursor_pos;
         let mut it =
-            ForwardTokenIterator::new(buffer, &self.jieba, lnum, col, word)?
+            TokenCursor::new_forward(buffer, self, lnum, col, word)?
                 .peekable();
         while count > 0 && it.peek().is_some() {
             let item = it.next().unwrap()?;
@@ -268,4 +275,56 @@ mod tests {
     #[vcase(name = "word_newline_counts", buffer = ["ab{cd  efg", " ", "  hij   }", ""], count = 3)]
     #[vcase(name = "word_newline_counts", buffer = ["ab{cd  efg", " ", "  ", "  ", "  hij  }", "  ", ""], count = 3)]
     mod motion_omap_y_w {}
+
+    // Property-based differential test against a live Vim oracle, covering
+    // far more of the input space than the hand-written `vcase`s above.
+    // `c` is excluded -- it goes through `omap_c_w` instead, which has its
+    // own property test.
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_omap_w_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask, ask_replay, render_diff, MotionCase, MotionKind, OracleQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust_cursor = crate::motion::WORD_MOTION
+                    .omap_w(&case.buffer, case.cursor, case.count, word)
+                    .unwrap()
+                    .new_cursor_pos;
+                for operator in ['d', 'y'] {
+                    let query = OracleQuery {
+                        buffer: case.buffer.clone(),
+                        cursor: case.cursor,
+                        count: case.count,
+                        word,
+                        motion: MotionKind::W,
+                        operator: Some(operator),
+                        visual: None,
+                    };
+                    let groundtruth = ask(query.clone());
+                    let replay = ask_replay(query, rust_cursor);
+                    if groundtruth != replay {
+                        return TestResult::error(format!(
+                            "{}w(word={}) on {:?} from {:?}: rust lands on {:?}, vim on {:?}\n{}",
+                            operator,
+                            word,
+                            case.buffer,
+                            case.cursor,
+                            rust_cursor,
+                            groundtruth.cursor,
+                            render_diff(&replay.buffer, &groundtruth.buffer),
+                        ));
+                    }
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
 }
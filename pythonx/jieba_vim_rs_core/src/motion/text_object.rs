@@ -0,0 +1,659 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use super::token_iter::{TokenCursor, TokenIteratorItem};
+use super::{BufferLike, WordMotion};
+use crate::token::{JiebaPlaceholder, Token, TokenLike, TokenType};
+
+/// The inclusive span a `iw`/`aw` text object covers. Unlike [`MotionOutput`]
+/// (which a plain motion reports as a single landing position), a text
+/// object has two ends.
+///
+/// [`MotionOutput`]: super::MotionOutput
+#[derive(Debug, PartialEq, Eq)]
+pub struct TextObjectOutput {
+    /// Start of the span, inclusive.
+    pub start: (usize, usize),
+    /// End of the span, inclusive.
+    pub end: (usize, usize),
+}
+
+fn is_space(item: &TokenIteratorItem) -> bool {
+    matches!(item.token.map(|t| t.ty), Some(TokenType::Space))
+}
+
+/// Index into `tokens` of the `n`th (0-indexed) non-`Space` token, if any.
+fn nth_word_index(tokens: &[Token], n: usize) -> Option<usize> {
+    tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, tok)| tok.ty != TokenType::Space)
+        .nth(n)
+        .map(|(i, _)| i)
+}
+
+/// The `(start_byte, incl_end_byte, excl_end_byte)` triple
+/// `test_macros::token!` encodes, for `tokens[i]`.
+fn span_triple(tok: Token) -> (usize, usize, usize) {
+    (
+        tok.col.start_byte_index,
+        tok.col.incl_end_byte_index,
+        tok.col.excl_end_byte_index,
+    )
+}
+
+/// Like [`span_triple`], but widens `incl_end_byte`/`excl_end_byte` to cover
+/// `tokens[i + 1]` if it's a run of `Space` -- the same trailing-whitespace
+/// rule `aw`/`aW` applies via [`WordMotion::text_object_around`].
+fn span_triple_with_trailing_space(
+    tokens: &[Token],
+    i: usize,
+) -> (usize, usize, usize) {
+    let (start, incl_end, excl_end) = span_triple(tokens[i]);
+    match tokens.get(i + 1) {
+        Some(next) if next.ty == TokenType::Space => {
+            (start, next.col.incl_end_byte_index, next.col.excl_end_byte_index)
+        }
+        _ => (start, incl_end, excl_end),
+    }
+}
+
+impl<C: JiebaPlaceholder> WordMotion<C> {
+    /// Vim text object `iw` (if `word` is `true`) or `iW` (if `word` is
+    /// `false`). Selects the jieba token (a word, or a run of whitespace)
+    /// the cursor lies in, extended to cover `count` consecutive tokens.
+    /// Empty lines count as a token of their own, same as `w`/`b` treat
+    /// them.
+    fn text_object_inner<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        mut count: u64,
+        word: bool,
+    ) -> Result<TextObjectOutput, B::Error> {
+        let (lnum, col) = cursor_pos;
+        let mut it = TokenCursor::new_forward(buffer, self, lnum, col, word)?;
+        let first = it.next().unwrap()?;
+        let start = (first.lnum, first.token.first_char());
+        let mut end = (first.lnum, first.token.last_char());
+        count = count.saturating_sub(1);
+        while count > 0 {
+            let Some(item) = it.next() else { break };
+            let item = item?;
+            end = (item.lnum, item.token.last_char());
+            count -= 1;
+        }
+        Ok(TextObjectOutput { start, end })
+    }
+
+    /// Vim text object `aw` (if `word` is `true`) or `aW` (if `word` is
+    /// `false`). Like `iw`, but pulls in the whitespace around the word: if
+    /// the cursor starts on whitespace, `count` words after it are included
+    /// along with that leading whitespace; otherwise `count` words are
+    /// included along with the whitespace that trails them, or, if there is
+    /// none, the whitespace that precedes them.
+    fn text_object_around<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        count: u64,
+        word: bool,
+    ) -> Result<TextObjectOutput, B::Error> {
+        let (lnum, col) = cursor_pos;
+        let mut it =
+            TokenCursor::new_forward(buffer, self, lnum, col, word)?.peekable();
+        let first = it.next().unwrap()?;
+        let start = (first.lnum, first.token.first_char());
+        let mut end = (first.lnum, first.token.last_char());
+
+        let starts_on_space = is_space(&first);
+        let mut words_left =
+            if starts_on_space { count } else { count.saturating_sub(1) };
+        while words_left > 0 {
+            let Some(item) = it.next() else { break };
+            let item = item?;
+            end = (item.lnum, item.token.last_char());
+            if !is_space(&item) {
+                words_left -= 1;
+            }
+        }
+
+        if !starts_on_space {
+            if let Some(Ok(next)) = it.peek() {
+                if is_space(next) {
+                    let next = it.next().unwrap()?;
+                    end = (next.lnum, next.token.last_char());
+                    return Ok(TextObjectOutput { start, end });
+                }
+            }
+            // No trailing whitespace: pull in leading whitespace instead.
+            let mut bit =
+                TokenCursor::new_backward(buffer, self, lnum, col, word)?;
+            bit.next_back().transpose()?; // Skip the cursor's own token.
+            if let Some(prev) = bit.next_back() {
+                let prev = prev?;
+                if is_space(&prev) {
+                    return Ok(TextObjectOutput {
+                        start: (prev.lnum, prev.token.first_char()),
+                        end,
+                    });
+                }
+            }
+        }
+
+        Ok(TextObjectOutput { start, end })
+    }
+
+    /// Vim text object `iw` (if `word` is `true`) or `iW` (if `word` is
+    /// `false`) in operator-pending mode, e.g. `diw`/`ciW`. See
+    /// [`Self::text_object_inner`] for the selection rules.
+    pub fn omap_iw<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        count: u64,
+        word: bool,
+    ) -> Result<TextObjectOutput, B::Error> {
+        self.text_object_inner(buffer, cursor_pos, count, word)
+    }
+
+    /// Vim text object `iw` (if `word` is `true`) or `iW` (if `word` is
+    /// `false`) in visual mode, e.g. `viw`/`viW`. See
+    /// [`Self::text_object_inner`] for the selection rules.
+    pub fn xmap_iw<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        count: u64,
+        word: bool,
+    ) -> Result<TextObjectOutput, B::Error> {
+        self.text_object_inner(buffer, cursor_pos, count, word)
+    }
+
+    /// Vim text object `aw` (if `word` is `true`) or `aW` (if `word` is
+    /// `false`) in operator-pending mode, e.g. `daw`/`caW`. See
+    /// [`Self::text_object_around`] for the selection rules.
+    pub fn omap_aw<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        count: u64,
+        word: bool,
+    ) -> Result<TextObjectOutput, B::Error> {
+        self.text_object_around(buffer, cursor_pos, count, word)
+    }
+
+    /// Vim text object `aw` (if `word` is `true`) or `aW` (if `word` is
+    /// `false`) in visual mode, e.g. `vaw`/`vaW`. See
+    /// [`Self::text_object_around`] for the selection rules.
+    pub fn xmap_aw<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        count: u64,
+        word: bool,
+    ) -> Result<TextObjectOutput, B::Error> {
+        self.text_object_around(buffer, cursor_pos, count, word)
+    }
+
+    /// The boundaries of the single token the cursor `(lnum, col)` lies in
+    /// -- the shared token-boundary primitive behind [`Self::omap_iw`] and
+    /// [`Self::omap_aw`] (with `count` fixed at `1`), exposed directly for a
+    /// caller that wants to build its own text object without
+    /// re-implementing the cursor-token/adjacent-whitespace lookup. `None`
+    /// if the cursor is past the last token of a non-empty line, mirroring
+    /// [`super::token_iter::TokenCursor::new_forward`]; an empty line is
+    /// still a valid span-less "token" like `w`/`b` treat it, so it reports
+    /// `Some` with `start_col`/`end_col` both `0`.
+    ///
+    /// If `with_adjacent_space` is `true` and the cursor's own token isn't
+    /// whitespace, also reports the inclusive bounds of the one run of
+    /// `Space` immediately trailing it as [`TokenSpan::adjacent_space`], or,
+    /// if there is none, the one immediately leading it -- the same
+    /// trailing-then-leading preference [`Self::text_object_around`] uses
+    /// for `aw`/`aW`.
+    pub fn token_span_at<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        word: bool,
+        with_adjacent_space: bool,
+    ) -> Result<Option<TokenSpan>, B::Error> {
+        let (lnum, col) = cursor_pos;
+        let mut it = TokenCursor::new_forward(buffer, self, lnum, col, word)?;
+        let Some(first) = it.next() else { return Ok(None) };
+        let first = first?;
+
+        let lnum = first.lnum;
+        let start_col = first.token.first_char();
+        let end_col = first.token.last_char();
+
+        let adjacent_space = if with_adjacent_space
+            && first.token.is_some()
+            && !is_space(&first)
+        {
+            match it.next() {
+                Some(next) => {
+                    let next = next?;
+                    if is_space(&next) {
+                        Some((next.token.first_char(), next.token.last_char()))
+                    } else {
+                        self.leading_space(buffer, lnum, col, word)?
+                    }
+                }
+                None => self.leading_space(buffer, lnum, col, word)?,
+            }
+        } else {
+            None
+        };
+
+        Ok(Some(TokenSpan { lnum, start_col, end_col, adjacent_space }))
+    }
+
+    /// The number of non-`Space` tokens on line `lnum`, cut into `word`s if
+    /// `word` is `true` or `WORD`s otherwise. An empty line counts as `0`,
+    /// not `1` -- unlike `w`/`b`/`iw`, which treat an empty line as a token
+    /// of its own, a line with nothing on it has no word to index by
+    /// [`Self::word`].
+    pub fn words<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        lnum: usize,
+        word: bool,
+    ) -> Result<usize, B::Error> {
+        Ok(self
+            .tokens(buffer, lnum, word)?
+            .iter()
+            .filter(|tok| tok.ty != TokenType::Space)
+            .count())
+    }
+
+    /// The `(start_byte, incl_end_byte, excl_end_byte)` span of the `n`th
+    /// (0-indexed) non-`Space` token on line `lnum`, in the same triple
+    /// shape `test_macros::token!` encodes. `None` if the line has fewer
+    /// than `n + 1` words.
+    pub fn word<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        lnum: usize,
+        n: usize,
+        word: bool,
+    ) -> Result<Option<(usize, usize, usize)>, B::Error> {
+        let tokens = self.tokens(buffer, lnum, word)?;
+        Ok(nth_word_index(&tokens, n).map(|i| span_triple(tokens[i])))
+    }
+
+    /// Like [`Self::word`], but widens the span to also cover the run of
+    /// trailing whitespace immediately after the word, if any -- the same
+    /// rule [`Self::text_object_around`] applies for `aw`/`aW`.
+    pub fn word_aw<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        lnum: usize,
+        n: usize,
+        word: bool,
+    ) -> Result<Option<(usize, usize, usize)>, B::Error> {
+        let tokens = self.tokens(buffer, lnum, word)?;
+        Ok(nth_word_index(&tokens, n)
+            .map(|i| span_triple_with_trailing_space(&tokens, i)))
+    }
+
+    /// The span of the first word on line `lnum`, i.e. [`Self::word`] with
+    /// `n` fixed at `0`.
+    pub fn first_word<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        lnum: usize,
+        word: bool,
+    ) -> Result<Option<(usize, usize, usize)>, B::Error> {
+        self.word(buffer, lnum, 0, word)
+    }
+
+    /// The span of the last word on line `lnum`.
+    pub fn last_word<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        lnum: usize,
+        word: bool,
+    ) -> Result<Option<(usize, usize, usize)>, B::Error> {
+        let tokens = self.tokens(buffer, lnum, word)?;
+        Ok(tokens
+            .iter()
+            .rposition(|tok| tok.ty != TokenType::Space)
+            .map(|i| span_triple(tokens[i])))
+    }
+
+    /// The inclusive `(start_col, end_col)` of the `Space` token immediately
+    /// to the left of the cursor `(lnum, col)`'s own token, if any.
+    fn leading_space<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        lnum: usize,
+        col: usize,
+        word: bool,
+    ) -> Result<Option<(usize, usize)>, B::Error> {
+        let mut bit = TokenCursor::new_backward(buffer, self, lnum, col, word)?;
+        bit.next_back().transpose()?; // Skip the cursor's own token.
+        if let Some(prev) = bit.next_back() {
+            let prev = prev?;
+            if is_space(&prev) {
+                return Ok(Some((
+                    prev.token.first_char(),
+                    prev.token.last_char(),
+                )));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// The boundaries of a single token, as returned by
+/// [`WordMotion::token_span_at`] -- lower-level than [`TextObjectOutput`],
+/// since it reports the raw bounds of the cursor's own token (and,
+/// optionally, one adjacent `Space` token) without the `count`/inner-vs-around
+/// selection semantics `omap_iw`/`omap_aw` layer on top.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenSpan {
+    /// Line the span is on.
+    pub lnum: usize,
+    /// Start column of the cursor's own token, inclusive. `0` if the line is
+    /// empty.
+    pub start_col: usize,
+    /// End column of the cursor's own token, inclusive. `0` if the line is
+    /// empty.
+    pub end_col: usize,
+    /// Bounds of the adjacent `Space` token, if [`Self::token_span_at`] was
+    /// asked for one and one exists.
+    pub adjacent_space: Option<(usize, usize)>,
+}
+
+// `iw`/`aw` report a span rather than a new cursor position, so they don't
+// fit the `MotionOutput`/`VerifiableCase` shape the vader.vim-backed
+// `verified_cases` machinery in `jieba_vim_rs_test` verifies against a live
+// Vim oracle. `omap_iw`/`xmap_iw` and `omap_aw`/`xmap_aw` are identical
+// regardless of which visual submode (`xc`/`xl`/`xb`) or operator-pending
+// mode invokes them -- the submode only changes how a Vim-side caller
+// applies the returned span -- so these plain unit tests exercise the
+// shared `text_object_inner`/`text_object_around` logic directly instead.
+#[cfg(test)]
+mod tests {
+    use super::super::WORD_MOTION;
+    use super::{TextObjectOutput, TokenSpan};
+
+    #[test]
+    fn test_omap_iw_selects_the_word_under_the_cursor() {
+        let buffer = vec!["hello world"];
+        let result = WORD_MOTION.omap_iw(&buffer, (1, 2), 1, true).unwrap();
+        assert_eq!(result, TextObjectOutput { start: (1, 0), end: (1, 4) });
+    }
+
+    #[test]
+    fn test_xmap_iw_on_cjk_text_selects_the_jieba_token() {
+        // Whitespace alone can't delimit "你好世界" into words; jieba's
+        // segmentation is what makes `iw` land on just "世界" (byte cols
+        // 6..=11) here, not the whole four-character run.
+        let buffer = vec!["你好世界"];
+        let result = WORD_MOTION.xmap_iw(&buffer, (1, 6), 1, true).unwrap();
+        assert_eq!(result, TextObjectOutput { start: (1, 6), end: (1, 11) });
+    }
+
+    #[test]
+    fn test_omap_iw_on_whitespace_selects_the_run_of_blanks() {
+        let buffer = vec!["a   b"];
+        let result = WORD_MOTION.omap_iw(&buffer, (1, 2), 1, true).unwrap();
+        assert_eq!(result, TextObjectOutput { start: (1, 1), end: (1, 3) });
+    }
+
+    #[test]
+    fn test_omap_iw_count_extends_across_alternating_segments() {
+        let buffer = vec!["a b c d"];
+        // From "a", a count of 3 alternates word/space/word: "a", " ", "b".
+        let result = WORD_MOTION.omap_iw(&buffer, (1, 0), 3, true).unwrap();
+        assert_eq!(result, TextObjectOutput { start: (1, 0), end: (1, 2) });
+    }
+
+    #[test]
+    fn test_omap_aw_includes_trailing_whitespace() {
+        let buffer = vec!["foo bar"];
+        let result = WORD_MOTION.omap_aw(&buffer, (1, 0), 1, true).unwrap();
+        assert_eq!(result, TextObjectOutput { start: (1, 0), end: (1, 3) });
+    }
+
+    #[test]
+    fn test_xmap_aw_with_no_trailing_whitespace_pulls_in_leading_whitespace() {
+        let buffer = vec!["foo bar"];
+        let result = WORD_MOTION.xmap_aw(&buffer, (1, 4), 1, true).unwrap();
+        assert_eq!(result, TextObjectOutput { start: (1, 3), end: (1, 6) });
+    }
+
+    #[test]
+    fn test_omap_aw_starting_on_whitespace_includes_the_word_after_it() {
+        let buffer = vec!["foo   bar"];
+        let result = WORD_MOTION.omap_aw(&buffer, (1, 4), 1, true).unwrap();
+        assert_eq!(result, TextObjectOutput { start: (1, 3), end: (1, 8) });
+    }
+
+    #[test]
+    fn test_omap_aw_count_extends_across_multiple_words() {
+        let buffer = vec!["a b c d"];
+        let result = WORD_MOTION.omap_aw(&buffer, (1, 0), 2, true).unwrap();
+        assert_eq!(result, TextObjectOutput { start: (1, 0), end: (1, 3) });
+    }
+
+    // Property-based differential tests against a live Vim oracle, covering
+    // far more of the input space than the hand-written unit tests above.
+    // The oracle selects the text object with `viw`/`vaw` and friends and
+    // reads the resulting span back from the `'<`/`'>` marks.
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_iw_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask_text_object, MotionCase, TextObjectKind, TextObjectQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust = WORD_MOTION
+                    .omap_iw(&case.buffer, case.cursor, case.count, word)
+                    .unwrap();
+                let vim = ask_text_object(TextObjectQuery {
+                    buffer: case.buffer.clone(),
+                    cursor: case.cursor,
+                    count: case.count,
+                    word,
+                    kind: TextObjectKind::Inner,
+                });
+                if (rust.start, rust.end) != (vim.start, vim.end) {
+                    return TestResult::error(format!(
+                        "iw(word={}) on {:?} from {:?}: rust selected {:?}..={:?}, vim {:?}..={:?}",
+                        word, case.buffer, case.cursor, rust.start, rust.end, vim.start, vim.end,
+                    ));
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
+
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_aw_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask_text_object, MotionCase, TextObjectKind, TextObjectQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust = WORD_MOTION
+                    .omap_aw(&case.buffer, case.cursor, case.count, word)
+                    .unwrap();
+                let vim = ask_text_object(TextObjectQuery {
+                    buffer: case.buffer.clone(),
+                    cursor: case.cursor,
+                    count: case.count,
+                    word,
+                    kind: TextObjectKind::Around,
+                });
+                if (rust.start, rust.end) != (vim.start, vim.end) {
+                    return TestResult::error(format!(
+                        "aw(word={}) on {:?} from {:?}: rust selected {:?}..={:?}, vim {:?}..={:?}",
+                        word, case.buffer, case.cursor, rust.start, rust.end, vim.start, vim.end,
+                    ));
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
+
+    #[test]
+    fn test_token_span_at_reports_the_cursor_token() {
+        let buffer = vec!["hello world"];
+        let result =
+            WORD_MOTION.token_span_at(&buffer, (1, 2), true, false).unwrap();
+        assert_eq!(
+            result,
+            Some(TokenSpan {
+                lnum: 1,
+                start_col: 0,
+                end_col: 4,
+                adjacent_space: None
+            })
+        );
+    }
+
+    #[test]
+    fn test_token_span_at_prefers_trailing_space() {
+        let buffer = vec!["hello world"];
+        let result =
+            WORD_MOTION.token_span_at(&buffer, (1, 2), true, true).unwrap();
+        assert_eq!(
+            result,
+            Some(TokenSpan {
+                lnum: 1,
+                start_col: 0,
+                end_col: 4,
+                adjacent_space: Some((5, 5)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_token_span_at_falls_back_to_leading_space() {
+        let buffer = vec!["hello world"];
+        let result = WORD_MOTION
+            .token_span_at(&buffer, (1, 6), true, true)
+            .unwrap();
+        assert_eq!(
+            result,
+            Some(TokenSpan {
+                lnum: 1,
+                start_col: 6,
+                end_col: 10,
+                adjacent_space: Some((5, 5)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_token_span_at_past_last_token_is_none() {
+        let buffer = vec!["aaa"];
+        let result =
+            WORD_MOTION.token_span_at(&buffer, (1, 5), true, false).unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_token_span_at_empty_line_has_no_span() {
+        let buffer = vec![""];
+        let result =
+            WORD_MOTION.token_span_at(&buffer, (1, 0), true, true).unwrap();
+        assert_eq!(
+            result,
+            Some(TokenSpan {
+                lnum: 1,
+                start_col: 0,
+                end_col: 0,
+                adjacent_space: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_words_counts_non_space_tokens() {
+        let buffer = vec!["foo.bar baz"];
+        assert_eq!(WORD_MOTION.words(&buffer, 1, true).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_words_on_empty_line_is_zero() {
+        let buffer = vec![""];
+        assert_eq!(WORD_MOTION.words(&buffer, 1, true).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_word_returns_the_nth_words_span() {
+        let buffer = vec!["foo.bar baz"];
+        // Words: "foo" (0..=2), "." (3..=3), "bar" (4..=6), "baz" (8..=10).
+        assert_eq!(
+            WORD_MOTION.word(&buffer, 1, 2, true).unwrap(),
+            Some((4, 6, 7))
+        );
+    }
+
+    #[test]
+    fn test_word_past_the_last_word_is_none() {
+        let buffer = vec!["foo"];
+        assert_eq!(WORD_MOTION.word(&buffer, 1, 1, true).unwrap(), None);
+    }
+
+    #[test]
+    fn test_word_aw_folds_in_trailing_space() {
+        let buffer = vec!["foo bar"];
+        assert_eq!(
+            WORD_MOTION.word_aw(&buffer, 1, 0, true).unwrap(),
+            Some((0, 3, 4))
+        );
+    }
+
+    #[test]
+    fn test_word_aw_with_no_trailing_space_matches_word() {
+        let buffer = vec!["foo bar"];
+        assert_eq!(
+            WORD_MOTION.word_aw(&buffer, 1, 1, true).unwrap(),
+            Some((4, 6, 7))
+        );
+    }
+
+    #[test]
+    fn test_first_word_and_last_word() {
+        let buffer = vec!["foo.bar baz"];
+        assert_eq!(
+            WORD_MOTION.first_word(&buffer, 1, true).unwrap(),
+            Some((0, 2, 3))
+        );
+        assert_eq!(
+            WORD_MOTION.last_word(&buffer, 1, true).unwrap(),
+            Some((8, 10, 11))
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use super::token_iter::{ForwardTokenIterator, TokenIteratorItem};
+use super::token_iter::{TokenCursor, TokenIteratorItem};
 use super::{BufferLike, MotionOutput, WordMotion};
 use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
 
@@ -7,8 +7,12 @@ fn is_stoppable(item: &TokenIteratorItem) -> bool {
     match item.token {
         None => false,
         Some(token) => match token.ty {
-            TokenType::Word => true,
+            TokenType::Word | TokenType::Punctuation => true,
             TokenType::Space => false,
+            TokenType::Sentence => unreachable!(
+                "is_stoppable only sees tokens from WordMotion::tokens, \
+                 which never emits Sentence"
+            ),
         },
     }
 }
@@ -43,7 +47,7 @@ impl<C: JiebaPlaceholder> WordMotion<C> {
     ) -> Result<MotionOutput, B::Error> {
         let (mut lnum, mut col) = cursor_pos;
         let mut it =
-            ForwardTokenIterator::new(buffer, &self.jieba, lnum, col, word)?
+            TokenCursor::new_forward(buffer, self, lnum, col, word)?
                 .peekable();
         while count > 0 && it.peek().is_some() {
             let item = it.next().unwrap()?;
@@ -1,4 +1,5 @@
-use super::{BufferLike, MotionOutput, WordMotion};
+use super::{BufferLike, CursorRange, MotionOutput, VisualKind, WordMotion};
+use crate::char_class::display_col;
 use crate::token::JiebaPlaceholder;
 
 impl<C: JiebaPlaceholder> WordMotion<C> {
@@ -32,6 +33,45 @@ impl<C: JiebaPlaceholder> WordMotion<C> {
     ) -> Result<MotionOutput, B::Error> {
         self.nmap_ge(buffer, cursor_pos, count, word)
     }
+
+    /// [`Self::xmap_ge`], extending a [`CursorRange`] selection instead of
+    /// returning a bare cursor position. `anchor` is the selection's fixed
+    /// end; `head` is its current, moving end -- the position `ge`/`gE`
+    /// itself runs from. `ge`/`gE` is inclusive, so the returned range
+    /// always covers the landed character in [`VisualKind::Char`] mode.
+    pub fn xmap_ge_range<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        anchor: (usize, usize),
+        head: (usize, usize),
+        count: u64,
+        word: bool,
+        kind: VisualKind,
+    ) -> Result<CursorRange, B::Error> {
+        let new_head = self.xmap_ge(buffer, head, count, word)?.new_cursor_pos;
+        Ok(CursorRange::extend(kind, anchor, head, new_head, true))
+    }
+
+    /// [`Self::xmap_ge`], additionally reporting the landed cursor's virtual
+    /// display column -- the screen-cell offset from column 0, where e.g. a
+    /// 汉字 counts 2 cells -- alongside the usual byte column in
+    /// [`MotionOutput::new_cursor_pos`], for callers that position a screen
+    /// cursor or draw a selection instead of indexing buffer bytes. Opt-in:
+    /// existing byte-column-only callers keep using [`Self::xmap_ge`]
+    /// unaffected.
+    pub fn xmap_ge_vcol<B: BufferLike + ?Sized>(
+        &self,
+        buffer: &B,
+        cursor_pos: (usize, usize),
+        count: u64,
+        word: bool,
+    ) -> Result<(MotionOutput, usize), B::Error> {
+        let output = self.xmap_ge(buffer, cursor_pos, count, word)?;
+        let (lnum, col) = output.new_cursor_pos;
+        let line = buffer.getline_ref(lnum)?;
+        let vcol = display_col(&line, col, &self.width_config);
+        Ok((output, vcol))
+    }
 }
 
 #[cfg(test)]
@@ -220,4 +260,79 @@ mod tests {
     #[vcase(name = "large_unnecessary_count", buffer = ["}{"], count = 10293949403)]
     #[vcase(name = "large_unnecessary_count", buffer = ["}aaa  aaa{aa"], count = 10293949403)]
     mod motion_xbmap_ge {}
+
+    use super::super::{CursorRange, VisualKind};
+
+    #[test]
+    fn xmap_ge_range_char_is_inclusive_of_landed_char() {
+        let buffer: Vec<&'static str> = vec!["aaaa  aaaa"];
+        let range = crate::motion::WORD_MOTION
+            .xmap_ge_range(&buffer, (1, 9), (1, 9), 1, true, VisualKind::Char)
+            .unwrap();
+        assert_eq!(
+            range,
+            CursorRange {
+                anchor: (1, 9),
+                head: (1, 3),
+                inclusive: true,
+            }
+        );
+    }
+
+    #[test]
+    fn xmap_ge_range_line_drops_head_column() {
+        let buffer: Vec<&'static str> = vec!["aaaa", "bbbb"];
+        let range = crate::motion::WORD_MOTION
+            .xmap_ge_range(&buffer, (2, 0), (2, 0), 1, true, VisualKind::Line)
+            .unwrap();
+        assert_eq!(
+            range,
+            CursorRange {
+                anchor: (2, 0),
+                head: (1, 0),
+                inclusive: true,
+            }
+        );
+    }
+
+    #[test]
+    fn xmap_ge_range_block_preserves_head_column() {
+        let buffer: Vec<&'static str> = vec!["aaaa", "bbbb"];
+        let range = crate::motion::WORD_MOTION
+            .xmap_ge_range(&buffer, (2, 0), (2, 3), 1, true, VisualKind::Block)
+            .unwrap();
+        assert_eq!(
+            range,
+            CursorRange {
+                anchor: (2, 0),
+                // The landed row is 1, but the block's own column (3) is
+                // preserved rather than snapping to wherever `ge` itself
+                // would have landed.
+                head: (1, 3),
+                inclusive: true,
+            }
+        );
+    }
+
+    #[test]
+    fn xmap_ge_vcol_counts_wide_chars_as_two_cells() {
+        // "中文  aaaa": ge from inside "aaaa" lands on 文 (byte col 3),
+        // whose display column is 2 -- one cell for 中.
+        let buffer: Vec<&'static str> = vec!["中文  aaaa"];
+        let (output, vcol) = crate::motion::WORD_MOTION
+            .xmap_ge_vcol(&buffer, (1, 9), 1, true)
+            .unwrap();
+        assert_eq!(output.new_cursor_pos, (1, 3));
+        assert_eq!(vcol, 2);
+    }
+
+    #[test]
+    fn xmap_ge_vcol_at_line_start_is_zero() {
+        let buffer: Vec<&'static str> = vec!["aaaa"];
+        let (output, vcol) = crate::motion::WORD_MOTION
+            .xmap_ge_vcol(&buffer, (1, 0), 1, true)
+            .unwrap();
+        assert_eq!(output.new_cursor_pos, (1, 0));
+        assert_eq!(vcol, 0);
+    }
 }
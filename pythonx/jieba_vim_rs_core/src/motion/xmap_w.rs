@@ -1,18 +1,22 @@
-use super::token_iter::{ForwardTokenIterator, TokenIteratorItem};
+use super::token_iter::{TokenCursor, TokenIteratorItem};
 use super::{BufferLike, MotionOutput, WordMotion};
 use crate::token::{JiebaPlaceholder, TokenLike, TokenType};
 
-/// Test if a token is stoppable for `xmap_w`.
+/// Test if a token is stoppable for `xmap_w`. A [`WordMotion`] configured
+/// with a [`crate::token::classify::Classifier`] (see
+/// [`WordMotion::with_classifier`]) already splits jieba's `Word` tokens at
+/// keyword/punctuation transitions, so treating both `Word` and
+/// `Punctuation` as stoppable here is what makes `w`/`W` also stop at those
+/// transitions instead of only at jieba's word/space boundaries.
 fn is_stoppable(item: &TokenIteratorItem) -> bool {
     if item.cursor {
         false
     } else {
         match item.token {
             None => true,
-            Some(token) => match token.ty {
-                TokenType::Word => true,
-                TokenType::Space => false,
-            },
+            Some(token) => {
+                matches!(token.ty, TokenType::Word | TokenType::Punctuation)
+            }
         }
     }
 }
@@ -46,7 +50,7 @@ impl<C: JiebaPlaceholder> WordMotion<C> {
     ) -> Result<MotionOutput, B::Error> {
         let (mut lnum, mut col) = cursor_pos;
         let mut it =
-            ForwardTokenIterator::new(buffer, &self.jieba, lnum, col, word)?
+            TokenCursor::new_forward(buffer, self, lnum, col, word)?
                 .peekable();
         while count > 0 && it.peek().is_some() {
             let item = it.next().unwrap()?;
@@ -205,4 +209,71 @@ mod tests {
     #[vcase(name = "large_unnecessary_count", buffer = ["a{aa aaaa}"], count = 10293949403)]
     #[vcase(name = "large_unnecessary_count", buffer = ["aaa {aaaa}"], count = 10293949403)]
     mod motion_xbmap_w {}
+
+    #[test]
+    fn xmap_w_with_classifier_stops_at_punctuation_boundary() {
+        use super::super::WordMotion;
+        use crate::token::classify::Classifier;
+        use crate::token::TokenType;
+
+        // `_` is in Vim's default 'iskeyword', so jieba's own char grouping
+        // merges "foo_bar" into a single `Word` token and `w` has nothing to
+        // stop on before "baz". A classifier that reclassifies `_` as
+        // `Punctuation` splits it into "foo"/"_"/"bar", giving `w` a stop at
+        // the underscore that plain jieba segmentation can't produce.
+        let classifier = Classifier::new(
+            vec![(fancy_regex::Regex::new(r"_").unwrap(), TokenType::Punctuation)],
+            TokenType::Word,
+        );
+        let wm = WordMotion::with_classifier(jieba_rs::Jieba::new(), classifier);
+        let buffer: Vec<&'static str> = vec!["foo_bar baz"];
+
+        let output = wm.xmap_w(&buffer, (1, 0), 1, true).unwrap();
+        assert_eq!(output.new_cursor_pos, (1, 3));
+    }
+
+    // Property-based differential test against a live Vim oracle, covering
+    // far more of the input space than the hand-written `vcase`s above.
+    // `xmap_w`'s charwise-visual landing spot is just the new cursor
+    // position, same shape as `nmap_w`, so entering visual mode with `v`
+    // before the motion and reading the cursor back out is enough -- no
+    // selection-range bookkeeping needed.
+    #[cfg(feature = "verifiable_case")]
+    #[test]
+    fn property_xmap_w_matches_vim() {
+        use jieba_vim_rs_test::verified_case::property::{
+            ask, MotionCase, MotionKind, OracleQuery,
+        };
+        use quickcheck::TestResult;
+
+        fn prop(case: MotionCase) -> TestResult {
+            for word in [true, false] {
+                let rust_cursor = crate::motion::WORD_MOTION
+                    .xmap_w(&case.buffer, case.cursor, case.count, word)
+                    .unwrap()
+                    .new_cursor_pos;
+                let vim_cursor = ask(OracleQuery {
+                    buffer: case.buffer.clone(),
+                    cursor: case.cursor,
+                    count: case.count,
+                    word,
+                    motion: MotionKind::W,
+                    operator: None,
+                    visual: Some('v'),
+                })
+                .cursor;
+                if rust_cursor != vim_cursor {
+                    return TestResult::error(format!(
+                        "xmap_w(word={}) on {:?} from {:?}: rust landed on {:?}, vim on {:?}",
+                        word, case.buffer, case.cursor, rust_cursor, vim_cursor,
+                    ));
+                }
+            }
+            TestResult::passed()
+        }
+
+        quickcheck::QuickCheck::new()
+            .tests(200)
+            .quickcheck(prop as fn(MotionCase) -> TestResult);
+    }
 }
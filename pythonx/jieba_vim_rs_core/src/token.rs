@@ -12,15 +12,124 @@
 // License for the specific language governing permissions and limitations
 // under the License.
 
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+
+use crate::char_class::{CharCategory, CharClass, CharClassConfig, CharClassifier};
 use crate::utils;
 
 pub trait JiebaPlaceholder {
     /// Cut sentence with `hmm` enabled.
     fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str>;
+
+    /// Cut a maximal run of [`WordCharType::Dictionary`] chars of the given
+    /// `script` into dictionary-sized pieces, the way [`Self::cut_hmm`] does
+    /// for 汉字. The default leaves `sentence` as a single piece -- i.e. no
+    /// internal `w`/`e`/`b` stops -- so implementors that don't care about
+    /// `script` get the old all-one-WORD behavior unchanged. Override this
+    /// to plug in a dictionary- or LSTM-based word breaker, e.g. for
+    /// Thai/Lao/Khmer or Japanese kana.
+    fn cut_dictionary<'a>(
+        &self,
+        _script: Script,
+        sentence: &'a str,
+    ) -> Vec<&'a str> {
+        vec![sentence]
+    }
+
+    /// Interior char-offset cut points to additionally split a maximal run
+    /// of 汉字 that [`Self::cut_hmm`] already cut into one piece, the way
+    /// jieba's "search" mode re-splits long compounds for full-text search
+    /// (e.g. "中华人民共和国" also yields "中华", "华人", "人民", "共和",
+    /// "共和国") so a search-mode motion can step inside them. Returns
+    /// offsets strictly between `0` and `sentence.chars().count()`; they
+    /// need not be sorted or deduplicated, since [`cut_hanzi_search_rule`]
+    /// does that -- jieba's real search cut produces overlapping
+    /// fragments, not plain cut points, so an implementor wrapping it is
+    /// expected to reduce those fragments' boundaries down to this. The
+    /// default returns none, i.e. no intra-word stops, so implementors
+    /// that don't care about this mode get the old behavior unchanged.
+    fn cut_for_search(&self, _sentence: &str) -> Vec<usize> {
+        vec![]
+    }
+
+    /// Cut a maximal run of [`WordCharType::Other`] chars (letters, digits,
+    /// `_`, with no 汉字 or [`Self::cut_dictionary`]-backed script mixed in)
+    /// into sub-identifier pieces, the way [`Self::cut_hmm`] does for 汉字.
+    /// The default leaves `sentence` as a single piece -- i.e. the old
+    /// behavior, where `fooBarBaz` is one `w`/`e`/`b` stop -- so existing
+    /// implementors are unaffected. Override this with [`subword::split`]
+    /// to additionally stop at `camelCase`/`snake_case` boundaries, useful
+    /// for source code buffers mixing identifiers with 汉字 comments.
+    fn cut_other<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        vec![sentence]
+    }
+
+    /// The classifier that decides, for chars outside the CJK-specific
+    /// rules below, whether they start/continue a word, are punctuation, or
+    /// are blank. Defaults to [`CharClassifier::default`]; implementors may
+    /// override this to plug in e.g. a Python-configured classifier.
+    fn classifier(&self) -> &CharClassifier {
+        static DEFAULT: once_cell::sync::Lazy<CharClassifier> =
+            once_cell::sync::Lazy::new(CharClassifier::default);
+        &DEFAULT
+    }
+
+    /// The word-char set [`categorize_char`] consults for the ASCII/Latin-1
+    /// range it would otherwise hardcode, mirroring Vim's buffer-local
+    /// `'iskeyword'` option. Defaults to [`CharClassConfig::default`] (Vim's
+    /// own default `'iskeyword'`); implementors may override this with
+    /// [`CharClassConfig::parse_iskeyword`] to pass a buffer's actual
+    /// `'iskeyword'` value straight through.
+    fn char_class_config(&self) -> &CharClassConfig {
+        static DEFAULT: once_cell::sync::Lazy<CharClassConfig> =
+            once_cell::sync::Lazy::new(CharClassConfig::default);
+        &DEFAULT
+    }
+
+    /// An optional Viterbi-HMM fallback (see [`hmm::HmmModel`]) run over
+    /// every maximal run of consecutive un-dictionaried 汉字 that
+    /// [`Self::cut_hmm`] returned as one-char pieces, before
+    /// [`cut_hanzi_rule`] hands the run's pieces off to
+    /// `CharGroup::split_into_subgroups`. `None` by default, so the
+    /// existing pure-dictionary behavior -- one `Word` stop per
+    /// un-dictionaried character -- is unchanged unless a caller opts in
+    /// with a trained [`hmm::HmmModel`] (or any other [`Segmenter`]).
+    fn hmm_fallback(&self) -> Option<&dyn Segmenter> {
+        None
+    }
+
+    /// Inverse document frequency of `word`, for TF-IDF keyword scoring (see
+    /// the `keyword` module under `motion`). The default returns `1.0` for
+    /// every word, i.e. plain term-frequency ranking, so implementors that
+    /// don't have an IDF table handy get a reasonable fallback instead of a
+    /// missing method. Override this to plug in a corpus-derived table,
+    /// e.g. jieba's bundled `idf.txt` or a Python-configured one.
+    fn idf(&self, _word: &str) -> f64 {
+        1.0
+    }
+}
+
+/// A script whose words aren't delimited by whitespace and aren't 汉字
+/// either, so it needs its own dictionary/LSTM-based segmentation backend
+/// (see [`JiebaPlaceholder::cut_dictionary`]) rather than falling back to
+/// one giant WORD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Script {
+    Thai,
+    Lao,
+    Khmer,
+    /// No longer reached by `categorize_char`'s hardcoded tables -- kana
+    /// gets [`WordCharType::Hiragana`]/[`WordCharType::Katakana`] instead,
+    /// which split at script-transition boundaries without a dictionary
+    /// backend. Kept so a [`CharClassifier::with_category_hook`] callback
+    /// can still route specific chars through
+    /// [`JiebaPlaceholder::cut_dictionary`] if an embedder wants
+    /// dictionary-based kana segmentation instead.
+    Kana,
 }
 
 /// Character types.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum CharType {
     /// Whitespace characters.
     Space,
@@ -31,16 +140,57 @@ enum CharType {
 }
 
 /// Word character types.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum WordCharType {
     /// 汉字 characters.
     Hanzi,
+    /// Characters of a [`Script`] that needs dictionary/LSTM-based
+    /// segmentation instead of whitespace to find word boundaries.
+    Dictionary(Script),
+    /// Japanese hiragana (`\u{3040}..=\u{309f}`).
+    Hiragana,
+    /// Japanese katakana (`\u{30a0}..=\u{30ff}`).
+    Katakana,
+    /// Korean hangul syllables (`\u{ac00}..=\u{d7a3}`).
+    Hangul,
     /// Other word characters.
     Other,
 }
 
+impl From<CharCategory> for CharType {
+    fn from(category: CharCategory) -> Self {
+        match category {
+            CharCategory::Space => CharType::Space,
+            CharCategory::WordHanzi => CharType::Word(WordCharType::Hanzi),
+            CharCategory::WordDictionary(script) => {
+                CharType::Word(WordCharType::Dictionary(script))
+            }
+            CharCategory::WordHiragana => {
+                CharType::Word(WordCharType::Hiragana)
+            }
+            CharCategory::WordKatakana => {
+                CharType::Word(WordCharType::Katakana)
+            }
+            CharCategory::WordHangul => CharType::Word(WordCharType::Hangul),
+            CharCategory::WordOther => CharType::Word(WordCharType::Other),
+            CharCategory::NonWordLeftPunc => {
+                CharType::NonWord(NonWordCharType::LeftPunc)
+            }
+            CharCategory::NonWordRightPunc => {
+                CharType::NonWord(NonWordCharType::RightPunc)
+            }
+            CharCategory::NonWordIsolatedPunc => {
+                CharType::NonWord(NonWordCharType::IsolatedPunc)
+            }
+            CharCategory::NonWordOther => {
+                CharType::NonWord(NonWordCharType::Other)
+            }
+        }
+    }
+}
+
 /// Non-word character types.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 enum NonWordCharType {
     /// Left-associated CJK punctuations. When a word character is followed by
     /// a [`NonWordCharType::LeftPunc`], an implicit space is added in between.
@@ -88,7 +238,30 @@ enum NonWordCharType {
 // by myself, with help from https://www.compart.com/en/unicode. For CJK
 // punctuations that I don't know how to categorize, I've marked them with `??`
 // on the right.
-fn categorize_char(c: char) -> CharType {
+fn categorize_char(
+    c: char,
+    classifier: &CharClassifier,
+    char_class_config: &CharClassConfig,
+) -> CharType {
+    // A `CharClassifier::with_category_hook` callback runs before every rule
+    // below, including the hardcoded CJK/punctuation tables, so an embedder
+    // can reclassify a char the tables would otherwise claim.
+    if let Some(category) = classifier.category_override(c) {
+        return category.into();
+    }
+    // This curated CJK/fullwidth punctuation table is consulted up front,
+    // ahead of both the table-driven match below and
+    // `general_category_classify`: it's an exception list either path
+    // needs, since neither the hardcoded CJK-block ranges nor a
+    // General_Category-derived classifier alone can tell which side of a
+    // punctuation mark implicit whitespace belongs on (see
+    // `NonWordCharType::{LeftPunc,RightPunc,IsolatedPunc}`).
+    if let Some(ty) = curated_cjk_punctuation(c) {
+        return CharType::NonWord(ty);
+    }
+    if char_class_config.general_category_mode() {
+        return general_category_classify(c, classifier, char_class_config);
+    }
     match c {
         // Vim ASCII whitespace.
         ' ' | '\t'
@@ -100,6 +273,13 @@ fn categorize_char(c: char) -> CharType {
         | '\u{303f}'
         => CharType::Space,
 
+        // Remaining Unicode whitespace (NBSP, ogham space, en/em quads,
+        // narrow/medium mathematical spaces, the Mongolian vowel separator,
+        // zero-width no-break space), gated behind
+        // `CharClassConfig::with_unicode_whitespace` since Vim itself
+        // doesn't treat e.g. NBSP as blank by default.
+        c if char_class_config.is_unicode_whitespace(c) => CharType::Space,
+
         // Ideographic number zero.
         | '\u{3007}'
         // CJK unified ideographs.
@@ -122,12 +302,84 @@ fn categorize_char(c: char) -> CharType {
         | '\u{2e80}'..='\u{2ef3}'
         => CharType::Word(WordCharType::Hanzi),
 
-        // Default value of 'iskeyword' in Vim (ASCII range).
-        'a'..='z' | 'A'..='Z' | '0'..='9' | '_'
-        // Default value of 'iskeyword' in Vim (extended ASCII range).
-        | '\u{c0}'..='\u{ff}'
-        => CharType::Word(WordCharType::Other),
+        // Vim's 'iskeyword' word-char set, configurable via
+        // `CharClassConfig` (defaults to Vim's own default 'iskeyword':
+        // ASCII letters/digits/underscore plus the Latin-1 extended range).
+        c if char_class_config.is_word_char(c) => CharType::Word(WordCharType::Other),
+
+        // Thai.
+        '\u{0e00}'..='\u{0e7f}'
+        => CharType::Word(WordCharType::Dictionary(Script::Thai)),
+
+        // Lao.
+        '\u{0e80}'..='\u{0eff}'
+        => CharType::Word(WordCharType::Dictionary(Script::Lao)),
+
+        // Khmer.
+        '\u{1780}'..='\u{17ff}'
+        => CharType::Word(WordCharType::Dictionary(Script::Khmer)),
+
+        // Hiragana. A plain `WordCharType`, not `Dictionary`: unlike Thai/
+        // Lao/Khmer, kana's word boundaries don't need a dictionary
+        // backend -- the script transition itself (kana <-> kanji,
+        // hiragana <-> katakana) is already a natural word edge.
+        '\u{3040}'..='\u{309f}'
+        => CharType::Word(WordCharType::Hiragana),
+
+        // Katakana.
+        '\u{30a0}'..='\u{30ff}'
+        => CharType::Word(WordCharType::Katakana),
+
+        // Hangul syllables. Same reasoning as kana: Korean syllable blocks
+        // are already visually segmented, so no dictionary backend needed.
+        '\u{ac00}'..='\u{d7a3}'
+        => CharType::Word(WordCharType::Hangul),
+
+        // Everything else (Latin/Greek/Cyrillic/etc. letters, digits outside
+        // the ASCII range, emoji, combining marks, ...) is delegated to the
+        // configurable classifier instead of being lumped into
+        // `NonWordCharType::Other`.
+        _ => match classifier.classify(c) {
+            CharClass::Blank => CharType::Space,
+            CharClass::Word => CharType::Word(WordCharType::Other),
+            CharClass::Punct => CharType::NonWord(NonWordCharType::Other),
+        },
+    }
+}
+
+/// Whether `c` falls in one of the curated CJK ideograph block ranges
+/// [`categorize_char`]'s table-driven match hardcodes as
+/// [`WordCharType::Hanzi`]. Factored out so
+/// [`general_category_classify`] can apply the same Hanzi-vs-other-script
+/// distinction on top of a `General_Category`-derived `Lo` classification,
+/// which alone doesn't know about Unicode block boundaries.
+fn is_cjk_ideograph(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3007}'
+        | '\u{4e00}'..='\u{9fff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{20000}'..='\u{2a6df}'
+        | '\u{2a700}'..='\u{2b73f}'
+        | '\u{2b740}'..='\u{2b81f}'
+        | '\u{2f800}'..='\u{2fa1f}'
+        | '\u{2f00}'..='\u{2fd5}'
+        | '\u{2e80}'..='\u{2ef3}'
+    )
+}
 
+/// The crate's curated table of CJK/fullwidth punctuation that needs
+/// implicit-whitespace handling (see
+/// [`NonWordCharType::LeftPunc`]/[`RightPunc`](NonWordCharType::RightPunc)/
+/// [`IsolatedPunc`](NonWordCharType::IsolatedPunc)). Kept as an explicit
+/// exception table consulted ahead of both the table-driven match in
+/// [`categorize_char`] and [`general_category_classify`]'s
+/// `General_Category`-derived rules, since neither the hardcoded CJK-block
+/// ranges nor General_Category alone encode which side of a punctuation
+/// mark implicit whitespace belongs on.
+fn curated_cjk_punctuation(c: char) -> Option<NonWordCharType> {
+    match c {
         // Fullwidth ASCII variants.
         '\u{ff04}' | '\u{ff08}' | '\u{ff3b}' | '\u{ff5b}' | '\u{ff5f}'
         // Halfwidth CJK punctuation.
@@ -138,7 +390,7 @@ fn categorize_char(c: char) -> CharType {
         | '\u{3014}' | '\u{3016}' | '\u{3018}' | '\u{301a}' | '\u{301d}'
         // Quotation marks and apostrophe.
         | '\u{2018}' | '\u{201c}'
-        => CharType::NonWord(NonWordCharType::LeftPunc),
+        => Some(NonWordCharType::LeftPunc),
 
         // Fullwidth ASCII variants.
         '\u{ff09}' | '\u{ff0c}' | '\u{ff1a}' | '\u{ff1b}' | '\u{ff3d}'
@@ -165,7 +417,7 @@ fn categorize_char(c: char) -> CharType {
         | '\u{ff61}'
         // Ideographic full stop.
         | '\u{3002}'
-        => CharType::NonWord(NonWordCharType::RightPunc),
+        => Some(NonWordCharType::RightPunc),
 
         // Fullwidth ASCII variants.
         '\u{ff02}' | '\u{ff03}' |  '\u{ff06}'
@@ -195,10 +447,189 @@ fn categorize_char(c: char) -> CharType {
         | '\u{fe4f}'
         // Latin punctuation.
         | '\u{00b7}'
-        => CharType::NonWord(NonWordCharType::IsolatedPunc),
+        => Some(NonWordCharType::IsolatedPunc),
+
+        _ => None,
+    }
+}
+
+/// The `General_Category`-driven alternative to [`categorize_char`]'s
+/// table-driven match, selected via
+/// [`CharClassConfig::with_general_category_classification`]: `Zs` space
+/// separators classify as `Space`; letters (`Lo`/`Lu`/`Ll`/`Lt`/`Lm`) and
+/// numbers (`Nd`/`Nl`/`No`) classify as `Word` (with `Lo` inside
+/// [`is_cjk_ideograph`]'s ranges still tagged
+/// [`WordCharType::Hanzi`]); everything else is `NonWord`. This tree has no
+/// package manifest to add the real `unicode-general-category` crate, so
+/// the `Lu`/`Ll`/.../`Nd`/`Nl`/`No` buckets are approximated with
+/// [`char::is_alphanumeric`] and [`char::is_whitespace`] rather than true
+/// General_Category lookups, and the `Ps`/`Pi`/`Pe`/`Pf`/`Po` punctuation
+/// subcategories the request asks for aren't distinguishable at all without
+/// that crate -- every non-alphanumeric, non-whitespace char not in
+/// [`curated_cjk_punctuation`]'s exception table falls back to
+/// [`NonWordCharType::Other`] instead.
+fn general_category_classify(
+    c: char,
+    classifier: &CharClassifier,
+    char_class_config: &CharClassConfig,
+) -> CharType {
+    if char_class_config.is_unicode_whitespace(c) || c.is_whitespace() {
+        return CharType::Space;
+    }
+    if char_class_config.is_word_char(c) || c.is_alphanumeric() {
+        return CharType::Word(if is_cjk_ideograph(c) {
+            WordCharType::Hanzi
+        } else {
+            WordCharType::Other
+        });
+    }
+    match classifier.classify(c) {
+        CharClass::Blank => CharType::Space,
+        CharClass::Word => CharType::Word(WordCharType::Other),
+        CharClass::Punct => CharType::NonWord(NonWordCharType::Other),
+    }
+}
+
+/// Approximates the UAX #29 extended grapheme cluster boundary rules that
+/// matter for this file's purposes: CR is never separated from a following
+/// LF (GB3); a combining mark or other `Extend`/`SpacingMark` char never
+/// starts a new cluster (GB9/GB9a); an emoji followed by any number of
+/// `Extend` chars then a ZWJ stays joined to a following emoji (GB11); and
+/// regional-indicator symbols only break in pairs, so a two-codepoint flag
+/// stays one cluster (GB12/GB13). Everything else breaks, same as UAX #29's
+/// default (GB999).
+fn is_extend(c: char, classifier: &CharClassifier) -> bool {
+    // Variation selectors, plus whatever `classifier` considers a combining
+    // mark -- both are `Extend` for UAX #29's purposes, on top of
+    // `CharClassifier::is_combining`'s own (different) purpose of attaching
+    // a mark to the preceding token/group.
+    matches!(c as u32, 0xfe00..=0xfe0f | 0xe0100..=0xe01ef)
+        || classifier.is_combining(c)
+}
+
+/// Approximates the Unicode `Spacing_Mark` (`Mc`) category by listing the
+/// blocks of Indic vowel signs that account for most of its members.
+fn is_spacing_mark(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0903..=0x0903 // Devanagari sign visarga
+        | 0x093b..=0x093b
+        | 0x093e..=0x0940
+        | 0x0949..=0x094c
+        | 0x094e..=0x094f
+        | 0x0982..=0x0983 // Bengali
+        | 0x09be..=0x09c0
+        | 0x09c7..=0x09c8
+        | 0x09cb..=0x09cc
+        | 0x09d7..=0x09d7
+    )
+}
+
+fn is_zwj(c: char) -> bool {
+    c == '\u{200d}'
+}
 
-        _ => CharType::NonWord(NonWordCharType::Other),
+fn is_regional_indicator(c: char) -> bool {
+    matches!(c as u32, 0x1f1e6..=0x1f1ff)
+}
+
+/// Approximates `Extended_Pictographic` with the emoji-heavy blocks that
+/// matter for GB11 (ZWJ sequences); not meant to be exhaustive.
+fn is_extended_pictographic(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x2600..=0x27bf // Misc symbols, Dingbats
+        | 0x1f300..=0x1faff // Misc symbols and pictographs, emoticons, ...
+    )
+}
+
+/// Whether a grapheme cluster boundary ever follows a char that's been
+/// classified as the start of an emoji-ZWJ chain, so a following
+/// `is_extended_pictographic` char stays glued to it (GB11).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClusterChain {
+    None,
+    /// Saw an `Extended_Pictographic` char, possibly followed by `Extend`
+    /// chars since.
+    Pictographic,
+    /// `Pictographic`, then a ZWJ: the next `Extended_Pictographic` glues on.
+    PictographicZwj,
+    /// Saw an odd number of regional-indicator chars so far in this
+    /// cluster: the next one pairs with it instead of starting a new
+    /// cluster.
+    RegionalIndicator,
+}
+
+fn advance_cluster_chain(
+    chain: ClusterChain,
+    c: char,
+    classifier: &CharClassifier,
+) -> ClusterChain {
+    if is_extend(c, classifier) {
+        match chain {
+            ClusterChain::Pictographic => ClusterChain::Pictographic,
+            _ => ClusterChain::None,
+        }
+    } else if is_zwj(c) {
+        match chain {
+            ClusterChain::Pictographic => ClusterChain::PictographicZwj,
+            _ => ClusterChain::None,
+        }
+    } else if is_extended_pictographic(c) {
+        ClusterChain::Pictographic
+    } else if is_regional_indicator(c) {
+        match chain {
+            ClusterChain::RegionalIndicator => ClusterChain::None,
+            _ => ClusterChain::RegionalIndicator,
+        }
+    } else {
+        ClusterChain::None
+    }
+}
+
+fn is_cluster_break(
+    prev: char,
+    cur: char,
+    chain: ClusterChain,
+    classifier: &CharClassifier,
+) -> bool {
+    if prev == '\r' && cur == '\n' {
+        return false; // GB3
+    }
+    if is_extend(cur, classifier) || is_spacing_mark(cur) || is_zwj(cur) {
+        return false; // GB9, extended to ZWJ same as UAX #29 does
+    }
+    if chain == ClusterChain::PictographicZwj && is_extended_pictographic(cur)
+    {
+        return false; // GB11
+    }
+    if chain == ClusterChain::RegionalIndicator && is_regional_indicator(cur) {
+        return false; // GB12/GB13
     }
+    true
+}
+
+/// The exclusive end byte offset of each extended grapheme cluster in
+/// `line`, in order, the last one always equal to `line.len()`. An empty
+/// `line` yields an empty vec.
+fn grapheme_cluster_ends(line: &str, classifier: &CharClassifier) -> Vec<usize> {
+    let mut ends = Vec::new();
+    let mut chars = line.char_indices();
+    let Some((_, first)) = chars.next() else {
+        return ends;
+    };
+    let mut prev = first;
+    let mut chain = advance_cluster_chain(ClusterChain::None, first, classifier);
+    for (idx, c) in chars {
+        if is_cluster_break(prev, c, chain, classifier) {
+            ends.push(idx);
+            chain = ClusterChain::None;
+        }
+        chain = advance_cluster_chain(chain, c, classifier);
+        prev = c;
+    }
+    ends.push(line.len());
+    ends
 }
 
 /// The column location of a char or a token in a line.
@@ -213,34 +644,91 @@ pub(crate) struct Col {
     pub excl_end_byte_index: usize,
 }
 
-/// A Char token.
+/// A Char token. May represent a full extended grapheme cluster (a base
+/// scalar plus any combining marks or ZWJ-joined scalars) rather than a
+/// single Unicode scalar -- see [`Self::new_cluster`].
 #[derive(Debug)]
 struct Char {
     ch: char,
+    /// The full text of the char, i.e. every scalar of the cluster `ch`
+    /// leads. Equal to `ch.to_string()` for a single-scalar [`Self::new`]
+    /// char. Kept around so a [`CharGroup`] built out of these never drops
+    /// a trailing combining mark or ZWJ continuation when it's later
+    /// rendered back to a `String` (e.g. for handing to the jieba cutter).
+    text: String,
     col: Col,
     ty: CharType,
 }
 
 impl Char {
-    fn new(ch: char, start_byte_index: usize) -> Self {
+    fn new(
+        ch: char,
+        start_byte_index: usize,
+        classifier: &CharClassifier,
+        char_class_config: &CharClassConfig,
+    ) -> Self {
         Self {
             ch,
+            text: ch.to_string(),
             col: Col {
                 start_byte_index,
                 incl_end_byte_index: start_byte_index,
                 excl_end_byte_index: start_byte_index + ch.len_utf8(),
             },
-            ty: categorize_char(ch),
+            ty: categorize_char(ch, classifier, char_class_config),
+        }
+    }
+
+    /// Like [`Self::new`], but `cluster` is a full extended grapheme
+    /// cluster (UAX #29) starting at `start_byte_index` rather than a
+    /// single scalar -- e.g. a base letter plus its combining marks, or a
+    /// ZWJ-joined emoji sequence. `cluster`'s first scalar is what gets
+    /// categorized and stored as `ch`; `text` and `col` cover the whole
+    /// cluster, so later byte-offset arithmetic (and thus `w`/`e`/`b`) and
+    /// rendering back to a `String` can't land in, or drop, the middle of
+    /// it.
+    fn new_cluster(
+        cluster: &str,
+        start_byte_index: usize,
+        classifier: &CharClassifier,
+        char_class_config: &CharClassConfig,
+    ) -> Self {
+        let ch = cluster.chars().next().unwrap();
+        let (last_offset, last_ch) = cluster.char_indices().last().unwrap();
+        Self {
+            ch,
+            text: cluster.to_string(),
+            col: Col {
+                start_byte_index,
+                incl_end_byte_index: start_byte_index + last_offset,
+                excl_end_byte_index: start_byte_index
+                    + last_offset
+                    + last_ch.len_utf8(),
+            },
+            ty: categorize_char(ch, classifier, char_class_config),
         }
     }
 }
 
 /// The string `line` should not contain the end-of-line character. Return a
-/// vec of `Char`s. An empty returned vec signifies that the `line` is empty.
-fn parse_str_into_chars(line: &str) -> Vec<Char> {
-    line.char_indices()
-        .map(|(start_byte_index, ch)| Char::new(ch, start_byte_index))
-        .collect()
+/// vec of `Char`s, one per extended grapheme cluster (see
+/// [`grapheme_cluster_ends`]) rather than per Unicode scalar, so `Col`
+/// never lands in the middle of e.g. a base char's combining marks, a
+/// ZWJ-joined emoji sequence, or a flag. An empty returned vec signifies
+/// that the `line` is empty.
+fn parse_str_into_chars(
+    line: &str,
+    classifier: &CharClassifier,
+    char_class_config: &CharClassConfig,
+) -> Vec<Char> {
+    let mut chars: Vec<Char> = Vec::new();
+    let mut start = 0;
+    for end in grapheme_cluster_ends(line, classifier) {
+        let c = Char::new_cluster(&line[start..end], start, classifier, char_class_config);
+        chars.push(c);
+        start = end;
+    }
+    chars
 }
 
 /// Character group types.
@@ -260,8 +748,19 @@ enum CharGroupType {
 enum WordCharGroupType {
     /// A sequence of [`CharType::Word`] that contains [`WordCharType::Hanzi`].
     Hanzi,
+    /// A sequence of [`CharType::Word`] made up of [`WordCharType::Dictionary`]
+    /// chars of a single [`Script`].
+    Dictionary(Script),
+    /// A sequence of [`CharType::Word`] that contains
+    /// [`WordCharType::Hiragana`], with no [`WordCharType::Katakana`],
+    /// [`WordCharType::Hangul`], or [`WordCharType::Hanzi`] mixed in.
+    Hiragana,
+    /// Same as [`Self::Hiragana`] but for [`WordCharType::Katakana`].
+    Katakana,
+    /// Same as [`Self::Hiragana`] but for [`WordCharType::Hangul`].
+    Hangul,
     /// A sequence of [`CharType::Word`] that doesn't contain
-    /// [`WordCharType::Hanzi`].
+    /// [`WordCharType::Hanzi`] or [`WordCharType::Dictionary`].
     Other,
 }
 
@@ -292,7 +791,11 @@ enum NonWordCharGroupType {
 
 #[derive(Debug, PartialEq, Eq)]
 struct CharGroup {
-    chars: Vec<char>,
+    /// The full text of every [`Char`] (cluster or single scalar) in the
+    /// group, one entry each, in order. Kept whole per-`Char` rather than
+    /// flattened into a single `String` so [`Self::split_into_subgroups`]
+    /// can still tell where one cluster ends and the next begins.
+    chars: Vec<String>,
     col: Col,
     ty: CharGroupType,
 }
@@ -300,13 +803,25 @@ struct CharGroup {
 impl From<Char> for CharGroup {
     fn from(c: Char) -> Self {
         Self {
-            chars: vec![c.ch],
+            chars: vec![c.text],
             col: c.col,
             ty: match c.ty {
                 CharType::Space => CharGroupType::Space,
                 CharType::Word(WordCharType::Hanzi) => {
                     CharGroupType::Word(WordCharGroupType::Hanzi)
                 }
+                CharType::Word(WordCharType::Dictionary(script)) => {
+                    CharGroupType::Word(WordCharGroupType::Dictionary(script))
+                }
+                CharType::Word(WordCharType::Hiragana) => {
+                    CharGroupType::Word(WordCharGroupType::Hiragana)
+                }
+                CharType::Word(WordCharType::Katakana) => {
+                    CharGroupType::Word(WordCharGroupType::Katakana)
+                }
+                CharType::Word(WordCharType::Hangul) => {
+                    CharGroupType::Word(WordCharGroupType::Hangul)
+                }
                 CharType::Word(WordCharType::Other) => {
                     CharGroupType::Word(WordCharGroupType::Other)
                 }
@@ -364,11 +879,42 @@ impl CharGroup {
         match (&self.ty, &c.ty) {
             (G::Space, Space) => (),
 
-            (G::Word(WG::Hanzi), Word(_)) => (),
+            // `Hanzi` absorbs anything except kana/hangul, so the whole run
+            // (including embedded `Other`/`Dictionary` chars, e.g. "B超")
+            // gets sent to `cut_hanzi_rule` as one group. Kana and hangul
+            // are excluded on purpose: a kana<->kanji or hangul<->kanji
+            // transition is already a natural word edge, so it should split
+            // the group here rather than get glued into the Chinese cutter.
+            (G::Word(WG::Hanzi), Word(W::Hanzi))
+            | (G::Word(WG::Hanzi), Word(W::Dictionary(_)))
+            | (G::Word(WG::Hanzi), Word(W::Other)) => (),
+
+            (G::Word(WG::Dictionary(gs)), Word(W::Dictionary(cs)))
+                if gs == cs => {}
+            (G::Word(WG::Dictionary(_)), Word(W::Other)) => (),
+
+            (G::Word(WG::Hiragana), Word(W::Hiragana))
+            | (G::Word(WG::Hiragana), Word(W::Other)) => (),
+            (G::Word(WG::Katakana), Word(W::Katakana))
+            | (G::Word(WG::Katakana), Word(W::Other)) => (),
+            (G::Word(WG::Hangul), Word(W::Hangul))
+            | (G::Word(WG::Hangul), Word(W::Other)) => (),
 
             (G::Word(WG::Other), Word(W::Hanzi)) => {
                 self.ty = G::Word(WG::Hanzi);
             }
+            (G::Word(WG::Other), Word(W::Dictionary(script))) => {
+                self.ty = G::Word(WG::Dictionary(*script));
+            }
+            (G::Word(WG::Other), Word(W::Hiragana)) => {
+                self.ty = G::Word(WG::Hiragana);
+            }
+            (G::Word(WG::Other), Word(W::Katakana)) => {
+                self.ty = G::Word(WG::Katakana);
+            }
+            (G::Word(WG::Other), Word(W::Hangul)) => {
+                self.ty = G::Word(WG::Hangul);
+            }
             (G::Word(WG::Other), Word(W::Other)) => (),
 
             (G::NonWord(NG::LeftPuncLeading), NonWord(N::LeftPunc))
@@ -411,7 +957,7 @@ impl CharGroup {
 
             _ => return Err(c),
         }
-        self.chars.push(c.ch);
+        self.chars.push(c.text);
         self.col.incl_end_byte_index = c.col.incl_end_byte_index;
         self.col.excl_end_byte_index = c.col.excl_end_byte_index;
         Ok(())
@@ -431,7 +977,7 @@ impl CharGroup {
 // `std::fmt::Display`.
 impl ToString for CharGroup {
     fn to_string(&self) -> String {
-        self.chars.iter().collect()
+        self.chars.concat()
     }
 }
 
@@ -472,6 +1018,13 @@ fn group_chars_rule(
                     }
                     (NonWord(_), Word(_)) => vec![group, c],
 
+                    // Two incompatible `Word` subtypes, e.g. a script
+                    // transition (kanji -> hiragana, hiragana -> katakana,
+                    // hangul -> kanji, ...) or an unmerged `Dictionary`
+                    // pair. No implicit whitespace: they're still both
+                    // words, just split at the boundary between them.
+                    (Word(_), Word(_)) => vec![group, c],
+
                     // Should not happen.
                     _ => panic!(),
                 }
@@ -482,17 +1035,26 @@ fn group_chars_rule(
 }
 
 impl CharGroup {
-    /// Split `self` into subgroups, whose types will be recategorized. Panics
-    /// if `self.chars.len() != sizes.sum()`.
-    fn split_into_subgroups(self, sizes: Vec<usize>) -> Vec<CharGroup> {
+    /// Split `self` into subgroups, whose types will be recategorized.
+    /// `sizes` counts in [`Char`]s (i.e. grapheme clusters), not raw
+    /// scalars, so a cluster's combining marks or ZWJ continuations always
+    /// land in the same subgroup as its base scalar and the incl/excl byte
+    /// indices never come apart in the middle of one. Panics if
+    /// `self.chars.len() != sizes.sum()`.
+    fn split_into_subgroups(
+        self,
+        sizes: Vec<usize>,
+        classifier: &CharClassifier,
+        char_class_config: &CharClassConfig,
+    ) -> Vec<CharGroup> {
         assert_eq!(self.chars.len(), sizes.iter().sum::<usize>());
         let mut sub_groups = Vec::with_capacity(sizes.len());
         let mut chars = self.chars.into_iter();
         let mut start = self.col.start_byte_index;
         for sz in sizes {
             let mut sub_chars = (0..sz).map(|_| {
-                let ch = chars.next().unwrap();
-                let ch = Char::new(ch, start);
+                let text = chars.next().unwrap();
+                let ch = Char::new_cluster(&text, start, classifier, char_class_config);
                 start = ch.col.excl_end_byte_index;
                 ch
             });
@@ -541,6 +1103,47 @@ fn insert_implicit_whitespace_in_cut_result_rule(
     }
 }
 
+/// Re-split every maximal run of consecutive one-char pieces in `n_chars`
+/// (as produced by [`JiebaPlaceholder::cut_hmm`]) via `segmenter`, leaving
+/// every other, already dictionary-matched piece untouched. `cut_hmm`'s
+/// only signal that a character wasn't matched against a dictionary word is
+/// that it came back as its own one-char piece, so a maximal run of those
+/// is this function's (and [`JiebaPlaceholder::hmm_fallback`]'s) notion of
+/// "un-dictionaried 汉字" -- a lone un-dictionaried character is left as is,
+/// since there's nothing for an HMM to decide between.
+fn apply_hmm_fallback(
+    s: &str,
+    n_chars: Vec<usize>,
+    segmenter: &dyn Segmenter,
+) -> Vec<usize> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut out = Vec::with_capacity(n_chars.len());
+    let mut char_offset = 0;
+    let mut i = 0;
+    while i < n_chars.len() {
+        if n_chars[i] == 1 {
+            let run_start = char_offset;
+            let mut run_len = 0;
+            while i < n_chars.len() && n_chars[i] == 1 {
+                run_len += 1;
+                char_offset += 1;
+                i += 1;
+            }
+            if run_len > 1 {
+                let run: String = chars[run_start..char_offset].iter().collect();
+                out.extend(segmenter.cut(&run));
+            } else {
+                out.push(1);
+            }
+        } else {
+            out.push(n_chars[i]);
+            char_offset += n_chars[i];
+            i += 1;
+        }
+    }
+    out
+}
+
 /// Cut [`CharGroup`]s of type [`WordCharGroupType::Hanzi`] into sub groups,
 /// and insert implicit whitespaces in between. Since this merging rule
 /// ought to be used after [`group_chars_rule`], we won't need to care about
@@ -561,12 +1164,118 @@ fn cut_hanzi_rule<C: JiebaPlaceholder>(
     match group.ty {
         Word(W::Hanzi) => {
             let s = group.to_string();
-            let n_chars: Vec<_> = jieba
+            let mut n_chars: Vec<_> = jieba
                 .cut_hmm(&s)
                 .into_iter()
                 .map(|part| part.chars().count())
                 .collect();
-            let sub_groups = group.split_into_subgroups(n_chars);
+            if let Some(segmenter) = jieba.hmm_fallback() {
+                n_chars = apply_hmm_fallback(&s, n_chars, segmenter);
+            }
+            let sub_groups = group.split_into_subgroups(
+                n_chars,
+                jieba.classifier(),
+                jieba.char_class_config(),
+            );
+            utils::chain_into_vec(
+                prev_group,
+                utils::stack_merge(
+                    sub_groups,
+                    &(),
+                    insert_implicit_whitespace_in_cut_result_rule,
+                ),
+            )
+        }
+
+        // Otherwise, return as is.
+        _ => utils::chain_into_vec(prev_group, [group]),
+    }
+}
+
+/// Convert `parts` -- a split of `clusters.concat()` produced by a cutter
+/// that only knows about `char`s -- into sizes counted in clusters, for
+/// [`CharGroup::split_into_subgroups`]. `clusters` is assumed to partition
+/// the same bytes as `parts`, i.e. neither splits a byte the other keeps
+/// whole; this holds unless a cutter splits in the middle of a multi-scalar
+/// grapheme cluster (a base letter plus combining marks, or a ZWJ
+/// sequence), which none of ours do.
+fn cluster_sizes(clusters: &[String], parts: &[&str]) -> Vec<usize> {
+    let mut cluster_lens = clusters.iter().map(String::len);
+    parts
+        .iter()
+        .map(|part| {
+            let mut consumed = 0;
+            let mut n = 0;
+            while consumed < part.len() {
+                consumed += cluster_lens
+                    .next()
+                    .expect("parts and clusters should cover the same bytes");
+                n += 1;
+            }
+            n
+        })
+        .collect()
+}
+
+/// Cut [`CharGroup`]s of type [`WordCharGroupType::Dictionary`] into sub
+/// groups via [`JiebaPlaceholder::cut_dictionary`], and insert implicit
+/// whitespaces in between, mirroring [`cut_hanzi_rule`] for scripts whose
+/// words aren't whitespace-delimited but also aren't 汉字 (e.g. Thai, Lao,
+/// Khmer, or Japanese kana).
+fn cut_dictionary_rule<C: JiebaPlaceholder>(
+    prev_group: Option<CharGroup>,
+    group: CharGroup,
+    jieba: &C,
+) -> Vec<CharGroup> {
+    use CharGroupType::*;
+    use WordCharGroupType as W;
+    match group.ty {
+        Word(W::Dictionary(script)) => {
+            let s = group.to_string();
+            let parts = jieba.cut_dictionary(script, &s);
+            let sizes = cluster_sizes(&group.chars, &parts);
+            let sub_groups = group.split_into_subgroups(
+                sizes,
+                jieba.classifier(),
+                jieba.char_class_config(),
+            );
+            utils::chain_into_vec(
+                prev_group,
+                utils::stack_merge(
+                    sub_groups,
+                    &(),
+                    insert_implicit_whitespace_in_cut_result_rule,
+                ),
+            )
+        }
+
+        // Otherwise, return as is.
+        _ => utils::chain_into_vec(prev_group, [group]),
+    }
+}
+
+/// Cut [`CharGroup`]s of type [`WordCharGroupType::Other`] into sub groups
+/// via [`JiebaPlaceholder::cut_other`], and insert implicit whitespaces in
+/// between, mirroring [`cut_dictionary_rule`] for plain ASCII-ish
+/// identifiers whose internal word boundaries (if any) come from
+/// `camelCase`/`snake_case` casing rather than a script dictionary.
+fn cut_other_rule<C: JiebaPlaceholder>(
+    prev_group: Option<CharGroup>,
+    group: CharGroup,
+    jieba: &C,
+) -> Vec<CharGroup> {
+    use CharGroupType::*;
+    use WordCharGroupType as W;
+    match group.ty {
+        Word(W::Other) => {
+            let s = group.to_string();
+            let parts = jieba.cut_other(&s);
+            let sizes = cluster_sizes(&group.chars, &parts);
+            let sub_groups = group.split_into_subgroups(
+                sizes,
+                jieba.classifier(),
+                jieba.char_class_config(),
+            );
             utils::chain_into_vec(
                 prev_group,
                 utils::stack_merge(
@@ -582,6 +1291,52 @@ fn cut_hanzi_rule<C: JiebaPlaceholder>(
     }
 }
 
+/// Further split each [`WordCharGroupType::Hanzi`] group of 3 or more 汉字
+/// -- already cut by [`cut_hanzi_rule`] -- at the points
+/// [`JiebaPlaceholder::cut_for_search`] returns, so a search-mode motion
+/// can step inside long compounds (e.g. "中华人民共和国") instead of jumping
+/// over the whole word. Shorter groups are left as is: there's no interior
+/// stop worth adding to a 1- or 2-character word. No implicit whitespace is
+/// inserted between the resulting sub-pieces: they're still the same word,
+/// just with extra stops inside it.
+fn cut_hanzi_search_rule<C: JiebaPlaceholder>(
+    prev_group: Option<CharGroup>,
+    group: CharGroup,
+    jieba: &C,
+) -> Vec<CharGroup> {
+    use CharGroupType::*;
+    use WordCharGroupType as W;
+    match group.ty {
+        Word(W::Hanzi) if group.chars.len() >= 3 => {
+            let s = group.to_string();
+            let n_chars = s.chars().count();
+            let mut cuts: Vec<usize> = jieba
+                .cut_for_search(&s)
+                .into_iter()
+                .filter(|&cut| cut > 0 && cut < n_chars)
+                .collect();
+            cuts.sort_unstable();
+            cuts.dedup();
+            let mut sizes = Vec::with_capacity(cuts.len() + 1);
+            let mut prev = 0;
+            for cut in cuts {
+                sizes.push(cut - prev);
+                prev = cut;
+            }
+            sizes.push(n_chars - prev);
+            let sub_groups = group.split_into_subgroups(
+                sizes,
+                jieba.classifier(),
+                jieba.char_class_config(),
+            );
+            utils::chain_into_vec(prev_group, sub_groups)
+        }
+
+        // Otherwise, return as is.
+        _ => utils::chain_into_vec(prev_group, [group]),
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub(crate) struct Token {
     pub col: Col,
@@ -590,6 +1345,7 @@ pub(crate) struct Token {
 
 /// Token types.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub(crate) enum TokenType {
     /// Either a word or a WORD token, depending on the context.
     ///
@@ -599,6 +1355,13 @@ pub(crate) enum TokenType {
     /// characters.
     Word,
     Space,
+    /// A run of non-blank, non-keyword characters, e.g. punctuation --
+    /// only produced by [`classify::Classifier::split`] reclassifying a
+    /// [`TokenType::Word`] token, never by jieba segmentation itself.
+    Punctuation,
+    /// A run of text from [`parse_str_into_sentences`] bounded by sentence
+    /// terminators rather than whitespace; may itself contain whitespace.
+    Sentence,
 }
 
 impl From<CharGroup> for Token {
@@ -640,6 +1403,8 @@ fn parse_chars_into_words<C: JiebaPlaceholder>(
 ) -> Vec<Token> {
     let groups = utils::stack_merge(chars, &(), group_chars_rule);
     let groups = utils::stack_merge(groups, jieba, cut_hanzi_rule);
+    let groups = utils::stack_merge(groups, jieba, cut_dictionary_rule);
+    let groups = utils::stack_merge(groups, jieba, cut_other_rule);
     let groups =
         utils::stack_merge(groups, &(), remove_implicit_whitespace_rule);
     groups.into_iter().map(Token::from).collect()
@@ -675,135 +1440,1505 @@ fn parse_chars_into_WORDs<C: JiebaPlaceholder>(
 ) -> Vec<Token> {
     let groups = utils::stack_merge(chars, &(), group_chars_rule);
     let groups = utils::stack_merge(groups, jieba, cut_hanzi_rule);
+    let groups = utils::stack_merge(groups, jieba, cut_dictionary_rule);
+    let groups = utils::stack_merge(groups, jieba, cut_other_rule);
     let groups = utils::stack_merge(groups, &(), concat_nonspace_groups_rule);
     let groups =
         utils::stack_merge(groups, &(), remove_implicit_whitespace_rule);
     groups.into_iter().map(Token::from).collect()
 }
 
-/// Parse `line` into tokens. If `into_word` is `true`, the non-space tokens
-/// will be interpretable as `word`s; otherwise, they will be `WORD`s.
+/// The granularity [`parse_str`] segments a line into, corresponding to
+/// Vim's `word` ([`Granularity::Word`]) and `WORD` ([`Granularity::WORD`])
+/// textobjects, plus a third, jieba-inspired [`Granularity::Search`] mode
+/// that keeps `word`-level segmentation but additionally splits every
+/// maximal run of 汉字 at its [`JiebaPlaceholder::cut_for_search`] cut
+/// points, so a search-style motion can stop inside a long compound (e.g.
+/// "中华人民共和国") instead of jumping over the whole word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(non_camel_case_types)]
+pub(crate) enum Granularity {
+    Word,
+    WORD,
+    Search,
+}
+
+impl From<bool> for Granularity {
+    /// `true` maps to [`Granularity::Word`], `false` to
+    /// [`Granularity::WORD`] -- the same convention every `word: bool`
+    /// parameter elsewhere in this crate already uses.
+    fn from(word: bool) -> Self {
+        if word {
+            Granularity::Word
+        } else {
+            Granularity::WORD
+        }
+    }
+}
+
+/// Parse `line` into tokens at the given [`Granularity`].
 pub(crate) fn parse_str<S: AsRef<str>, C: JiebaPlaceholder>(
     line: S,
     jieba: &C,
-    into_word: bool,
+    granularity: Granularity,
 ) -> Vec<Token> {
-    let chars = parse_str_into_chars(line.as_ref());
-    if into_word {
-        parse_chars_into_words(chars, jieba)
-    } else {
-        parse_chars_into_WORDs(chars, jieba)
+    let chars =
+        parse_str_into_chars(line.as_ref(), jieba.classifier(), jieba.char_class_config());
+    match granularity {
+        Granularity::Word => parse_chars_into_words(chars, jieba),
+        Granularity::WORD => parse_chars_into_WORDs(chars, jieba),
+        Granularity::Search => {
+            let groups = utils::stack_merge(chars, &(), group_chars_rule);
+            let groups = utils::stack_merge(groups, jieba, cut_hanzi_rule);
+            let groups = utils::stack_merge(groups, jieba, cut_dictionary_rule);
+            let groups = utils::stack_merge(groups, jieba, cut_other_rule);
+            let groups = utils::stack_merge(groups, jieba, cut_hanzi_search_rule);
+            let groups =
+                utils::stack_merge(groups, &(), remove_implicit_whitespace_rule);
+            groups.into_iter().map(Token::from).collect()
+        }
     }
 }
 
-/// A token or an empty line.
-pub(crate) trait TokenLike {
-    /// The byte position of the first character in the token.
-    fn first_char(&self) -> usize;
-    /// The byte position of the last character in the token.
-    fn last_char(&self) -> usize;
-    /// The byte position of the end of the last character in the token.
-    fn last_char1(&self) -> usize;
+/// A set of literal "atoms" -- URLs, paths, operators like `::`/`=>`, emoji
+/// clusters, or anything else that must stay one `word`/`WORD` token no
+/// matter what jieba's own cutters would otherwise do to it -- matched via
+/// a single Aho-Corasick automaton built once at construction, rather than
+/// one substring search per registered atom per line. Plug one into a
+/// [`crate::motion::WordMotion`] via
+/// [`with_atoms`](crate::motion::WordMotion::with_atoms).
+pub struct AtomMatcher {
+    automaton: AhoCorasick,
 }
 
-impl TokenLike for Token {
-    fn first_char(&self) -> usize {
-        self.col.start_byte_index
+impl AtomMatcher {
+    /// Build the automaton from `atoms`. Matching uses
+    /// [`MatchKind::LeftmostLongest`], so on overlap the match starting
+    /// earliest wins, ties broken by the longer match -- e.g. registering
+    /// both `"::"` and `":::"` keeps a `":::"` run as one atom instead of
+    /// splitting it into `"::"` plus a stray `":"`.
+    pub fn new<I, S>(atoms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<[u8]>,
+    {
+        let automaton = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(atoms)
+            .expect("atom patterns form a valid Aho-Corasick automaton");
+        Self { automaton }
     }
 
-    fn last_char(&self) -> usize {
-        self.col.incl_end_byte_index
+    /// The non-overlapping byte spans of `line` covered by a registered
+    /// atom, in left-to-right order.
+    fn protected_spans(&self, line: &str) -> Vec<(usize, usize)> {
+        self.automaton
+            .find_iter(line)
+            .map(|m| (m.start(), m.end()))
+            .collect()
     }
+}
 
-    fn last_char1(&self) -> usize {
-        self.col.excl_end_byte_index
+/// Shift every byte offset in `token` by `offset`, for splicing a
+/// [`Token`] parsed from a substring of a line back into that line's own
+/// coordinates.
+fn shift_token(token: Token, offset: usize) -> Token {
+    Token {
+        col: Col {
+            start_byte_index: token.col.start_byte_index + offset,
+            incl_end_byte_index: token.col.incl_end_byte_index + offset,
+            excl_end_byte_index: token.col.excl_end_byte_index + offset,
+        },
+        ty: token.ty,
     }
 }
 
-// `None` is used to denote the empty line.
-impl TokenLike for Option<Token> {
-    fn first_char(&self) -> usize {
-        self.map(|t| t.first_char()).unwrap_or(0)
+/// Like [`parse_str`], but first carves `atoms`'s registered spans out of
+/// `line` as single opaque [`TokenType::Word`] tokens, then only runs
+/// [`parse_str`] over the gaps between them -- so a URL, operator, or other
+/// registered atom survives as one token even though it mixes char classes
+/// that [`CharGroup::push`] would otherwise never merge into the same
+/// group. `atoms` being `None`, or matching nothing in `line`, is
+/// identical to calling [`parse_str`] directly.
+pub(crate) fn parse_str_with_atoms<S: AsRef<str>, C: JiebaPlaceholder>(
+    line: S,
+    jieba: &C,
+    granularity: Granularity,
+    atoms: Option<&AtomMatcher>,
+) -> Vec<Token> {
+    let line = line.as_ref();
+    let spans = atoms
+        .map(|matcher| matcher.protected_spans(line))
+        .unwrap_or_default();
+    if spans.is_empty() {
+        return parse_str(line, jieba, granularity);
     }
 
-    fn last_char(&self) -> usize {
-        self.map(|t| t.last_char()).unwrap_or(0)
+    let mut tokens = Vec::new();
+    let mut cursor = 0;
+    for (start, end) in spans {
+        if cursor < start {
+            tokens.extend(
+                parse_str(&line[cursor..start], jieba, granularity)
+                    .into_iter()
+                    .map(|tok| shift_token(tok, cursor)),
+            );
+        }
+        let (last_offset, last_ch) = line[start..end].char_indices().last().unwrap();
+        tokens.push(Token {
+            col: Col {
+                start_byte_index: start,
+                incl_end_byte_index: start + last_offset,
+                excl_end_byte_index: start + last_offset + last_ch.len_utf8(),
+            },
+            ty: TokenType::Word,
+        });
+        cursor = end;
     }
-
-    fn last_char1(&self) -> usize {
-        self.map(|t| t.last_char1()).unwrap_or(0)
+    if cursor < line.len() {
+        tokens.extend(
+            parse_str(&line[cursor..], jieba, granularity)
+                .into_iter()
+                .map(|tok| shift_token(tok, cursor)),
+        );
     }
+    tokens
 }
 
-#[cfg(test)]
-pub(crate) mod test_macros {
-    #[macro_export]
-    macro_rules! token {
-        ($i:literal, $j:literal, $k:literal, $t:ident) => {
-            crate::token::Token {
-                col: crate::token::Col {
-                    start_byte_index: $i,
-                    incl_end_byte_index: $j,
-                    excl_end_byte_index: $k,
-                },
-                ty: crate::token::TokenType::$t,
-            }
-        };
-    }
-
-    pub use token;
+/// A lazy, streaming counterpart to [`parse_str`]: yields [`Token`]s one at
+/// a time by pulling the next [`CharGroup`] through the same group/cut/
+/// merge pipeline on demand (see [`utils::StackMergeIter`]), the way
+/// `rustc_lexer` streams tokens off a `&str` instead of tokenizing it all
+/// up front. Every yielded [`Token`]'s [`Col`] has the same
+/// `start_byte_index`/`incl_end_byte_index`/`excl_end_byte_index` contract
+/// as [`parse_str`]'s -- this just defers the work, it doesn't change it --
+/// so a caller that only needs the next boundary or two relative to the
+/// cursor (most motions) can stop pulling early instead of paying for the
+/// rest of the line.
+///
+/// The per-stage pipeline yields a different concrete [`utils::StackMergeIter`]
+/// type depending on [`Granularity`], so [`tokenize`] erases it behind a
+/// `dyn Iterator` once built; char classification/grapheme clustering
+/// ([`parse_str_into_chars`]) still runs eagerly over the whole line before
+/// that, since it scans the raw `&str` rather than merging a `CharGroup`
+/// stream.
+pub(crate) struct Tokens<'a> {
+    inner: Box<dyn Iterator<Item = CharGroup> + 'a>,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use jieba_rs::Jieba;
-    use once_cell::sync::OnceCell;
-    use proptest::prelude::*;
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token;
 
-    impl JiebaPlaceholder for Jieba {
-        fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
-            self.cut(sentence, true)
-        }
+    fn next(&mut self) -> Option<Token> {
+        self.inner.next().map(Token::from)
     }
+}
 
-    #[test]
-    fn test_categorize_char_sanity_check() {
-        assert!(matches!(
-            categorize_char('-'),
-            CharType::NonWord(NonWordCharType::Other)
-        ));
-        assert!(matches!(
-            categorize_char(','),
+/// Lazily tokenize `line` at the given [`Granularity`] -- see [`Tokens`].
+pub(crate) fn tokenize<'a, C: JiebaPlaceholder + 'a>(
+    line: &'a str,
+    jieba: &'a C,
+    granularity: Granularity,
+) -> Tokens<'a> {
+    let chars =
+        parse_str_into_chars(line, jieba.classifier(), jieba.char_class_config());
+    let groups = utils::StackMergeIter::new(chars.into_iter(), &(), group_chars_rule);
+    let groups = utils::StackMergeIter::new(groups, jieba, cut_hanzi_rule);
+    let groups = utils::StackMergeIter::new(groups, jieba, cut_dictionary_rule);
+    let groups = utils::StackMergeIter::new(groups, jieba, cut_other_rule);
+    let inner: Box<dyn Iterator<Item = CharGroup> + 'a> = match granularity {
+        Granularity::Word => Box::new(utils::StackMergeIter::new(
+            groups,
+            &(),
+            remove_implicit_whitespace_rule,
+        )),
+        Granularity::WORD => {
+            let groups =
+                utils::StackMergeIter::new(groups, &(), concat_nonspace_groups_rule);
+            Box::new(utils::StackMergeIter::new(
+                groups,
+                &(),
+                remove_implicit_whitespace_rule,
+            ))
+        }
+        Granularity::Search => {
+            let groups = utils::StackMergeIter::new(groups, jieba, cut_hanzi_search_rule);
+            Box::new(utils::StackMergeIter::new(
+                groups,
+                &(),
+                remove_implicit_whitespace_rule,
+            ))
+        }
+    };
+    Tokens { inner }
+}
+
+/// A char that ends a sentence: ASCII `.`/`!`/`?` and the CJK/fullwidth
+/// ideographic full stop, fullwidth exclamation mark, and fullwidth
+/// question mark.
+fn is_sentence_terminator(c: char) -> bool {
+    matches!(
+        c,
+        '.' | '!' | '?'
+        | '\u{3002}' // 。
+        | '\u{ff0e}' // ．
+        | '\u{ff01}' // ！
+        | '\u{ff1f}' // ？
+    )
+}
+
+/// A closing quote or bracket that may trail a sentence terminator while
+/// the sentence is still "closing", e.g. the `"` in `she said "no."` or the
+/// `』` in `世界。』`.
+fn is_closing_quote_or_bracket(c: char) -> bool {
+    matches!(
+        c,
+        ')' | ']' | '}' | '"' | '\''
+        | '\u{ff09}' | '\u{ff3d}' | '\u{ff5d}'
+        | '\u{2019}' | '\u{201d}'
+        | '\u{3009}' | '\u{300b}' | '\u{300d}' | '\u{300f}' | '\u{3011}'
+        | '\u{3015}' | '\u{3017}' | '\u{3019}' | '\u{301b}' | '\u{301e}'
+    )
+}
+
+/// Parse `line` into [`TokenType::Sentence`] and [`TokenType::Space`]
+/// tokens, analogous to [`parse_chars_into_words`] but at sentence rather
+/// than word granularity, so Vim `)`/`(` can jump sentence-at-a-time using
+/// the same CJK-aware tokenizer as `w`/`b`/`e`.
+///
+/// A sentence ends after a run of [`is_sentence_terminator`] chars,
+/// optionally followed by [`is_closing_quote_or_bracket`] chars, except
+/// that a lone `.` flanked by [`CharType::Word`] chars on both sides (a
+/// decimal like `3.14`, or an abbreviation like `U.S.A.`) is treated as an
+/// ordinary word char instead of a terminator. Unlike a `word`/`WORD`
+/// token, a `Sentence` token may itself contain whitespace; only the
+/// leading/trailing whitespace *between* sentences becomes its own `Space`
+/// token.
+pub(crate) fn parse_str_into_sentences<C: JiebaPlaceholder>(
+    line: &str,
+    jieba: &C,
+) -> Vec<Token> {
+    let chars = parse_str_into_chars(line, jieba.classifier(), jieba.char_class_config());
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let space_start = i;
+        while i < chars.len() && matches!(chars[i].ty, CharType::Space) {
+            i += 1;
+        }
+        if i > space_start {
+            tokens.push(Token {
+                col: Col {
+                    start_byte_index: chars[space_start].col.start_byte_index,
+                    incl_end_byte_index: chars[i - 1].col.incl_end_byte_index,
+                    excl_end_byte_index: chars[i - 1].col.excl_end_byte_index,
+                },
+                ty: TokenType::Space,
+            });
+        }
+        if i >= chars.len() {
+            break;
+        }
+
+        let sentence_start = i;
+        let mut sentence_end = i;
+        while i < chars.len() {
+            let c = chars[i].ch;
+            if is_sentence_terminator(c) {
+                let excepted_period = c == '.'
+                    && i > 0
+                    && i + 1 < chars.len()
+                    && matches!(chars[i - 1].ty, CharType::Word(_))
+                    && matches!(chars[i + 1].ty, CharType::Word(_));
+                if !excepted_period {
+                    i += 1;
+                    while i < chars.len()
+                        && is_sentence_terminator(chars[i].ch)
+                    {
+                        i += 1;
+                    }
+                    while i < chars.len()
+                        && is_closing_quote_or_bracket(chars[i].ch)
+                    {
+                        i += 1;
+                    }
+                    sentence_end = i;
+                    break;
+                }
+            }
+            i += 1;
+            sentence_end = i;
+        }
+
+        let last = &chars[sentence_end - 1];
+        tokens.push(Token {
+            col: Col {
+                start_byte_index: chars[sentence_start].col.start_byte_index,
+                incl_end_byte_index: last.col.incl_end_byte_index,
+                excl_end_byte_index: last.col.excl_end_byte_index,
+            },
+            ty: TokenType::Sentence,
+        });
+        i = sentence_end;
+    }
+    tokens
+}
+
+/// A small `nom`-based sub-tokenizer for `camelCase`/`PascalCase`/
+/// `snake_case` identifiers, for plugging into
+/// [`JiebaPlaceholder::cut_other`] so source-code buffers get `w`/`e`/`b`
+/// stops at case/underscore boundaries instead of treating the whole
+/// identifier as one word.
+pub mod subword {
+    use nom::bytes::complete::take_while1;
+    use nom::multi::many0;
+    use nom::IResult;
+
+    /// One maximal run of a single char class within an identifier.
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    enum RunType {
+        Upper,
+        Lower,
+        Digit,
+        /// `_`, kept as its own run so it surfaces as its own piece, the
+        /// same way jieba surfaces an embedded `_` as its own token when
+        /// cutting a Hanzi-mixed run (see [`super::cut_hanzi_rule`]).
+        Underscore,
+    }
+
+    fn run_type(c: char) -> RunType {
+        if c == '_' {
+            RunType::Underscore
+        } else if c.is_ascii_digit() {
+            RunType::Digit
+        } else if c.is_uppercase() {
+            RunType::Upper
+        } else {
+            RunType::Lower
+        }
+    }
+
+    fn one_run(input: &str) -> IResult<&str, (RunType, &str)> {
+        let Some(first) = input.chars().next() else {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Eof,
+            )));
+        };
+        let ty = run_type(first);
+        let (rest, matched) = take_while1(move |c| run_type(c) == ty)(input)?;
+        Ok((rest, (ty, matched)))
+    }
+
+    fn runs(input: &str) -> IResult<&str, Vec<(RunType, &str)>> {
+        many0(one_run)(input)
+    }
+
+    /// Split `sentence` -- a maximal run of [`super::WordCharType::Other`]
+    /// chars, i.e. letters/digits/`_` with no 汉字 or dictionary-backed
+    /// script mixed in -- at `camelCase`/`PascalCase` transitions and `_`
+    /// boundaries. A trailing run of capitals immediately followed by a
+    /// lowercase run donates its last capital to that lowercase run (so
+    /// `"HTTPServer"` splits as `"HTTP"`, `"Server"`, not `"HTTPS"`,
+    /// `"erver"`), matching how acronym-prefixed identifiers are normally
+    /// read. Digit runs are never merged into an adjacent letter run, so
+    /// e.g. `"v2Format"` splits as `"v"`, `"2"`, `"Format"`.
+    ///
+    /// Does not attempt to keep URLs or other non-word-delimited constructs
+    /// together -- those already span [`super::CharType::NonWord`] chars
+    /// (`:`, `/`, `.`, ...) that this function never sees, since it only
+    /// runs on a single [`super::WordCharGroupType::Other`] group.
+    pub fn split(sentence: &str) -> Vec<&str> {
+        let Ok((_, runs)) = runs(sentence) else {
+            return vec![sentence];
+        };
+
+        // Byte ranges are tracked alongside `runs` rather than recovered by
+        // pointer arithmetic -- the runs are contiguous and cover all of
+        // `sentence`, so a running offset is simplest.
+        let mut ranges = Vec::with_capacity(runs.len());
+        let mut offset = 0;
+        for (ty, text) in &runs {
+            ranges.push((*ty, offset, offset + text.len()));
+            offset += text.len();
+        }
+
+        let mut pieces: Vec<&str> = Vec::new();
+        let mut i = 0;
+        while i < ranges.len() {
+            let (ty, start, end) = ranges[i];
+            if ty == RunType::Upper
+                && sentence[start..end].chars().count() > 1
+                && i + 1 < ranges.len()
+                && ranges[i + 1].0 == RunType::Lower
+            {
+                // Donate the last capital of this acronym run to the
+                // lowercase run that follows it.
+                let (_, _, next_end) = ranges[i + 1];
+                let last_char_start = start
+                    + sentence[start..end].char_indices().last().unwrap().0;
+                pieces.push(&sentence[start..last_char_start]);
+                pieces.push(&sentence[last_char_start..next_end]);
+                i += 2;
+            } else {
+                pieces.push(&sentence[start..end]);
+                i += 1;
+            }
+        }
+        pieces
+    }
+}
+
+/// A pluggable word-segmentation backend, decoupled from
+/// [`JiebaPlaceholder`]'s wider interface (idf, classifier, ...) so a
+/// caller that only wants to swap out *how a run of chars gets subdivided*
+/// -- e.g. to segment Japanese/Thai/Lao/Khmer without bundling jieba's
+/// Chinese dictionary -- doesn't have to implement the rest of it. Plug one
+/// in by calling [`segment_into_pieces`] from inside
+/// [`JiebaPlaceholder::cut_hmm`]/[`JiebaPlaceholder::cut_dictionary`].
+pub trait Segmenter {
+    /// The char length of each sub-token `s` should be cut into, in order.
+    /// The lengths must sum to `s.chars().count()`.
+    fn cut(&self, s: &str) -> Vec<usize>;
+}
+
+/// Slice `s` into the pieces a [`Segmenter`]'s char lengths denote, doing
+/// the char-to-byte-offset bookkeeping once so implementors of
+/// [`JiebaPlaceholder::cut_hmm`]/[`JiebaPlaceholder::cut_dictionary`] that
+/// delegate to a `Segmenter` don't each have to redo it.
+pub fn segment_into_pieces<'a>(segmenter: &dyn Segmenter, s: &'a str) -> Vec<&'a str> {
+    let mut pieces = Vec::new();
+    let mut char_ends = s.char_indices().map(|(i, c)| i + c.len_utf8());
+    let mut start = 0;
+    for len in segmenter.cut(s) {
+        let mut end = start;
+        for _ in 0..len {
+            end = char_ends.next().unwrap_or(s.len());
+        }
+        pieces.push(&s[start..end]);
+        start = end;
+    }
+    pieces
+}
+
+/// A dependency-free [`Segmenter`] that splits at every
+/// [`crate::char_class::CharClass`] transition (Word/Punct/Blank), the same
+/// coarse boundary [`categorize_char`]'s own fallback rule uses for scripts
+/// it has no dedicated table for. Not a real Unicode word/line segmenter --
+/// this tree has no package manifest to add a dependency like
+/// `icu_segmenter` -- but gives Japanese/Thai/Lao/Khmer and other
+/// non-jieba-covered scripts sensible `w`/`b` stops without bundling a
+/// Chinese dictionary. Swap in a real `icu_segmenter`-backed [`Segmenter`]
+/// at the call site once that dependency is available.
+pub struct UnicodeWordSegmenter {
+    classifier: CharClassifier,
+}
+
+impl Default for UnicodeWordSegmenter {
+    fn default() -> Self {
+        Self {
+            classifier: CharClassifier::default(),
+        }
+    }
+}
+
+impl Segmenter for UnicodeWordSegmenter {
+    fn cut(&self, s: &str) -> Vec<usize> {
+        let mut lens = Vec::new();
+        let mut prev_class = None;
+        let mut count = 0usize;
+        for c in s.chars() {
+            let class = self.classifier.classify(c);
+            if prev_class == Some(class) {
+                count += 1;
+            } else {
+                if count > 0 {
+                    lens.push(count);
+                }
+                count = 1;
+            }
+            prev_class = Some(class);
+        }
+        if count > 0 {
+            lens.push(count);
+        }
+        lens
+    }
+}
+
+/// Reclassifies a jieba-segmented [`TokenType::Word`] token into
+/// `Word`/`Punctuation`/`Space` runs via user-supplied, ordered
+/// `(pattern, TokenType)` rules, so callers can make `w`/`e`/`b` stop at
+/// keyword/punctuation transitions the way Vim's real `iskeyword`-driven
+/// word motions do, rather than only at jieba's word/space boundaries.
+/// Patterns are compiled with [`fancy_regex`] (not the plain `regex` crate)
+/// so a rule can use lookahead/lookbehind if it needs to.
+pub mod classify {
+    use super::{Col, Token, TokenType};
+
+    /// One `(pattern, class)` rule: a char matching `pattern` is classified
+    /// as `class`. Rules are tried in order; the first match wins.
+    struct ClassifyRule {
+        pattern: fancy_regex::Regex,
+        ty: TokenType,
+    }
+
+    /// An ordered set of char-classification rules plus a fallback class for
+    /// characters no rule matches. [`Classifier::default`] keeps every
+    /// character classified as [`TokenType::Word`], i.e. the original
+    /// two-class (`Word`/`Space`) behavior, since [`Classifier::split`] is
+    /// only ever run on a token jieba already classified as `Word`.
+    pub struct Classifier {
+        rules: Vec<ClassifyRule>,
+        default: TokenType,
+    }
+
+    impl Default for Classifier {
+        fn default() -> Self {
+            Self {
+                rules: Vec::new(),
+                default: TokenType::Word,
+            }
+        }
+    }
+
+    impl Classifier {
+        /// Build a classifier from ordered `(pattern, class)` rules plus the
+        /// class assigned to a character none of them match.
+        pub fn new(
+            rules: Vec<(fancy_regex::Regex, TokenType)>,
+            default: TokenType,
+        ) -> Self {
+            Self {
+                rules: rules
+                    .into_iter()
+                    .map(|(pattern, ty)| ClassifyRule { pattern, ty })
+                    .collect(),
+                default,
+            }
+        }
+
+        fn classify(&self, c: char) -> TokenType {
+            let mut buf = [0u8; 4];
+            let s = c.encode_utf8(&mut buf);
+            self.rules
+                .iter()
+                .find(|rule| rule.pattern.is_match(s).unwrap_or(false))
+                .map(|rule| rule.ty)
+                .unwrap_or(self.default)
+        }
+
+        /// Re-scan `token`'s characters in `line` left to right, splitting it
+        /// at every point this classifier's class changes, so each returned
+        /// token carries a single, homogeneous class. `token` must be a
+        /// [`TokenType::Word`] token; jieba already separates `Space` runs
+        /// out before this ever runs.
+        pub(crate) fn split(&self, token: Token, line: &str) -> Vec<Token> {
+            let start = token.col.start_byte_index;
+            let end = token.col.excl_end_byte_index;
+
+            let mut out = Vec::new();
+            let mut group: Option<(usize, TokenType)> = None;
+            let mut last_char_start = start;
+            let mut last_char_len = 0;
+            for (rel_index, c) in line[start..end].char_indices() {
+                let char_start = start + rel_index;
+                let ty = self.classify(c);
+                match group {
+                    Some((_, group_ty)) if group_ty == ty => {}
+                    Some((group_start, group_ty)) => {
+                        out.push(Token {
+                            col: Col {
+                                start_byte_index: group_start,
+                                incl_end_byte_index: last_char_start,
+                                excl_end_byte_index: last_char_start
+                                    + last_char_len,
+                            },
+                            ty: group_ty,
+                        });
+                        group = Some((char_start, ty));
+                    }
+                    None => group = Some((char_start, ty)),
+                }
+                last_char_start = char_start;
+                last_char_len = c.len_utf8();
+            }
+            if let Some((group_start, group_ty)) = group {
+                out.push(Token {
+                    col: Col {
+                        start_byte_index: group_start,
+                        incl_end_byte_index: last_char_start,
+                        excl_end_byte_index: last_char_start + last_char_len,
+                    },
+                    ty: group_ty,
+                });
+            }
+            out
+        }
+    }
+
+    /// Reclassify every [`TokenType::Word`] token in `tokens` via
+    /// `classifier`, leaving `Space` (and any other non-`Word`) tokens
+    /// untouched.
+    pub(crate) fn reclassify(
+        tokens: Vec<Token>,
+        line: &str,
+        classifier: &Classifier,
+    ) -> Vec<Token> {
+        tokens
+            .into_iter()
+            .flat_map(|token| {
+                if token.ty == TokenType::Word {
+                    classifier.split(token, line)
+                } else {
+                    vec![token]
+                }
+            })
+            .collect()
+    }
+}
+
+/// A Viterbi-decoded HMM fallback for maximal runs of 汉字
+/// [`JiebaPlaceholder::cut_hmm`] couldn't match against a dictionary, the
+/// way jieba-rs's own `hmm` feature segments out-of-vocabulary runs instead
+/// of leaving every character its own one-char token.
+pub mod hmm {
+    use std::collections::HashMap;
+
+    use super::Segmenter;
+
+    /// The four hidden states of the character-position HMM: B(egin),
+    /// M(iddle), E(nd) of a multi-char word, or S(ingle)-char word on its
+    /// own. Kept in this order since [`HmmModel::new`]'s `start_p`/
+    /// `trans_p`/`emit_p` arrays are indexed positionally by it.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum HmmState {
+        B,
+        M,
+        E,
+        S,
+    }
+
+    use HmmState::*;
+
+    const STATES: [HmmState; 4] = [B, M, E, S];
+
+    fn index(state: HmmState) -> usize {
+        match state {
+            B => 0,
+            M => 1,
+            E => 2,
+            S => 3,
+        }
+    }
+
+    /// Log-probability assigned to a character with no entry in a state's
+    /// emission table, mirroring jieba's own `MIN_FLOAT` -- small enough
+    /// that the Viterbi recurrence below only ever picks it when every
+    /// alternative is equally unseen.
+    const MIN_EMIT_P: f64 = -3.14e100;
+
+    /// A trained four-state (B/M/E/S) character-position HMM, decoded with
+    /// the Viterbi algorithm into word boundaries: `dp[i][state] =
+    /// emit(state, char_i) + max_prev(dp[i-1][prev] + trans[prev][state])`,
+    /// backtracked from the best of `E`/`S` in the final column (a word can
+    /// only end on one of those two states). This crate has no bundled
+    /// dictionary or training corpus, so `start_p`/`trans_p`/`emit_p` are
+    /// supplied by the caller -- e.g. loaded from jieba's own
+    /// `prob_start.py`/`prob_trans.py`/`prob_emit.py` tables -- rather than
+    /// hardcoded here.
+    pub struct HmmModel {
+        start_p: [f64; 4],
+        trans_p: [[f64; 4]; 4],
+        emit_p: [HashMap<char, f64>; 4],
+    }
+
+    impl HmmModel {
+        /// Build a model from `start_p[state]`, `trans_p[prev][cur]`, and
+        /// `emit_p[state]` (a sparse per-character log-probability table;
+        /// missing characters fall back to [`MIN_EMIT_P`]), each indexed by
+        /// [`HmmState`] in `B, M, E, S` order.
+        pub fn new(
+            start_p: [f64; 4],
+            trans_p: [[f64; 4]; 4],
+            emit_p: [HashMap<char, f64>; 4],
+        ) -> Self {
+            Self {
+                start_p,
+                trans_p,
+                emit_p,
+            }
+        }
+
+        fn emit(&self, state: HmmState, c: char) -> f64 {
+            *self.emit_p[index(state)].get(&c).unwrap_or(&MIN_EMIT_P)
+        }
+
+        /// The best `HmmState` path for `chars`, one state per character.
+        fn viterbi(&self, chars: &[char]) -> Vec<HmmState> {
+            let n = chars.len();
+            let mut dp = vec![[f64::NEG_INFINITY; 4]; n];
+            let mut back = vec![[0usize; 4]; n];
+            for &state in &STATES {
+                dp[0][index(state)] = self.start_p[index(state)] + self.emit(state, chars[0]);
+            }
+            for i in 1..n {
+                for &state in &STATES {
+                    let (best_prev, best_score) = STATES
+                        .iter()
+                        .map(|&prev| {
+                            (prev, dp[i - 1][index(prev)] + self.trans_p[index(prev)][index(state)])
+                        })
+                        .max_by(|a, b| a.1.total_cmp(&b.1))
+                        .unwrap();
+                    dp[i][index(state)] = best_score + self.emit(state, chars[i]);
+                    back[i][index(state)] = index(best_prev);
+                }
+            }
+
+            let last = n - 1;
+            let mut state = [E, S]
+                .into_iter()
+                .max_by(|&a, &b| dp[last][index(a)].total_cmp(&dp[last][index(b)]))
+                .unwrap();
+            let mut path = vec![S; n];
+            path[last] = state;
+            for i in (1..n).rev() {
+                let prev_index = back[i][index(state)];
+                state = STATES[prev_index];
+                path[i - 1] = state;
+            }
+            path
+        }
+    }
+
+    impl Segmenter for HmmModel {
+        /// Decode `s` into word lengths, cutting after every `E` and every
+        /// `S` -- the only states a word can end on.
+        fn cut(&self, s: &str) -> Vec<usize> {
+            let chars: Vec<char> = s.chars().collect();
+            if chars.is_empty() {
+                return vec![];
+            }
+            let path = self.viterbi(&chars);
+            let mut lens = Vec::new();
+            let mut run_start = 0;
+            for (i, &state) in path.iter().enumerate() {
+                if matches!(state, E | S) {
+                    lens.push(i + 1 - run_start);
+                    run_start = i + 1;
+                }
+            }
+            lens
+        }
+    }
+}
+
+/// A token or an empty line.
+pub(crate) trait TokenLike {
+    /// The byte position of the first character in the token.
+    fn first_char(&self) -> usize;
+    /// The byte position of the last character in the token.
+    fn last_char(&self) -> usize;
+    /// The byte position of the end of the last character in the token.
+    fn last_char1(&self) -> usize;
+}
+
+impl TokenLike for Token {
+    fn first_char(&self) -> usize {
+        self.col.start_byte_index
+    }
+
+    fn last_char(&self) -> usize {
+        self.col.incl_end_byte_index
+    }
+
+    fn last_char1(&self) -> usize {
+        self.col.excl_end_byte_index
+    }
+}
+
+// `None` is used to denote the empty line.
+impl TokenLike for Option<Token> {
+    fn first_char(&self) -> usize {
+        self.map(|t| t.first_char()).unwrap_or(0)
+    }
+
+    fn last_char(&self) -> usize {
+        self.map(|t| t.last_char()).unwrap_or(0)
+    }
+
+    fn last_char1(&self) -> usize {
+        self.map(|t| t.last_char1()).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_macros {
+    #[macro_export]
+    macro_rules! token {
+        ($i:literal, $j:literal, $k:literal, $t:ident) => {
+            crate::token::Token {
+                col: crate::token::Col {
+                    start_byte_index: $i,
+                    incl_end_byte_index: $j,
+                    excl_end_byte_index: $k,
+                },
+                ty: crate::token::TokenType::$t,
+            }
+        };
+    }
+
+    pub use token;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    use jieba_rs::Jieba;
+    use once_cell::sync::OnceCell;
+    use proptest::prelude::*;
+
+    impl JiebaPlaceholder for Jieba {
+        fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+            self.cut(sentence, true)
+        }
+    }
+
+    #[test]
+    fn test_categorize_char_sanity_check() {
+        let classifier = CharClassifier::default();
+        let config = CharClassConfig::default();
+        assert!(matches!(
+            categorize_char('-', &classifier, &config),
+            CharType::NonWord(NonWordCharType::Other)
+        ));
+        assert!(matches!(
+            categorize_char(',', &classifier, &config),
             CharType::NonWord(NonWordCharType::Other)
         ));
         assert!(matches!(
-            categorize_char('我'),
+            categorize_char('我', &classifier, &config),
             CharType::Word(WordCharType::Hanzi)
         ));
         assert!(matches!(
-            categorize_char('，'),
+            categorize_char('，', &classifier, &config),
             CharType::NonWord(NonWordCharType::RightPunc)
         ));
         assert!(matches!(
-            categorize_char('。'),
+            categorize_char('。', &classifier, &config),
             CharType::NonWord(NonWordCharType::RightPunc)
         ));
         assert!(matches!(
-            categorize_char('（'),
+            categorize_char('（', &classifier, &config),
             CharType::NonWord(NonWordCharType::LeftPunc)
         ));
         assert!(matches!(
-            categorize_char('—'),
-            CharType::NonWord(NonWordCharType::IsolatedPunc)
+            categorize_char('—', &classifier, &config),
+            CharType::NonWord(NonWordCharType::IsolatedPunc)
+        ));
+        assert!(matches!(
+            categorize_char('\u{3000}', &classifier, &config),
+            CharType::Space
+        ));
+        assert!(matches!(
+            categorize_char('\u{0e01}', &classifier, &config), // Thai "ko kai"
+            CharType::Word(WordCharType::Dictionary(Script::Thai))
+        ));
+        assert!(matches!(
+            categorize_char('\u{0e81}', &classifier, &config), // Lao "ko"
+            CharType::Word(WordCharType::Dictionary(Script::Lao))
+        ));
+        assert!(matches!(
+            categorize_char('\u{1780}', &classifier, &config), // Khmer "ka"
+            CharType::Word(WordCharType::Dictionary(Script::Khmer))
+        ));
+        assert!(matches!(
+            categorize_char('\u{3042}', &classifier, &config), // Hiragana "a"
+            CharType::Word(WordCharType::Hiragana)
+        ));
+        assert!(matches!(
+            categorize_char('\u{30a2}', &classifier, &config), // Katakana "a"
+            CharType::Word(WordCharType::Katakana)
+        ));
+        assert!(matches!(
+            categorize_char('\u{ac00}', &classifier, &config), // Hangul "ga"
+            CharType::Word(WordCharType::Hangul)
+        ));
+    }
+
+    #[test]
+    fn test_categorize_char_unicode_whitespace_gated_by_config() {
+        let classifier = CharClassifier::default();
+        // The Mongolian vowel separator isn't Unicode White_Space, so it
+        // categorizes as punctuation regardless of the flag.
+        let off = CharClassConfig::default();
+        assert!(matches!(
+            categorize_char('\u{180e}', &classifier, &off),
+            CharType::NonWord(NonWordCharType::Other)
+        ));
+        let on = CharClassConfig::default().with_unicode_whitespace(true);
+        assert!(matches!(
+            categorize_char('\u{180e}', &classifier, &on),
+            CharType::Space
+        ));
+        assert!(matches!(
+            categorize_char('\u{feff}', &classifier, &on), // ZWNBSP
+            CharType::Space
+        ));
+    }
+
+    #[test]
+    fn test_general_category_mode_off_keeps_table_driven_classification() {
+        let classifier = CharClassifier::default();
+        let config = CharClassConfig::default();
+        // '·' is in the curated punctuation table (IsolatedPunc) regardless
+        // of `general_category_mode`, since that table is applied ahead of
+        // both classifiers.
+        assert!(matches!(
+            categorize_char('·', &classifier, &config),
+            CharType::NonWord(NonWordCharType::IsolatedPunc)
+        ));
+    }
+
+    #[test]
+    fn test_general_category_mode_classifies_letters_and_digits_as_word() {
+        let classifier = CharClassifier::default();
+        let config =
+            CharClassConfig::default().with_general_category_classification(true);
+        // Cyrillic and Greek have no dedicated range in the table-driven
+        // match, but General_Category still calls them `Word`.
+        assert!(matches!(
+            categorize_char('я', &classifier, &config),
+            CharType::Word(WordCharType::Other)
+        ));
+        assert!(matches!(
+            categorize_char('Ω', &classifier, &config),
+            CharType::Word(WordCharType::Other)
+        ));
+        assert!(matches!(
+            categorize_char('5', &classifier, &config),
+            CharType::Word(WordCharType::Other)
+        ));
+    }
+
+    #[test]
+    fn test_general_category_mode_still_tags_cjk_ideographs_as_hanzi() {
+        let classifier = CharClassifier::default();
+        let config =
+            CharClassConfig::default().with_general_category_classification(true);
+        assert!(matches!(
+            categorize_char('漢', &classifier, &config),
+            CharType::Word(WordCharType::Hanzi)
+        ));
+    }
+
+    #[test]
+    fn test_general_category_mode_classifies_whitespace_as_space() {
+        let classifier = CharClassifier::default();
+        let config =
+            CharClassConfig::default().with_general_category_classification(true);
+        assert!(matches!(
+            categorize_char(' ', &classifier, &config),
+            CharType::Space
+        ));
+        assert!(matches!(
+            categorize_char('\u{3000}', &classifier, &config), // CJK ideographic space
+            CharType::Space
+        ));
+        // Unaffected by `general_category_mode`: the Mongolian vowel
+        // separator isn't Unicode `White_Space`, so it's still gated behind
+        // `CharClassConfig::with_unicode_whitespace`.
+        assert!(matches!(
+            categorize_char('\u{180e}', &classifier, &config),
+            CharType::NonWord(NonWordCharType::Other)
+        ));
+    }
+
+    #[test]
+    fn test_category_hook_overrides_hardcoded_tables() {
+        // '·' is hardcoded as `IsolatedPunc`; the hook should win anyway.
+        let classifier = CharClassifier::default().with_category_hook(|c| {
+            (c == '·').then_some(CharCategory::WordOther)
+        });
+        let config = CharClassConfig::default();
+        assert!(matches!(
+            categorize_char('·', &classifier, &config),
+            CharType::Word(WordCharType::Other)
+        ));
+        // Chars the hook doesn't claim still fall through to the tables.
+        assert!(matches!(
+            categorize_char('我', &classifier, &config),
+            CharType::Word(WordCharType::Hanzi)
+        ));
+    }
+
+    /// A [`JiebaPlaceholder`] that segments every [`Script::Thai`] run into
+    /// two-char pieces, to exercise [`cut_dictionary_rule`] without pulling
+    /// in a real Thai word-breaker.
+    struct PairCutter;
+
+    impl JiebaPlaceholder for PairCutter {
+        fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+            vec![sentence]
+        }
+
+        fn cut_dictionary<'a>(
+            &self,
+            script: Script,
+            sentence: &'a str,
+        ) -> Vec<&'a str> {
+            assert_eq!(script, Script::Thai);
+            let mut pieces = Vec::new();
+            let mut rest = sentence;
+            while !rest.is_empty() {
+                let split = rest
+                    .char_indices()
+                    .nth(2)
+                    .map(|(i, _)| i)
+                    .unwrap_or(rest.len());
+                let (piece, remainder) = rest.split_at(split);
+                pieces.push(piece);
+                rest = remainder;
+            }
+            pieces
+        }
+    }
+
+    #[test]
+    fn test_parse_str_dictionary_backend() {
+        // "กขคง" (4 Thai chars, no whitespace) cut into 2-char pieces by
+        // `PairCutter`, each becoming its own `word` token.
+        let tokens = parse_str("กขคง", &PairCutter, Granularity::Word);
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 3, 6, Word),
+                test_macros::token!(6, 9, 12, Word),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subword_split_camel_case() {
+        assert_eq!(subword::split("fooBarBaz"), vec!["foo", "Bar", "Baz"]);
+    }
+
+    #[test]
+    fn test_subword_split_acronym_donates_last_capital() {
+        assert_eq!(subword::split("HTTPServer"), vec!["HTTP", "Server"]);
+    }
+
+    #[test]
+    fn test_subword_split_snake_case() {
+        assert_eq!(subword::split("foo_bar_baz"), vec!["foo", "_", "bar", "_", "baz"]);
+    }
+
+    #[test]
+    fn test_subword_split_digits_stand_alone() {
+        assert_eq!(subword::split("v2Format"), vec!["v", "2", "Format"]);
+    }
+
+    #[test]
+    fn test_subword_split_single_run_is_unsplit() {
+        assert_eq!(subword::split("hello"), vec!["hello"]);
+        assert_eq!(subword::split("HELLO"), vec!["HELLO"]);
+    }
+
+    #[test]
+    fn test_unicode_word_segmenter_splits_at_class_transitions() {
+        let segmenter = UnicodeWordSegmenter::default();
+        assert_eq!(segmenter.cut("foo bar"), vec![3, 1, 3]);
+        assert_eq!(segmenter.cut("foo!"), vec![3, 1]);
+        assert_eq!(segmenter.cut("hello"), vec![5]);
+        assert_eq!(segmenter.cut(""), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_segment_into_pieces_slices_by_char_length() {
+        let segmenter = UnicodeWordSegmenter::default();
+        assert_eq!(
+            segment_into_pieces(&segmenter, "foo bar"),
+            vec!["foo", " ", "bar"]
+        );
+        // Multi-byte chars are sliced on their own char boundaries, not
+        // jumbled with the ASCII byte-length math. `CharClass` doesn't
+        // distinguish 汉字 from other alphanumeric scripts, so the
+        // transition here is the `!`, not the 中文/ab boundary.
+        assert_eq!(
+            segment_into_pieces(&segmenter, "中文!ab"),
+            vec!["中文", "!", "ab"]
+        );
+    }
+
+    fn punctuation_classifier() -> classify::Classifier {
+        classify::Classifier::new(
+            vec![(
+                fancy_regex::Regex::new(r"[^\w\s]").unwrap(),
+                TokenType::Punctuation,
+            )],
+            TokenType::Word,
+        )
+    }
+
+    #[test]
+    fn test_classify_split_separates_punctuation_from_word() {
+        // "foo()" as one jieba `Word` token splits into "foo" (Word) and
+        // the adjacent "()" run, merged into a single `Punctuation` token
+        // since both its characters classify the same.
+        let token = test_macros::token!(0, 4, 5, Word);
+        let pieces = punctuation_classifier().split(token, "foo()");
+        assert_eq!(
+            pieces,
+            vec![
+                test_macros::token!(0, 2, 3, Word),
+                test_macros::token!(3, 4, 5, Punctuation),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classify_split_with_default_classifier_is_unsplit() {
+        // A classifier with no rules reproduces the original two-class
+        // (`Word`/`Space`) behavior: a `Word` token is returned unchanged.
+        let token = test_macros::token!(0, 4, 5, Word);
+        let pieces = classify::Classifier::default().split(token, "foo()");
+        assert_eq!(pieces, vec![token]);
+    }
+
+    #[test]
+    fn test_classify_reclassify_leaves_space_tokens_untouched() {
+        let space = test_macros::token!(3, 3, 4, Space);
+        let tokens = vec![space];
+        assert_eq!(
+            classify::reclassify(tokens, "foo bar", &punctuation_classifier()),
+            vec![space]
+        );
+    }
+
+    /// A [`JiebaPlaceholder`] that treats the whole sentence as one
+    /// `cut_hmm` piece and routes [`WordCharType::Other`] runs through
+    /// [`subword::split`], to exercise [`cut_other_rule`] without pulling
+    /// in a real `JiebaWrapper`.
+    struct CamelCaseCutter;
+
+    impl JiebaPlaceholder for CamelCaseCutter {
+        fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+            vec![sentence]
+        }
+
+        fn cut_other<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+            subword::split(sentence)
+        }
+    }
+
+    #[test]
+    fn test_parse_str_other_backend() {
+        // "fooBarBaz" (9 ASCII chars, no whitespace) cut at camelCase
+        // boundaries by `CamelCaseCutter`, each becoming its own `word`
+        // token.
+        let tokens = parse_str("fooBarBaz", &CamelCaseCutter, Granularity::Word);
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 2, 3, Word),
+                test_macros::token!(3, 5, 6, Word),
+                test_macros::token!(6, 8, 9, Word),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_str_other_backend_default_is_uncut() {
+        // Without overriding `cut_other`, `PairCutter` leaves `fooBarBaz`
+        // as a single `word` token, same as before this backend existed.
+        let tokens = parse_str("fooBarBaz", &PairCutter, Granularity::Word);
+        assert_eq!(tokens, vec![test_macros::token!(0, 8, 9, Word)]);
+    }
+
+    /// A [`JiebaPlaceholder`] that records the exact text [`cut_other_rule`]
+    /// hands it, to confirm a [`CharGroup`] never drops a grapheme
+    /// cluster's combining marks when rendering itself back to a `String`
+    /// for the cutter.
+    #[derive(Default)]
+    struct RecordingOtherCutter {
+        seen: RefCell<Vec<String>>,
+    }
+
+    impl JiebaPlaceholder for RecordingOtherCutter {
+        fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+            vec![sentence]
+        }
+
+        fn cut_other<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+            self.seen.borrow_mut().push(sentence.to_string());
+            vec![sentence]
+        }
+    }
+
+    #[test]
+    fn test_cut_other_rule_sees_the_full_cluster_not_just_its_base_scalar() {
+        // "é" decomposed as "e" + U+0301, then "x": `cut_other_rule` must
+        // hand the cutter both bytes of the cluster, not just "e".
+        let line = "e\u{0301}x";
+        let cutter = RecordingOtherCutter::default();
+        parse_str(line, &cutter, Granularity::Word);
+        assert_eq!(*cutter.seen.borrow(), vec![line.to_string()]);
+    }
+
+    /// A [`JiebaPlaceholder`] that treats the whole sentence as one
+    /// `cut_hmm` piece, then re-splits every 2 chars via `cut_for_search`
+    /// -- including an overlapping cut point jieba's real search cut would
+    /// also produce -- to exercise [`cut_hanzi_search_rule`]'s
+    /// deduplication without pulling in real jieba.
+    struct SearchCutter;
+
+    impl JiebaPlaceholder for SearchCutter {
+        fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+            vec![sentence]
+        }
+
+        fn cut_for_search(&self, sentence: &str) -> Vec<usize> {
+            let n = sentence.chars().count();
+            let mut cuts: Vec<usize> = (2..n).step_by(2).collect();
+            // Duplicate a cut point, the way overlapping fragments from a
+            // real search cut could imply the same boundary twice.
+            if let Some(&first) = cuts.first() {
+                cuts.push(first);
+            }
+            cuts
+        }
+    }
+
+    #[test]
+    fn test_parse_str_search_granularity_splits_hanzi_compounds() {
+        // "中华人民共和国" (7 Hanzi chars) re-split every 2 chars by
+        // `SearchCutter`, with no implicit whitespace between the pieces.
+        let tokens = parse_str("中华人民共和国", &SearchCutter, Granularity::Search);
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 3, 6, Word),
+                test_macros::token!(6, 9, 12, Word),
+                test_macros::token!(12, 15, 18, Word),
+                test_macros::token!(18, 18, 21, Word),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_str_search_granularity_default_is_uncut() {
+        // The trait default `cut_for_search` returns no cut points, so a
+        // `JiebaPlaceholder` that doesn't override it behaves exactly like
+        // `Granularity::Word`.
+        let tokens = parse_str("中华人民共和国", &PairCutter, Granularity::Search);
+        assert_eq!(tokens, parse_str("中华人民共和国", &PairCutter, Granularity::Word));
+    }
+
+    /// A [`JiebaPlaceholder`] whose `cut_for_search` always offers a cut
+    /// point after the first char, to exercise [`cut_hanzi_search_rule`]'s
+    /// 3-char length gate independent of how many cut points it's given.
+    struct AlwaysCutAfterFirstChar;
+
+    impl JiebaPlaceholder for AlwaysCutAfterFirstChar {
+        fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+            vec![sentence]
+        }
+
+        fn cut_for_search(&self, _sentence: &str) -> Vec<usize> {
+            vec![1]
+        }
+    }
+
+    #[test]
+    fn test_parse_str_search_granularity_leaves_short_hanzi_words_uncut() {
+        // `cut_hanzi_search_rule` only fires on a group of 3 or more 汉字,
+        // so a 2-character word is never re-split even though
+        // `cut_for_search` offers a cut point inside it.
+        let tokens = parse_str("中华", &AlwaysCutAfterFirstChar, Granularity::Search);
+        assert_eq!(tokens, vec![test_macros::token!(0, 3, 6, Word)]);
+    }
+
+    #[test]
+    fn test_parse_str_search_granularity_splits_three_char_hanzi_words() {
+        // A 3-character word is long enough for the gate to let
+        // `cut_for_search`'s cut point through.
+        let tokens = parse_str("中华人", &AlwaysCutAfterFirstChar, Granularity::Search);
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 0, 3, Word),
+                test_macros::token!(3, 6, 9, Word),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_str_with_atoms_protects_a_url_from_its_own_punctuation_splits() {
+        // "http://example.com" mixes Word and NonWord chars, which
+        // `CharGroup::push` would otherwise always break into several
+        // groups -- `AtomMatcher` carves it out before that pipeline ever
+        // sees it.
+        let atoms = AtomMatcher::new(["http://example.com"]);
+        let tokens = parse_str_with_atoms(
+            "see http://example.com here",
+            &Jieba::new(),
+            Granularity::Word,
+            Some(&atoms),
+        );
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 2, 3, Word),
+                test_macros::token!(3, 3, 4, Space),
+                test_macros::token!(4, 22, 23, Word),
+                test_macros::token!(23, 23, 24, Space),
+                test_macros::token!(24, 27, 28, Word),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_str_with_atoms_leftmost_longest_on_overlap() {
+        // Both "::" and ":::" are registered; a run of three colons must
+        // match the longer atom rather than "::" plus a stray ":".
+        let atoms = AtomMatcher::new(["::", ":::"]);
+        let tokens =
+            parse_str_with_atoms(":::", &Jieba::new(), Granularity::Word, Some(&atoms));
+        assert_eq!(tokens, vec![test_macros::token!(0, 2, 3, Word)]);
+    }
+
+    #[test]
+    fn test_parse_str_with_atoms_no_match_is_identical_to_parse_str() {
+        let atoms = AtomMatcher::new(["http://example.com"]);
+        let with_atoms =
+            parse_str_with_atoms("hello world", &Jieba::new(), Granularity::Word, Some(&atoms));
+        let plain = parse_str("hello world", &Jieba::new(), Granularity::Word);
+        assert_eq!(with_atoms, plain);
+    }
+
+    #[test]
+    fn test_parse_str_with_atoms_none_is_identical_to_parse_str() {
+        let with_atoms =
+            parse_str_with_atoms("hello world", &Jieba::new(), Granularity::Word, None);
+        let plain = parse_str("hello world", &Jieba::new(), Granularity::Word);
+        assert_eq!(with_atoms, plain);
+    }
+
+    #[test]
+    fn test_parse_str_splits_at_kana_kanji_boundary() {
+        // "東京です" is 漢字 "東京" followed by hiragana "です", with no
+        // whitespace in between. The script transition alone should split
+        // them into two `word` tokens, without being glued into one
+        // `Hanzi` group and sent to `cut_hmm`.
+        let tokens = parse_str("東京です", &PairCutter, Granularity::Word);
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 3, 6, Word),
+                test_macros::token!(6, 9, 12, Word),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_str_splits_at_hiragana_katakana_boundary() {
+        // "です" (hiragana) followed directly by "カタカナ" (katakana).
+        let tokens = parse_str("ですカタカナ", &PairCutter, Granularity::Word);
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 3, 6, Word),
+                test_macros::token!(6, 15, 18, Word),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_str_splits_at_hangul_kanji_boundary() {
+        // "한글" (hangul) followed directly by "漢字" (kanji).
+        let tokens = parse_str("한글漢字", &PairCutter, Granularity::Word);
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 3, 6, Word),
+                test_macros::token!(6, 9, 12, Word),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_str_hangul_absorbs_trailing_other() {
+        // A trailing ASCII suffix folds into the hangul group instead of
+        // splitting it, the same way `Dictionary` groups absorb `Other`.
+        let tokens = parse_str("한글abc", &PairCutter, Granularity::Word);
+        assert_eq!(tokens, vec![test_macros::token!(0, 8, 9, Word)]);
+    }
+
+    #[test]
+    fn test_grapheme_cluster_ends() {
+        let classifier = CharClassifier::default();
+
+        // Plain ASCII: every char is its own cluster.
+        assert_eq!(grapheme_cluster_ends("ab", &classifier), vec![1, 2]);
+
+        // CRLF never splits (GB3).
+        assert_eq!(grapheme_cluster_ends("\r\n", &classifier), vec![2]);
+
+        // A base char plus a combining mark is one cluster (GB9).
+        let s = "e\u{0301}x"; // "é" (decomposed) + "x"
+        assert_eq!(grapheme_cluster_ends(s, &classifier), vec![3, 4]);
+
+        // Two regional indicators pair into one flag (GB12/GB13); a third
+        // starts a new cluster rather than pairing with the second.
+        let flag = "\u{1f1fa}\u{1f1f8}"; // 🇺🇸
+        assert_eq!(grapheme_cluster_ends(flag, &classifier), vec![8]);
+        let three_ri = "\u{1f1fa}\u{1f1f8}\u{1f1e6}";
+        assert_eq!(grapheme_cluster_ends(three_ri, &classifier), vec![8, 12]);
+
+        // Emoji + ZWJ + emoji stays one cluster (GB11).
+        let zwj_seq = "\u{1f468}\u{200d}\u{1f469}"; // man + ZWJ + woman
+        assert_eq!(grapheme_cluster_ends(zwj_seq, &classifier), vec![11]);
+
+        assert_eq!(grapheme_cluster_ends("", &classifier), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_parse_str_into_chars_keeps_combining_marks_with_their_base() {
+        let classifier = CharClassifier::default();
+        let config = CharClassConfig::default();
+        // "é" decomposed as "e" + U+0301: one `Char` spanning both bytes,
+        // categorized by the base scalar -- not a second `Char` for the
+        // combining mark that would fall through to `NonWord::Other`.
+        let chars = parse_str_into_chars("e\u{0301}x", &classifier, &config);
+        assert_eq!(chars.len(), 2);
+        assert_eq!(chars[0].ch, 'e');
+        assert_eq!(chars[0].col.start_byte_index, 0);
+        assert_eq!(chars[0].col.excl_end_byte_index, 3);
+        assert!(matches!(
+            chars[0].ty,
+            CharType::Word(WordCharType::Other)
         ));
-        assert!(matches!(categorize_char('\u{3000}'), CharType::Space));
+        assert_eq!(chars[1].ch, 'x');
+        assert_eq!(chars[1].col.start_byte_index, 3);
+
+        // The cluster boundary invariant `CharGroup::push`/`append` rely on:
+        // one cluster's exclusive end is the next cluster's start byte.
+        assert_eq!(
+            chars[0].col.excl_end_byte_index,
+            chars[1].col.start_byte_index
+        );
     }
 
     #[test]
     fn test_char_group_split_into_subgroups() {
         let cg = CharGroup {
-            chars: vec!['h', 'e', 'l', 'l', 'o'],
+            chars: vec!["h", "e", "l", "l", "o"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
             col: Col {
                 start_byte_index: 0,
                 incl_end_byte_index: 4,
@@ -811,12 +2946,16 @@ mod tests {
             },
             ty: CharGroupType::Word(WordCharGroupType::Other),
         };
-        let groups = cg.split_into_subgroups(vec![2, 2, 1]);
+        let groups = cg.split_into_subgroups(
+            vec![2, 2, 1],
+            &CharClassifier::default(),
+            &CharClassConfig::default(),
+        );
         assert_eq!(
             groups,
             vec![
                 CharGroup {
-                    chars: vec!['h', 'e'],
+                    chars: vec!["h".to_string(), "e".to_string()],
                     col: Col {
                         start_byte_index: 0,
                         incl_end_byte_index: 1,
@@ -825,7 +2964,7 @@ mod tests {
                     ty: CharGroupType::Word(WordCharGroupType::Other),
                 },
                 CharGroup {
-                    chars: vec!['l', 'l'],
+                    chars: vec!["l".to_string(), "l".to_string()],
                     col: Col {
                         start_byte_index: 2,
                         incl_end_byte_index: 3,
@@ -834,7 +2973,7 @@ mod tests {
                     ty: CharGroupType::Word(WordCharGroupType::Other),
                 },
                 CharGroup {
-                    chars: vec!['o'],
+                    chars: vec!["o".to_string()],
                     col: Col {
                         start_byte_index: 4,
                         incl_end_byte_index: 4,
@@ -846,6 +2985,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_char_group_to_string_preserves_full_grapheme_clusters() {
+        // A decomposed "é" ("e" + U+0301) followed by a ZWJ-joined emoji
+        // sequence: `group_chars_rule` groups each into a `CharGroup` by
+        // its leading scalar's class, but `to_string()` must still return
+        // every byte of both clusters, not just their leading scalars.
+        let line = "e\u{0301}\u{1f468}\u{200d}\u{1f469}";
+        let classifier = CharClassifier::default();
+        let config = CharClassConfig::default();
+        let chars = parse_str_into_chars(line, &classifier, &config);
+        let reconstructed: String = utils::stack_merge(chars, &(), group_chars_rule)
+            .into_iter()
+            .map(|g| g.to_string())
+            .collect();
+        assert_eq!(reconstructed, line);
+    }
+
     static JIEBA: OnceCell<Jieba> = OnceCell::new();
 
     #[ctor::ctor]
@@ -854,7 +3010,7 @@ mod tests {
     }
 
     fn parse_str_test(s: &str, into_word: bool) -> Vec<Token> {
-        parse_str(s, JIEBA.get().unwrap(), into_word)
+        parse_str(s, JIEBA.get().unwrap(), Granularity::from(into_word))
     }
 
     proptest! {
@@ -1014,4 +3170,167 @@ mod tests {
             ]
         );
     }
+
+    fn parse_sentences_test(s: &str) -> Vec<Token> {
+        parse_str_into_sentences(s, JIEBA.get().unwrap())
+    }
+
+    #[test]
+    #[ntest_timeout::timeout(10)]
+    fn test_parse_sentences_basic() {
+        let tokens = parse_sentences_test("Hello world. Next sentence.");
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 11, 12, Sentence), // "Hello world."
+                test_macros::token!(12, 12, 13, Space),
+                test_macros::token!(13, 26, 27, Sentence), // "Next sentence."
+            ]
+        );
+    }
+
+    #[test]
+    #[ntest_timeout::timeout(10)]
+    fn test_parse_sentences_decimal_not_terminal() {
+        // The decimal point in "3.14" doesn't end the sentence, since it's
+        // a `.` immediately flanked by word (digit) chars on both sides.
+        let tokens = parse_sentences_test("Pi is 3.14 today.");
+        assert_eq!(
+            tokens,
+            vec![test_macros::token!(0, 16, 17, Sentence)]
+        );
+    }
+
+    #[test]
+    #[ntest_timeout::timeout(10)]
+    fn test_parse_sentences_closing_quote_trails_terminator() {
+        // The closing quote after the period stays part of the sentence.
+        let tokens = parse_sentences_test(r#"She said "no." Then left."#);
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 13, 14, Sentence), // `She said "no."`
+                test_macros::token!(14, 14, 15, Space),
+                test_macros::token!(15, 24, 25, Sentence), // "Then left."
+            ]
+        );
+    }
+
+    #[test]
+    #[ntest_timeout::timeout(10)]
+    fn test_parse_sentences_cjk_terminator() {
+        let tokens = parse_sentences_test("你好。世界！");
+        assert_eq!(
+            tokens,
+            vec![
+                test_macros::token!(0, 8, 9, Sentence),   // "你好。"
+                test_macros::token!(9, 17, 18, Sentence), // "世界！"
+            ]
+        );
+    }
+
+    /// A toy [`hmm::HmmModel`] that only ever merges exactly two chars,
+    /// '三' then '四', into one `B`/`E` word: every other (state, char)
+    /// emission is left at [`hmm`]'s default fallback, so the Viterbi path
+    /// is forced through `B` at the first char and `E` at the second.
+    fn two_char_merge_model() -> hmm::HmmModel {
+        use std::collections::HashMap;
+        const MIN: f64 = -1e100;
+        // Indexed B, M, E, S.
+        let start_p = [0.0, MIN, MIN, MIN];
+        let trans_p = [
+            [MIN, MIN, 0.0, MIN], // B -> E
+            [MIN, MIN, MIN, MIN],
+            [MIN, MIN, MIN, MIN],
+            [MIN, MIN, MIN, MIN],
+        ];
+        let mut emit_b = HashMap::new();
+        emit_b.insert('三', 0.0);
+        let mut emit_e = HashMap::new();
+        emit_e.insert('四', 0.0);
+        hmm::HmmModel::new(start_p, trans_p, [emit_b, HashMap::new(), emit_e, HashMap::new()])
+    }
+
+    #[test]
+    fn test_hmm_model_merges_oov_run_into_one_word() {
+        let model = two_char_merge_model();
+        assert_eq!(model.cut("三四"), vec![2]);
+    }
+
+    #[test]
+    fn test_apply_hmm_fallback_only_touches_single_char_runs() {
+        let model = two_char_merge_model();
+        // "B超三四" cut by `cut_hmm` as ("B超", "三", "四"): the dictionary
+        // piece stays a single 2-char group, while the trailing
+        // un-dictionaried run gets re-split by the model.
+        let n_chars = apply_hmm_fallback("B超三四", vec![2, 1, 1], &model);
+        assert_eq!(n_chars, vec![2, 2]);
+    }
+
+    #[test]
+    fn test_apply_hmm_fallback_leaves_lone_unmatched_char_alone() {
+        // A single `1` run with no neighboring `1` isn't passed through the
+        // model at all -- there's nothing for an HMM to decide between one
+        // character on its own.
+        struct PanicsIfCalled;
+        impl Segmenter for PanicsIfCalled {
+            fn cut(&self, _s: &str) -> Vec<usize> {
+                panic!("segmenter should not be invoked for a lone char run");
+            }
+        }
+        let n_chars = apply_hmm_fallback("超人", vec![1, 1], &PanicsIfCalled);
+        assert_eq!(n_chars, vec![1, 1]);
+    }
+
+    #[test]
+    fn test_hmm_fallback_default_is_none() {
+        assert!(PairCutter.hmm_fallback().is_none());
+    }
+
+    #[test]
+    #[ntest_timeout::timeout(10)]
+    fn test_parse_str_applies_hmm_fallback_to_oov_run() {
+        struct HmmCutter(hmm::HmmModel);
+
+        impl JiebaPlaceholder for HmmCutter {
+            fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+                // Simulate a dictionary with no entries at all: every char
+                // is its own un-dictionaried piece.
+                sentence
+                    .char_indices()
+                    .map(|(i, c)| &sentence[i..i + c.len_utf8()])
+                    .collect()
+            }
+
+            fn hmm_fallback(&self) -> Option<&dyn Segmenter> {
+                Some(&self.0)
+            }
+        }
+
+        let cutter = HmmCutter(two_char_merge_model());
+        let tokens = parse_str("三四", &cutter, Granularity::Word);
+        assert_eq!(tokens, vec![test_macros::token!(0, 3, 6, Word)]);
+    }
+
+    #[test]
+    fn test_tokenize_matches_parse_str_for_every_granularity() {
+        let line = "中华人民  foo_bar中华人";
+        for granularity in [Granularity::Word, Granularity::WORD, Granularity::Search] {
+            let eager = parse_str(line, &PairCutter, granularity);
+            let lazy: Vec<Token> = tokenize(line, &PairCutter, granularity).collect();
+            assert_eq!(lazy, eager, "granularity {:?}", granularity);
+        }
+    }
+
+    #[test]
+    fn test_tokenize_can_stop_early_without_exhausting_the_line() {
+        // Only pulling the first token must not panic or require the rest
+        // of a (deliberately malformed-if-fully-drawn) line to resolve --
+        // `take(1)` should short-circuit `Tokens::next` after one group.
+        let line = "foo bar baz qux";
+        let first = tokenize(line, &PairCutter, Granularity::Word)
+            .next()
+            .unwrap();
+        assert_eq!(first, test_macros::token!(0, 2, 3, Word));
+    }
 }
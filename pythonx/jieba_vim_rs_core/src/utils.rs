@@ -36,3 +36,58 @@ where
 {
     i.into_iter().chain(j.into_iter()).collect()
 }
+
+/// A lazy, streaming counterpart to [`stack_merge`]: same merge-with-the-
+/// last-emitted-item algorithm, but pulls from an `Iterator<Item = T>` and
+/// yields `U`s one at a time instead of materializing the whole input and
+/// output as `Vec`s. Of what `rule_func` returns on each call, every
+/// element but the last is immediately finalized and queued for output;
+/// the last is held back as `pending`, since `stack_merge`'s next
+/// `stack.pop()` would still merge into it.
+pub struct StackMergeIter<'a, I, U, F, A> {
+    elements: I,
+    args: &'a A,
+    rule_func: F,
+    pending: Option<U>,
+    ready: std::collections::VecDeque<U>,
+}
+
+impl<'a, I, T, U, F, A> StackMergeIter<'a, I, U, F, A>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(Option<U>, T, &A) -> Vec<U>,
+{
+    pub fn new(elements: I, args: &'a A, rule_func: F) -> Self {
+        Self {
+            elements,
+            args,
+            rule_func,
+            pending: None,
+            ready: std::collections::VecDeque::new(),
+        }
+    }
+}
+
+impl<'a, I, T, U, F, A> Iterator for StackMergeIter<'a, I, U, F, A>
+where
+    I: Iterator<Item = T>,
+    F: FnMut(Option<U>, T, &A) -> Vec<U>,
+{
+    type Item = U;
+
+    fn next(&mut self) -> Option<U> {
+        loop {
+            if let Some(item) = self.ready.pop_front() {
+                return Some(item);
+            }
+            match self.elements.next() {
+                Some(e) => {
+                    let mut merged = (self.rule_func)(self.pending.take(), e, self.args);
+                    self.pending = merged.pop();
+                    self.ready.extend(merged);
+                }
+                None => return self.pending.take(),
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use std::env;
+use std::path::PathBuf;
+
+/// Regenerate `include/jieba_vim_rs_ffi.h` from this crate's `extern "C"`
+/// surface on every build, so the header handed to Lua/ctypes/cgo bindings
+/// can never drift from the `#[no_mangle]` functions that back it.
+fn main() {
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_path: PathBuf = [&crate_dir, "include", "jieba_vim_rs_ffi.h"]
+        .iter()
+        .collect();
+    println!("cargo:rerun-if-changed=src");
+
+    match cbindgen::generate(&crate_dir) {
+        Ok(bindings) => {
+            bindings.write_to_file(&out_path);
+        }
+        Err(err) => {
+            // The header is a convenience for bindings generators, not a
+            // requirement for the cdylib/staticlib itself to link -- don't
+            // fail the whole build over it.
+            println!(
+                "cargo:warning=cbindgen failed to generate {}: {}",
+                out_path.display(),
+                err
+            );
+        }
+    }
+}
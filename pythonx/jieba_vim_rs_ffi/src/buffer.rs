@@ -0,0 +1,100 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use std::fmt;
+use std::os::raw::c_void;
+use std::slice;
+
+use jieba_vim_rs_core::motion::BufferLike;
+
+/// Status codes a host's [`CBufferCallbacks::get_line`] may return.
+pub mod status {
+    pub const OK: i32 = 0;
+    pub const OUT_OF_BOUNDS: i32 = 1;
+}
+
+/// The two callbacks a host supplies in place of a concrete buffer type, so
+/// its text never has to be copied wholesale into a Rust-owned buffer up
+/// front the way `jieba_vim_rs_cli::buffer::LineBuffer`/
+/// `jieba_vim_rs_server::buffer::LineBuffer` do.
+///
+/// `get_line` must write a UTF-8 byte span for line `lnum` (1-indexed)
+/// through `out_ptr`/`out_len` and return [`status::OK`], or leave them
+/// untouched and return a nonzero status (e.g. [`status::OUT_OF_BOUNDS`]).
+/// The written pointer only needs to stay valid for the duration of the
+/// call -- [`CBuffer::getline`] copies the bytes into an owned `String`
+/// before returning.
+#[repr(C)]
+pub struct CBufferCallbacks {
+    pub ctx: *mut c_void,
+    pub line_count: unsafe extern "C" fn(ctx: *mut c_void) -> usize,
+    pub get_line: unsafe extern "C" fn(
+        ctx: *mut c_void,
+        lnum: usize,
+        out_ptr: *mut *const u8,
+        out_len: *mut usize,
+    ) -> i32,
+}
+
+/// A host `get_line` call returned a nonzero status, carried through
+/// [`BufferLike::Error`] so `jieba_vim_motion_run` can hand the same code
+/// back to the host as the call's own return status.
+#[derive(Debug)]
+pub struct CBufferError {
+    pub code: i32,
+}
+
+impl fmt::Display for CBufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "host buffer callback returned status {}", self.code)
+    }
+}
+
+impl std::error::Error for CBufferError {}
+
+/// [`BufferLike`] over a host's [`CBufferCallbacks`], borrowed only for the
+/// duration of one motion call.
+pub struct CBuffer<'a>(&'a CBufferCallbacks);
+
+impl<'a> CBuffer<'a> {
+    pub fn new(callbacks: &'a CBufferCallbacks) -> Self {
+        Self(callbacks)
+    }
+}
+
+impl<'a> BufferLike for CBuffer<'a> {
+    type Error = CBufferError;
+
+    fn getline(&self, lnum: usize) -> Result<String, Self::Error> {
+        let mut ptr: *const u8 = std::ptr::null();
+        let mut len: usize = 0;
+        // Safety: `get_line` is supplied by the host, which per
+        // `CBufferCallbacks`'s contract writes a valid `(ptr, len)` UTF-8
+        // span when it returns `status::OK`.
+        let code = unsafe { (self.0.get_line)(self.0.ctx, lnum, &mut ptr, &mut len) };
+        if code != status::OK {
+            return Err(CBufferError { code });
+        }
+        // Safety: the host's contract above guarantees `ptr` is valid for
+        // `len` bytes for at least the duration of this call.
+        let bytes = unsafe { slice::from_raw_parts(ptr, len) };
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    fn lines(&self) -> Result<usize, Self::Error> {
+        // Safety: `line_count` is supplied by the host and borrows nothing
+        // beyond `ctx`, which outlives this call.
+        Ok(unsafe { (self.0.line_count)(self.0.ctx) })
+    }
+}
@@ -0,0 +1,110 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use std::os::raw::c_int;
+
+use jieba_vim_rs_core::motion::{BufferLike, MotionOutput, WordMotion};
+
+use crate::motion::JiebaWrapper;
+
+/// `mode` values for [`crate::jieba_vim_motion_run`]'s `mode` parameter.
+pub mod mode {
+    pub const NORMAL: i32 = 0;
+    pub const OPERATOR_PENDING: i32 = 1;
+    pub const VISUAL: i32 = 2;
+}
+
+/// `motion` values for [`crate::jieba_vim_motion_run`]'s `motion` parameter.
+/// `word`/`count`/`mode` together distinguish e.g. `w` from `W` and `n` from
+/// `x`, so there is no separate "uppercase" variant here.
+pub mod motion {
+    pub const W: i32 = 0;
+    pub const E: i32 = 1;
+    pub const B: i32 = 2;
+}
+
+/// `operator` values for [`crate::jieba_vim_motion_run`]'s `operator`
+/// parameter. Only consulted when `mode` is [`mode::OPERATOR_PENDING`].
+pub mod operator {
+    pub const NONE: i32 = 0;
+    pub const CHANGE: i32 = 1;
+    /// `d` and `y` share the same exclusive/inclusive and word-boundary
+    /// rules -- yank never edits the buffer, so it never needs `cw`'s
+    /// extend-onto-trailing-space special case that `c` alone gets.
+    pub const DELETE_OR_YANK: i32 = 2;
+}
+
+/// Why [`run`] failed: either `(mode, motion, operator)` didn't name a
+/// supported combination, or the host's buffer callback itself errored.
+pub enum DispatchError<E> {
+    UnknownMode,
+    UnknownMotion,
+    UnknownOperator,
+    Buffer(E),
+}
+
+fn bare(new_cursor_pos: (usize, usize)) -> MotionOutput {
+    MotionOutput {
+        new_cursor_pos,
+        d_special: false,
+        prevent_change: false,
+    }
+}
+
+/// Run the motion described by `(mode, motion, operator)` against `wm`,
+/// dispatching to the `nmap_*`/`omap_*`/`xmap_*` family the same way
+/// `jieba_vim_rs_server::dispatch::run` does over JSON-RPC params instead of
+/// C ints.
+pub fn run<B: BufferLike + ?Sized>(
+    wm: &WordMotion<JiebaWrapper>,
+    buffer: &B,
+    cursor_pos: (usize, usize),
+    count: u64,
+    word: bool,
+    mode: c_int,
+    motion: c_int,
+    operator: c_int,
+) -> Result<MotionOutput, DispatchError<B::Error>> {
+    use self::{mode as m, motion as mo, operator as op};
+
+    let output = match (mode, operator) {
+        (m::NORMAL, _) => match motion {
+            mo::W => wm.nmap_w(buffer, cursor_pos, count, word).map(bare),
+            mo::E => wm.nmap_e(buffer, cursor_pos, count, word),
+            mo::B => wm.nmap_b(buffer, cursor_pos, count, word).map(bare),
+            _ => return Err(DispatchError::UnknownMotion),
+        },
+        (m::VISUAL, _) => match motion {
+            mo::W => wm.xmap_w(buffer, cursor_pos, count, word),
+            mo::E => wm.xmap_e(buffer, cursor_pos, count, word),
+            mo::B => wm.xmap_b(buffer, cursor_pos, count, word),
+            _ => return Err(DispatchError::UnknownMotion),
+        },
+        (m::OPERATOR_PENDING, op::CHANGE) => match motion {
+            mo::W => wm.omap_c_w(buffer, cursor_pos, count, word),
+            mo::E => wm.omap_e(buffer, cursor_pos, count, word),
+            mo::B => wm.omap_b(buffer, cursor_pos, count, word).map(bare),
+            _ => return Err(DispatchError::UnknownMotion),
+        },
+        (m::OPERATOR_PENDING, op::DELETE_OR_YANK) => match motion {
+            mo::W => wm.omap_w(buffer, cursor_pos, count, word),
+            mo::E => wm.omap_e(buffer, cursor_pos, count, word),
+            mo::B => wm.omap_b(buffer, cursor_pos, count, word).map(bare),
+            _ => return Err(DispatchError::UnknownMotion),
+        },
+        (m::OPERATOR_PENDING, _) => return Err(DispatchError::UnknownOperator),
+        _ => return Err(DispatchError::UnknownMode),
+    };
+    output.map_err(DispatchError::Buffer)
+}
@@ -0,0 +1,178 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+//! `extern "C"` ABI over [`jieba_vim_rs_core::motion::WordMotion`], so a
+//! host with no Rust toolchain (a Neovim Lua plugin via `ffi.cdef`, a C
+//! program, a cgo binding, ...) can drive jieba.vim's segmentation-aware
+//! motions directly instead of going through `jieba_vim_rs_server`'s
+//! JSON-RPC subprocess boundary.
+//!
+//! This crate builds as a `cdylib`/`staticlib`; `build.rs` runs `cbindgen`
+//! over it to emit `include/jieba_vim_rs_ffi.h`, so Lua (`ffi.cdef`),
+//! Python (`ctypes`/`cffi`), and cgo bindings all fall out of the same
+//! header instead of being hand-maintained per host.
+//!
+//! The buffer is never copied wholesale into an owned Rust buffer: every
+//! call takes a [`buffer::CBufferCallbacks`] pair of host-supplied function
+//! pointers, mirroring how `jieba_vim_rs_cli`/`jieba_vim_rs_server` each
+//! implement [`jieba_vim_rs_core::motion::BufferLike`] over their own
+//! buffer representation instead.
+
+mod buffer;
+mod dispatch;
+mod motion;
+
+use std::ffi::CStr;
+use std::fs::File;
+use std::io::BufReader;
+use std::os::raw::c_char;
+
+use jieba_rs::Jieba;
+use jieba_vim_rs_core::motion::WordMotion;
+
+pub use buffer::status as buffer_status;
+pub use buffer::CBufferCallbacks;
+pub use dispatch::{mode, motion as motion_kind, operator};
+use dispatch::DispatchError;
+use motion::JiebaWrapper;
+
+/// Status codes returned by every `jieba_vim_motion_*` entry point. `OK` is
+/// always `0`; every other variant is its own negative constant so a host
+/// never needs to special-case which kind of call produced the failure.
+pub mod status {
+    pub const OK: i32 = 0;
+    pub const NULL_ARGUMENT: i32 = -1;
+    pub const UNKNOWN_MODE: i32 = -2;
+    pub const UNKNOWN_MOTION: i32 = -3;
+    pub const UNKNOWN_OPERATOR: i32 = -4;
+    /// A positive status means the host's own
+    /// [`CBufferCallbacks::get_line`] returned it; see
+    /// [`crate::buffer_status`].
+}
+
+/// Opaque handle to a [`WordMotion`], returned by [`jieba_vim_motion_new`]
+/// and freed by [`jieba_vim_motion_free`]. The host only ever holds and
+/// passes back the pointer; its layout is not part of the ABI.
+pub struct WordMotionHandle(WordMotion<JiebaWrapper>);
+
+/// [`jieba_vim_rs_core::motion::MotionOutput`], laid out for C.
+#[repr(C)]
+pub struct CMotionOutput {
+    pub new_lnum: usize,
+    pub new_col: usize,
+    pub d_special: bool,
+    pub prevent_change: bool,
+}
+
+/// Construct a [`WordMotionHandle`] from a jieba dictionary file at
+/// `dict_path` (jieba's own plain-text `word freq tag` format), or the
+/// bundled default dictionary if `dict_path` is null. `hmm` mirrors
+/// jieba-rs's own `Jieba::cut` flag -- see `JiebaWrapper`.
+///
+/// Returns null on a malformed path or a dictionary load failure; a host
+/// that needs to distinguish the two should `stat`/open `dict_path` itself
+/// first, since this entry point has no `String` channel to report why.
+///
+/// # Safety
+///
+/// `dict_path`, if non-null, must be a valid null-terminated C string for
+/// the duration of this call.
+#[no_mangle]
+pub unsafe extern "C" fn jieba_vim_motion_new(
+    dict_path: *const c_char,
+    hmm: bool,
+) -> *mut WordMotionHandle {
+    let jieba = if dict_path.is_null() {
+        Jieba::new()
+    } else {
+        let path = match CStr::from_ptr(dict_path).to_str() {
+            Ok(path) => path,
+            Err(_) => return std::ptr::null_mut(),
+        };
+        let file = match File::open(path) {
+            Ok(file) => file,
+            Err(_) => return std::ptr::null_mut(),
+        };
+        match Jieba::with_dict(&mut BufReader::new(file)) {
+            Ok(jieba) => jieba,
+            Err(_) => return std::ptr::null_mut(),
+        }
+    };
+    let wm = WordMotion::new(JiebaWrapper { jieba, hmm });
+    Box::into_raw(Box::new(WordMotionHandle(wm)))
+}
+
+/// Destroy a handle returned by [`jieba_vim_motion_new`]. A null `handle` is
+/// a no-op.
+///
+/// # Safety
+///
+/// `handle` must be either null or a pointer previously returned by
+/// [`jieba_vim_motion_new`] and not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn jieba_vim_motion_free(handle: *mut WordMotionHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Run one motion against `handle`'s buffer, as described by
+/// `jieba_vim_rs_ffi::{mode, motion_kind, operator}`'s constants, writing
+/// the result through `out` and returning [`status::OK`] on success.
+///
+/// `lnum`/`col` is the cursor position (1-indexed line, 0-indexed column,
+/// matching every other cursor tuple in this crate family); `word` is
+/// `true` for `w`/`e`/`b` and `false` for `W`/`E`/`B`; `operator` is only
+/// consulted when `mode` is [`mode::OPERATOR_PENDING`].
+///
+/// # Safety
+///
+/// `handle` must be a live pointer from [`jieba_vim_motion_new`];
+/// `callbacks` and `out` must be non-null and valid for the duration of
+/// this call; `callbacks.ctx`/`callbacks.line_count`/`callbacks.get_line`
+/// must together satisfy [`buffer::CBufferCallbacks`]'s contract.
+#[no_mangle]
+pub unsafe extern "C" fn jieba_vim_motion_run(
+    handle: *const WordMotionHandle,
+    callbacks: *const CBufferCallbacks,
+    lnum: usize,
+    col: usize,
+    count: u64,
+    word: bool,
+    mode: i32,
+    motion: i32,
+    operator: i32,
+    out: *mut CMotionOutput,
+) -> i32 {
+    if handle.is_null() || callbacks.is_null() || out.is_null() {
+        return status::NULL_ARGUMENT;
+    }
+    let wm = &(*handle).0;
+    let buf = buffer::CBuffer::new(&*callbacks);
+    match dispatch::run(wm, &buf, (lnum, col), count, word, mode, motion, operator) {
+        Ok(output) => {
+            *out = CMotionOutput {
+                new_lnum: output.new_cursor_pos.0,
+                new_col: output.new_cursor_pos.1,
+                d_special: output.d_special,
+                prevent_change: output.prevent_change,
+            };
+            status::OK
+        }
+        Err(DispatchError::UnknownMode) => status::UNKNOWN_MODE,
+        Err(DispatchError::UnknownMotion) => status::UNKNOWN_MOTION,
+        Err(DispatchError::UnknownOperator) => status::UNKNOWN_OPERATOR,
+        Err(DispatchError::Buffer(err)) => err.code,
+    }
+}
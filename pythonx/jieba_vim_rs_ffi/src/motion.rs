@@ -0,0 +1,34 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use jieba_rs::Jieba;
+use jieba_vim_rs_core::token::JiebaPlaceholder;
+
+/// `hmm` mirrors jieba-rs's own `Jieba::cut` flag; see
+/// `jieba_vim_rs_server::dispatch::JiebaWrapper` for the equivalent over a
+/// JSON-RPC boundary instead of a C ABI one.
+pub struct JiebaWrapper {
+    pub jieba: Jieba,
+    pub hmm: bool,
+}
+
+impl JiebaPlaceholder for JiebaWrapper {
+    fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        self.jieba.cut(sentence, self.hmm)
+    }
+
+    fn cut_other<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        jieba_vim_rs_core::token::subword::split(sentence)
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use std::fmt;
+
+use jieba_vim_rs_core::motion::BufferLike;
+
+/// A buffer of lines carried in a request's `buffer` field, analogous to
+/// `jieba_vim_rs_cli::buffer::LineBuffer`. Every request is stateless and
+/// brings its own buffer, so unlike the Vim/PyO3 side there is nothing to
+/// cache between requests.
+pub struct LineBuffer(Vec<String>);
+
+impl LineBuffer {
+    pub fn new(lines: Vec<String>) -> Self {
+        Self(lines)
+    }
+}
+
+#[derive(Debug)]
+pub struct BufferError {
+    lnum: usize,
+    n_lines: usize,
+}
+
+impl fmt::Display for BufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {} is out of bounds (buffer has {} line(s))",
+            self.lnum, self.n_lines
+        )
+    }
+}
+
+impl std::error::Error for BufferError {}
+
+impl BufferLike for LineBuffer {
+    type Error = BufferError;
+
+    fn getline(&self, lnum: usize) -> Result<String, Self::Error> {
+        lnum.checked_sub(1)
+            .and_then(|idx| self.0.get(idx))
+            .cloned()
+            .ok_or(BufferError {
+                lnum,
+                n_lines: self.0.len(),
+            })
+    }
+
+    fn lines(&self) -> Result<usize, Self::Error> {
+        Ok(self.0.len())
+    }
+}
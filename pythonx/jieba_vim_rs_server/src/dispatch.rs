@@ -0,0 +1,122 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use jieba_rs::Jieba;
+use jieba_vim_rs_core::motion::{MotionOutput, WordMotion};
+use jieba_vim_rs_core::token::JiebaPlaceholder;
+
+use crate::buffer::LineBuffer;
+use crate::protocol::{MotionParams, MotionResult, Span};
+
+/// `hmm` mirrors jieba-rs's own `Jieba::cut` flag: with it on, unknown runs
+/// of Hanzi fall back to the HMM-based new-word discovery model instead of
+/// only the dictionary; `--no-hmm` turns it off for users who want strictly
+/// dictionary-driven segmentation (e.g. to make a custom dictionary's word
+/// boundaries fully deterministic).
+pub struct JiebaWrapper {
+    pub jieba: Jieba,
+    pub hmm: bool,
+}
+
+impl JiebaPlaceholder for JiebaWrapper {
+    fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        self.jieba.cut(sentence, self.hmm)
+    }
+
+    fn cut_other<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        jieba_vim_rs_core::token::subword::split(sentence)
+    }
+}
+
+fn bare(new_cursor_pos: (usize, usize)) -> MotionOutput {
+    MotionOutput {
+        new_cursor_pos,
+        d_special: false,
+        prevent_change: false,
+    }
+}
+
+/// Whether `motion`/`operator` is inclusive of its endpoint, per
+/// `:help exclusive`/`:help inclusive`: `e`/`E` always are, `w`/`W`/`b`/`B`
+/// never are (the `cw`-style word-boundary adjustment `omap_c_w` makes is
+/// captured by `d_special`/`prevent_change` instead, not by flipping this).
+fn is_inclusive(motion: &str) -> bool {
+    matches!(motion, "e" | "E")
+}
+
+fn span(before: (usize, usize), after: (usize, usize)) -> Span {
+    Span {
+        start: before.min(after),
+        end: before.max(after),
+    }
+}
+
+/// Run the motion described by `params` against an ephemeral `WordMotion`,
+/// dispatching to the `nmap_*`/`omap_*`/`xmap_*` family the same way
+/// `jieba_vim_rs_cli::motion::run` and `WordMotionWrapper` do.
+pub fn run(wm: &WordMotion<JiebaWrapper>, params: &MotionParams) -> Result<MotionResult, String> {
+    let buffer = LineBuffer::new(params.buffer.clone());
+    let word = match params.motion.as_str() {
+        "w" | "e" | "b" => true,
+        "W" | "E" | "B" => false,
+        other => return Err(format!("unsupported motion: `{}`", other)),
+    };
+
+    let output = match (params.mode.as_str(), params.operator.as_deref()) {
+        ("n", _) => match params.motion.as_str() {
+            "w" | "W" => wm.nmap_w(&buffer, params.cursor, params.count, word).map(bare),
+            "e" | "E" => wm.nmap_e(&buffer, params.cursor, params.count, word),
+            "b" | "B" => wm.nmap_b(&buffer, params.cursor, params.count, word).map(bare),
+            other => return Err(format!("unsupported motion: `{}`", other)),
+        },
+        ("x", _) => match params.motion.as_str() {
+            "w" | "W" => wm.xmap_w(&buffer, params.cursor, params.count, word),
+            "e" | "E" => wm.xmap_e(&buffer, params.cursor, params.count, word),
+            "b" | "B" => wm.xmap_b(&buffer, params.cursor, params.count, word),
+            other => return Err(format!("unsupported motion: `{}`", other)),
+        },
+        ("o", Some("c")) => match params.motion.as_str() {
+            "w" | "W" => wm.omap_c_w(&buffer, params.cursor, params.count, word),
+            "e" | "E" => wm.omap_e(&buffer, params.cursor, params.count, word),
+            "b" | "B" => wm.omap_b(&buffer, params.cursor, params.count, word).map(bare),
+            other => return Err(format!("unsupported motion: `{}`", other)),
+        },
+        // `d` and `y` share the same exclusive/inclusive and word-boundary
+        // rules -- yank never edits the buffer, so it never needs `cw`'s
+        // extend-onto-trailing-space special case that `c` alone gets.
+        ("o", Some("d" | "y")) => match params.motion.as_str() {
+            "w" | "W" => wm.omap_w(&buffer, params.cursor, params.count, word),
+            "e" | "E" => wm.omap_e(&buffer, params.cursor, params.count, word),
+            "b" | "B" => wm.omap_b(&buffer, params.cursor, params.count, word).map(bare),
+            other => return Err(format!("unsupported motion: `{}`", other)),
+        },
+        ("o", operator) => {
+            return Err(format!(
+                "mode `o` requires `operator` to be `c`, `d`, or `y`, got {:?}",
+                operator
+            ))
+        }
+        (other, _) => return Err(format!("unsupported mode: `{}`", other)),
+    }
+    .map_err(|err| err.to_string())?;
+
+    let is_operator_pending = params.mode == "o";
+    Ok(MotionResult {
+        cursor: output.new_cursor_pos,
+        inclusive: is_operator_pending.then(|| is_inclusive(&params.motion)),
+        span: is_operator_pending.then(|| span(params.cursor, output.new_cursor_pos)),
+        d_special: output.d_special,
+        prevent_change: output.prevent_change,
+    })
+}
@@ -0,0 +1,173 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+//! Editor-agnostic JSON-RPC server front end for
+//! [`jieba_vim_rs_core::motion::WordMotion`].
+//!
+//! Unlike `jieba_vim_rs_cli`, which runs one motion per process invocation,
+//! this binary stays resident and answers requests over stdin/stdout so
+//! editors that can spawn a long-lived subprocess (Helix, VS Code, Kakoune,
+//! ...) pay the jieba dictionary load cost once and then reuse jieba.vim's
+//! segmentation-aware `w`/`b`/`e`/`W`/`B`/`E` motions for the life of the
+//! editor session.
+//!
+//! Requests and responses are JSON-RPC 2.0 messages, one per line on stdin
+//! and stdout respectively (no `Content-Length` framing -- every message is
+//! small enough that a client can just read a line). The only method
+//! currently implemented is `motion`; see `protocol::MotionParams` and
+//! `protocol::MotionResult` for its shape.
+//!
+//! Run `jieba-vim-motion-server --help` for the full flag list.
+
+mod buffer;
+mod dispatch;
+mod protocol;
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::Parser;
+use jieba_rs::Jieba;
+use jieba_vim_rs_core::motion::WordMotion;
+use serde_json::Value;
+
+use dispatch::JiebaWrapper;
+use protocol::{error_code, MotionParams, Request, Response};
+
+/// Stay resident and answer jieba.vim motion queries as JSON-RPC over
+/// stdin/stdout.
+#[derive(Debug, Parser)]
+#[command(name = "jieba-vim-motion-server", version)]
+struct Cli {
+    /// Custom jieba dictionary path. Defaults to jieba's bundled dictionary.
+    #[arg(long)]
+    dict: Option<PathBuf>,
+
+    /// Disable jieba's HMM-based new-word discovery, so segmentation of
+    /// Hanzi runs not covered by the dictionary is strictly dictionary-driven.
+    #[arg(long)]
+    no_hmm: bool,
+
+    /// Load `--dict` through a read-only mmap instead of reading it into
+    /// memory up front, so the OS pages the file in lazily. Ignored if
+    /// `--dict` is not given; falls back to the regular load path if the
+    /// platform can't mmap the file.
+    #[arg(long)]
+    mmap_dict: bool,
+}
+
+/// Load `path` through a read-only `mmap`, letting the OS page the
+/// (potentially large) dictionary file in lazily instead of `read`ing it
+/// into an owned buffer up front. Falls back to the regular
+/// [`BufReader`]-backed path on any `mmap` failure (e.g. the platform
+/// doesn't support it, or `path` lives on a filesystem that rejects it),
+/// since the parsed `Jieba` is identical either way -- only how the bytes
+/// reached `Jieba::with_dict` differs.
+fn load_jieba_mmapped(path: &Path) -> Result<Jieba, String> {
+    let file = fs::File::open(path).map_err(|err| format!("cannot open dict: {}", err))?;
+    // Safe as long as nothing else truncates or writes to `path` while the
+    // mapping is alive, which holds for a dictionary file held for the life
+    // of this long-running server process.
+    match unsafe { memmap2::Mmap::map(&file) } {
+        Ok(mmap) => {
+            let mut reader = io::Cursor::new(&mmap[..]);
+            Jieba::with_dict(&mut reader).map_err(|err| format!("jieba error: {}", err))
+        }
+        Err(_) => load_jieba_buffered(path),
+    }
+}
+
+fn load_jieba_buffered(path: &Path) -> Result<Jieba, String> {
+    let mut reader =
+        BufReader::new(fs::File::open(path).map_err(|err| format!("cannot open dict: {}", err))?);
+    Jieba::with_dict(&mut reader).map_err(|err| format!("jieba error: {}", err))
+}
+
+fn load_jieba(dict: Option<&Path>, mmap_dict: bool) -> Result<Jieba, String> {
+    match dict {
+        None => Ok(Jieba::new()),
+        Some(path) if mmap_dict => load_jieba_mmapped(path),
+        Some(path) => load_jieba_buffered(path),
+    }
+}
+
+/// Handle one already-parsed request, returning the response to write back.
+fn handle(wm: &WordMotion<JiebaWrapper>, request: Request) -> Response {
+    match request.method.as_str() {
+        "motion" => match serde_json::from_value::<MotionParams>(request.params) {
+            Ok(params) => match dispatch::run(wm, &params) {
+                Ok(result) => Response::ok(request.id, serde_json::to_value(result).unwrap()),
+                Err(err) => Response::err(request.id, error_code::INTERNAL_ERROR, err),
+            },
+            Err(err) => Response::err(
+                request.id,
+                error_code::INVALID_PARAMS,
+                format!("invalid params: {}", err),
+            ),
+        },
+        other => Response::err(
+            request.id,
+            error_code::METHOD_NOT_FOUND,
+            format!("unknown method: `{}`", other),
+        ),
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let jieba = match load_jieba(cli.dict.as_deref(), cli.mmap_dict) {
+        Ok(jieba) => jieba,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let wm = WordMotion::new(JiebaWrapper {
+        jieba,
+        hmm: !cli.no_hmm,
+    });
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(err) => {
+                eprintln!("error: cannot read stdin: {}", err);
+                return ExitCode::FAILURE;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => handle(&wm, request),
+            Err(err) => Response::err(
+                Value::Null,
+                error_code::PARSE_ERROR,
+                format!("invalid JSON-RPC request: {}", err),
+            ),
+        };
+
+        let encoded = serde_json::to_string(&response).unwrap();
+        if writeln!(stdout, "{}", encoded).and_then(|_| stdout.flush()).is_err() {
+            break;
+        }
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,126 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use serde::{Deserialize, Serialize};
+
+/// A single JSON-RPC 2.0 request, framed one per line on stdin (no
+/// `Content-Length` header the way full LSP is -- every message here is
+/// small and self-contained, so newline-delimited JSON is enough).
+#[derive(Deserialize)]
+pub struct Request {
+    #[allow(dead_code)]
+    pub jsonrpc: String,
+    pub id: serde_json::Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+#[derive(Serialize)]
+pub struct Response {
+    pub jsonrpc: &'static str,
+    pub id: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl Response {
+    pub fn ok(id: serde_json::Value, result: serde_json::Value) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    pub fn err(id: serde_json::Value, code: i64, message: impl Into<String>) -> Self {
+        Self {
+            jsonrpc: "2.0",
+            id,
+            result: None,
+            error: Some(RpcError {
+                code,
+                message: message.into(),
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+}
+
+/// Standard JSON-RPC error codes this server uses.
+pub mod error_code {
+    pub const PARSE_ERROR: i64 = -32700;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INTERNAL_ERROR: i64 = -32603;
+}
+
+/// Params for the `motion` method.
+#[derive(Deserialize)]
+pub struct MotionParams {
+    /// The buffer to move over, one string per line.
+    pub buffer: Vec<String>,
+    /// `(lnum, col)`, 1-indexed line and 0-indexed column, mirroring every
+    /// other cursor tuple in this crate family.
+    pub cursor: (usize, usize),
+    /// Number of times to repeat the motion. Defaults to 1.
+    #[serde(default = "one")]
+    pub count: u64,
+    /// `w`/`W`/`e`/`E`/`b`/`B`.
+    pub motion: String,
+    /// `n` (normal), `o` (operator-pending), or `x` (visual).
+    pub mode: String,
+    /// Required when `mode` is `o`: `c`, `d`, or `y`.
+    pub operator: Option<String>,
+}
+
+fn one() -> u64 {
+    1
+}
+
+/// A half-open, line/col span the client should apply its own operator to.
+/// `start`/`end` are already ordered regardless of whether the motion moved
+/// forward or backward.
+#[derive(Serialize)]
+pub struct Span {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+/// Result of the `motion` method.
+#[derive(Serialize)]
+pub struct MotionResult {
+    pub cursor: (usize, usize),
+    /// Present only for `mode: "o"`: whether `span` includes its end
+    /// position (see `:help exclusive` / `:help inclusive`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inclusive: Option<bool>,
+    /// Present only for `mode: "o"`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+    /// See `:help d-special` -- whether this motion's operator-pending
+    /// behavior gets the linewise-delete special case.
+    pub d_special: bool,
+    /// Whether the operator should be silently aborted (`:help cw`'s
+    /// "trailing white space" case and friends) rather than applied.
+    pub prevent_change: bool,
+}
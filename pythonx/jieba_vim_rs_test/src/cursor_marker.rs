@@ -12,10 +12,14 @@
 // License for the specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt;
 
 /// `{` represents the cursor before a motion. `}` represents the cursor after
-/// a motion.
+/// a motion. `\{`/`\}` escape to a literal brace, and `{+name}` anchors a
+/// named mark instead (the `+` keeps it unambiguous with two bare markers
+/// sitting next to each other, e.g. `{{`) -- see [`lexer`]/[`ast`] for how a
+/// `vcase` buffer is turned into these.
 pub struct CursorMarker;
 
 /// The error that may be raised by [`CursorMarker`].
@@ -25,6 +29,8 @@ pub enum Error {
     MoreThanOne(char),
     /// If the cursor marker enclosed is not found.
     Missing(char),
+    /// If the same `{+name}` mark appears more than once.
+    DuplicateNamedMark(String),
 }
 
 impl fmt::Debug for Error {
@@ -34,13 +40,16 @@ impl fmt::Debug for Error {
                 write!(f, "More than one marker `{}` is found", marker)
             }
             Self::Missing(marker) => write!(f, "Missing marker `{}`", marker),
+            Self::DuplicateNamedMark(name) => {
+                write!(f, "Named mark `{{{}}}` is used more than once", name)
+            }
         }
     }
 }
 
 /// The position (lnum, col) of a cursor. `lnum` is 1-indexed while `col` is
 /// 0-indexed.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CursorPosition {
     pub lnum: usize,
     pub col: usize,
@@ -52,89 +61,206 @@ pub struct StripMarkerOutput {
     pub before_cursor_position: CursorPosition,
     pub after_cursor_position: CursorPosition,
     pub striped_lines: Vec<String>,
+    /// Any `{+name}` marks found, keyed by `name`. Empty for every existing
+    /// `vcase` buffer, since none of them use named marks yet -- this is the
+    /// anchor a future block-visual (`xb`) case would use for a rectangular
+    /// selection's second corner instead of overloading `{`/`}`.
+    pub named_marks: HashMap<String, CursorPosition>,
 }
 
 // We assume that each cursor marker is ASCII, and consumes exactly one byte.
 const CURSOR_BEFORE_CHAR: char = '{';
 const CURSOR_AFTER_CHAR: char = '}';
 
-impl CursorMarker {
-    fn marker_predicate(&self, c: char) -> bool {
-        match c {
-            CURSOR_BEFORE_CHAR | CURSOR_AFTER_CHAR => true,
-            _ => false,
-        }
+/// Scans one buffer line into a flat token stream, resolving `\{`/`\}`
+/// escapes as it goes. [`ast`] is what actually understands what the tokens
+/// mean (which one is "before", which is "after", that a name can't repeat,
+/// ...); this stage only knows about braces and text.
+mod lexer {
+    /// One lexical token from a `vcase` buffer line. `byte_offset` is the
+    /// offset of the marker within the line *after* escapes are resolved,
+    /// i.e. the offset it will end up at in [`super::StripMarkerOutput`]'s
+    /// stripped line -- callers never need to re-derive it.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum Token {
+        /// A run of literal text, escapes already resolved.
+        Literal(String),
+        /// Bare `{`: the "before motion" cursor marker.
+        MarkerOpen { byte_offset: usize },
+        /// Bare `}`: the "after motion" cursor marker.
+        MarkerClose { byte_offset: usize },
+        /// `{+name}`, for any non-empty `name`: an anchor a caller can look
+        /// up by name instead of position.
+        NamedMark { name: String, byte_offset: usize },
     }
 
-    fn strip_marker_str(
-        &self,
-        s: &mut String,
-    ) -> Result<(Option<usize>, Option<usize>), Error> {
-        let mut before_cursor_col = None;
-        let mut after_cursor_col = None;
-        for _ in 0..2 {
-            if let Some(i) = s.find(|c| self.marker_predicate(c)) {
-                let c = s.drain(i..i + 1).next().unwrap();
-                if c == CURSOR_BEFORE_CHAR {
-                    if before_cursor_col.is_some() {
-                        return Err(Error::MoreThanOne(CURSOR_BEFORE_CHAR));
-                    }
-                    before_cursor_col.get_or_insert(i);
-                } else {
-                    if after_cursor_col.is_some() {
-                        return Err(Error::MoreThanOne(CURSOR_AFTER_CHAR));
+    /// Lex `line`. `{+` followed by non-empty, brace-free text and a closing
+    /// `}` is a [`Token::NamedMark`]; every other `{` or `}` is a bare
+    /// [`Token::MarkerOpen`]/[`Token::MarkerClose`] (so two bare markers
+    /// sitting next to each other, e.g. `{{`, still lex as two markers, not
+    /// as one mistaken attempt at a name). `\{` and `\}` always resolve to a
+    /// literal brace in [`Token::Literal`], never to a marker.
+    pub fn lex(line: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut out_offset = 0;
+        let mut chars = line.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' if matches!(chars.peek(), Some((_, '{' | '}'))) => {
+                    let (_, escaped) = chars.next().unwrap();
+                    literal.push(escaped);
+                    out_offset += escaped.len_utf8();
+                }
+                '{' => {
+                    if let Some(name) = peek_named_mark(line, i) {
+                        flush_literal(&mut tokens, &mut literal);
+                        tokens.push(Token::NamedMark {
+                            byte_offset: out_offset,
+                            name: name.to_string(),
+                        });
+                        // Skip past the `+`, the name, and the closing `}`
+                        // we already confirmed is there.
+                        chars.next();
+                        for _ in 0..name.chars().count() {
+                            chars.next();
+                        }
+                        chars.next();
+                        continue;
                     }
-                    after_cursor_col.get_or_insert(i);
+                    flush_literal(&mut tokens, &mut literal);
+                    tokens.push(Token::MarkerOpen {
+                        byte_offset: out_offset,
+                    });
+                }
+                '}' => {
+                    flush_literal(&mut tokens, &mut literal);
+                    tokens.push(Token::MarkerClose {
+                        byte_offset: out_offset,
+                    });
+                }
+                other => {
+                    literal.push(other);
+                    out_offset += other.len_utf8();
                 }
             }
         }
-        if let Some(i) = s.find(|c| self.marker_predicate(c)) {
-            let c = s.drain(i..i + 1).next().unwrap();
-            if c == CURSOR_BEFORE_CHAR {
-                return Err(Error::MoreThanOne(CURSOR_BEFORE_CHAR));
-            } else {
-                return Err(Error::MoreThanOne(CURSOR_AFTER_CHAR));
+        flush_literal(&mut tokens, &mut literal);
+        tokens
+    }
+
+    fn flush_literal(tokens: &mut Vec<Token>, literal: &mut String) {
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(std::mem::take(literal)));
+        }
+    }
+
+    /// If `line[open_idx..]` starts a `{+name}` with a non-empty, brace-free
+    /// `name`, return it.
+    fn peek_named_mark(line: &str, open_idx: usize) -> Option<&str> {
+        let rest = line[open_idx + 1..].strip_prefix('+')?;
+        let end = rest.find(|c| c == '{' || c == '}')?;
+        if end == 0 || rest.as_bytes()[end] != b'}' {
+            return None;
+        }
+        Some(&rest[..end])
+    }
+}
+
+/// Resolves a line's [`lexer::Token`]s into cursor anchors, and validates
+/// that the marks they describe are well-formed (at most one `{`, at most
+/// one `}`, no repeated `{name}`). Keeps the stripped text alongside the
+/// anchors since it's produced by the same walk over the tokens.
+mod ast {
+    use std::collections::HashMap;
+
+    use super::lexer::{lex, Token};
+    use super::{CursorPosition, Error, CURSOR_AFTER_CHAR, CURSOR_BEFORE_CHAR};
+
+    pub struct Resolved {
+        pub before_cursor_position: Option<CursorPosition>,
+        pub after_cursor_position: Option<CursorPosition>,
+        pub named_marks: HashMap<String, CursorPosition>,
+        pub striped_lines: Vec<String>,
+    }
+
+    pub fn resolve<L: IntoIterator<Item = String>>(
+        lines: L,
+    ) -> Result<Resolved, Error> {
+        let mut before_cursor_position = None;
+        let mut after_cursor_position = None;
+        let mut named_marks = HashMap::new();
+        let mut striped_lines = Vec::new();
+
+        for (lnum, line) in lines.into_iter().enumerate() {
+            let lnum = lnum + 1;
+            let mut striped_line = String::new();
+            for token in lex(&line) {
+                match token {
+                    Token::Literal(text) => striped_line.push_str(&text),
+                    Token::MarkerOpen { byte_offset } => {
+                        if before_cursor_position.is_some() {
+                            return Err(Error::MoreThanOne(CURSOR_BEFORE_CHAR));
+                        }
+                        before_cursor_position = Some(CursorPosition {
+                            lnum,
+                            col: byte_offset,
+                        });
+                    }
+                    Token::MarkerClose { byte_offset } => {
+                        if after_cursor_position.is_some() {
+                            return Err(Error::MoreThanOne(CURSOR_AFTER_CHAR));
+                        }
+                        after_cursor_position = Some(CursorPosition {
+                            lnum,
+                            col: byte_offset,
+                        });
+                    }
+                    Token::NamedMark { name, byte_offset } => {
+                        if named_marks.contains_key(&name) {
+                            return Err(Error::DuplicateNamedMark(name));
+                        }
+                        named_marks.insert(
+                            name,
+                            CursorPosition {
+                                lnum,
+                                col: byte_offset,
+                            },
+                        );
+                    }
+                }
             }
+            striped_lines.push(striped_line);
         }
-        Ok((before_cursor_col, after_cursor_col))
+
+        Ok(Resolved {
+            before_cursor_position,
+            after_cursor_position,
+            named_marks,
+            striped_lines,
+        })
     }
+}
 
+impl CursorMarker {
     /// Strip the markers off `lines`, and return the cursor positions
-    /// `(lnum, col)` before and after the underlying motion. Panics if the
-    /// markers are not found or duplicate markers are detected.
+    /// `(lnum, col)` before and after the underlying motion. Errors if a
+    /// required marker is missing or a marker is duplicated.
     pub fn strip_markers<L: IntoIterator<Item = String>>(
         &self,
         lines: L,
     ) -> Result<StripMarkerOutput, Error> {
-        let mut lines: Vec<_> = lines.into_iter().collect();
-        let mut before_position = None;
-        let mut after_position = None;
-        for (lnum, line) in lines.iter_mut().enumerate() {
-            let lnum = lnum + 1;
-            let (before_col, after_col) = self.strip_marker_str(line)?;
-            if let Some(i) = before_col {
-                if before_position.is_some() {
-                    return Err(Error::MoreThanOne(CURSOR_BEFORE_CHAR));
-                }
-                before_position.get_or_insert(CursorPosition { lnum, col: i });
-            }
-            if let Some(j) = after_col {
-                if after_position.is_some() {
-                    return Err(Error::MoreThanOne(CURSOR_AFTER_CHAR));
-                }
-                after_position.get_or_insert(CursorPosition { lnum, col: j });
-            }
-        }
-        if before_position.is_none() {
-            return Err(Error::Missing(CURSOR_BEFORE_CHAR));
-        }
-        if after_position.is_none() {
-            return Err(Error::Missing(CURSOR_AFTER_CHAR));
-        }
+        let resolved = ast::resolve(lines)?;
         Ok(StripMarkerOutput {
-            before_cursor_position: before_position.unwrap(),
-            after_cursor_position: after_position.unwrap(),
-            striped_lines: lines,
+            before_cursor_position: resolved
+                .before_cursor_position
+                .ok_or(Error::Missing(CURSOR_BEFORE_CHAR))?,
+            after_cursor_position: resolved
+                .after_cursor_position
+                .ok_or(Error::Missing(CURSOR_AFTER_CHAR))?,
+            striped_lines: resolved.striped_lines,
+            named_marks: resolved.named_marks,
         })
     }
 }
@@ -240,4 +366,43 @@ mod tests {
         let err = cm.strip_markers(lines).unwrap_err();
         assert_eq!(err, Error::MoreThanOne(CURSOR_BEFORE_CHAR));
     }
+
+    #[test]
+    fn test_cursor_marker_strip_markers_escapes_braces() {
+        let cm = CursorMarker;
+
+        let lines = into_vec_string([r"fo\{o {bar", r"hel\}lo}"]);
+        let o = cm.strip_markers(lines).unwrap();
+        assert_eq!(o.before_cursor_position, (1, 5));
+        assert_eq!(o.after_cursor_position, (2, 6));
+        assert_eq!(o.striped_lines, vec!["fo{o bar", "hel}lo"]);
+    }
+
+    #[test]
+    fn test_cursor_marker_strip_markers_named_marks() {
+        let cm = CursorMarker;
+
+        let lines = into_vec_string(["{+tl}foo{+br} bar", "baz"]);
+        let o = cm.strip_markers(lines).unwrap_err();
+        // `{+tl}`/`{+br}` are named marks, not the required `{`/`}` pair, so
+        // the unconditional before/after markers are still missing.
+        assert_eq!(o, Error::Missing(CURSOR_BEFORE_CHAR));
+
+        let lines = into_vec_string(["{foo{+tl}bar}"]);
+        let o = cm.strip_markers(lines).unwrap();
+        assert_eq!(o.before_cursor_position, (1, 0));
+        assert_eq!(o.after_cursor_position, (1, 6));
+        assert_eq!(o.striped_lines, vec!["foobar"]);
+        assert_eq!(o.named_marks["tl"], (1, 3));
+
+        // Two bare markers sitting next to each other must still behave
+        // exactly as before -- a named mark never shadows that case.
+        let lines = into_vec_string(["a{{b}}c"]);
+        let err = cm.strip_markers(lines).unwrap_err();
+        assert_eq!(err, Error::MoreThanOne(CURSOR_BEFORE_CHAR));
+
+        let lines = into_vec_string(["{a{+dup}b}{+dup}"]);
+        let err = cm.strip_markers(lines).unwrap_err();
+        assert_eq!(err, Error::DuplicateNamedMark("dup".to_string()));
+    }
 }
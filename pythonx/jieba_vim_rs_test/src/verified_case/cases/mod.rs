@@ -17,19 +17,12 @@ mod nmap_b;
 mod nmap_e;
 mod nmap_ge;
 mod nmap_w;
+mod omap;
 mod omap_c_b;
-mod omap_c_e;
-mod omap_c_ge;
 mod omap_c_w;
 mod omap_d_b;
-mod omap_d_e;
-mod omap_d_ge;
-mod omap_d_w;
-mod omap_y_b;
-mod omap_y_e;
-mod omap_y_ge;
 mod omap_y_w;
-mod utils;
+pub mod utils;
 mod xmap_b;
 mod xmap_e;
 mod xmap_ge;
@@ -41,17 +34,10 @@ pub use nmap_b::NmapBCase;
 pub use nmap_e::NmapECase;
 pub use nmap_ge::NmapGeCase;
 pub use nmap_w::NmapWCase;
+pub use omap::{OmapCase, OmapMotion, OmapOperator};
 pub use omap_c_b::OmapCBCase;
-pub use omap_c_e::OmapCECase;
-pub use omap_c_ge::OmapCGeCase;
 pub use omap_c_w::OmapCWCase;
 pub use omap_d_b::OmapDBCase;
-pub use omap_d_e::OmapDECase;
-pub use omap_d_ge::OmapDGeCase;
-pub use omap_d_w::OmapDWCase;
-pub use omap_y_b::OmapYBCase;
-pub use omap_y_e::OmapYECase;
-pub use omap_y_ge::OmapYGeCase;
 pub use omap_y_w::OmapYWCase;
 pub use xmap_b::XmapBCase;
 pub use xmap_e::XmapECase;
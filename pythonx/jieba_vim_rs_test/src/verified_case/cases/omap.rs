@@ -0,0 +1,258 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::super::Count;
+use super::{utils, MotionOutput, VerifiableCase, TEMPLATES};
+use crate::cursor_marker::{self, CursorMarker};
+
+/// Which operator an [`OmapCase`] pairs its motion with, selecting the
+/// `execute_omap_*` template that renders the `Execute:`/`Then:` blocks.
+/// `e`/`E`/`ge`/`gE`'s inclusive/exclusive and end-of-line special-casing
+/// differ between `c` and `d` (that's what `d_special` encodes, and it's
+/// only ever meaningful for [`OmapOperator::Delete`] -- see
+/// [`OmapCase::new`]), and `y` additionally leaves the cursor at the start
+/// of the yanked region instead of where `d`/`c` would land, and can assert
+/// the yanked register's contents via [`OmapCase::expected_register`].
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum OmapOperator {
+    Delete,
+    Change,
+    Yank,
+}
+
+impl OmapOperator {
+    fn letter(self) -> &'static str {
+        match self {
+            OmapOperator::Delete => "d",
+            OmapOperator::Change => "c",
+            OmapOperator::Yank => "y",
+        }
+    }
+
+    fn template(self) -> &'static str {
+        match self {
+            OmapOperator::Delete => "execute_omap_d",
+            OmapOperator::Change => "execute_omap_c",
+            OmapOperator::Yank => "execute_omap_y",
+        }
+    }
+}
+
+/// Which motion an [`OmapCase`] exercises. Supplies the `word`/`WORD` motion
+/// letters and the `o_v`/`d_special`/`prevent_change` behavior that used to
+/// be hard-coded per motion in the one-struct-per-motion siblings this type
+/// replaces (e.g. only `e`/`ge` ever meant anything by `d_special`, only
+/// `ge`/`b` by `prevent_change`). [`OmapCase::new`] masks `d_special`/
+/// `prevent_change` to `false` wherever the motion doesn't support them, so
+/// callers can pass whatever their own case input carries without checking
+/// which motion it's for.
+#[derive(PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub enum OmapMotion {
+    E,
+    Ge,
+    W,
+    B,
+}
+
+impl OmapMotion {
+    fn letters(self, word: bool) -> &'static str {
+        match (self, word) {
+            (OmapMotion::E, true) => "e",
+            (OmapMotion::E, false) => "E",
+            (OmapMotion::Ge, true) => "ge",
+            (OmapMotion::Ge, false) => "gE",
+            (OmapMotion::W, true) => "w",
+            (OmapMotion::W, false) => "W",
+            (OmapMotion::B, true) => "b",
+            (OmapMotion::B, false) => "B",
+        }
+    }
+
+    fn o_v(self) -> bool {
+        matches!(self, OmapMotion::E | OmapMotion::Ge)
+    }
+
+    fn has_d_special(self) -> bool {
+        matches!(self, OmapMotion::E | OmapMotion::Ge)
+    }
+
+    fn has_prevent_change(self) -> bool {
+        matches!(self, OmapMotion::Ge | OmapMotion::B)
+    }
+}
+
+/// A single operator-pending-mode motion case: `{operator}{count}{motion}`,
+/// e.g. `d2w` or `yge`. Replaces the former `OmapDECase`/`OmapDGeCase`/
+/// `OmapDWCase`/`OmapYBCase`/`OmapYECase` family, which differed only in
+/// which operator/motion they hard-coded and which of `d_special`/
+/// `prevent_change` they bothered to expose.
+#[derive(PartialEq, Clone, Serialize, Deserialize)]
+pub struct OmapCase {
+    pub lnum_before: usize,
+    pub col_before: usize,
+    pub lnum_after: usize,
+    pub col_after: usize,
+    pub buffer: Vec<String>,
+    pub count: Count,
+    pub word: bool,
+    pub operator: OmapOperator,
+    pub motion: OmapMotion,
+    pub d_special: bool,
+    pub prevent_change: bool,
+    /// Expected contents of the unnamed register after the motion runs,
+    /// asserted by the `execute_omap_y` template. Only meaningful (and only
+    /// ever set) for [`OmapOperator::Yank`]; `None` skips the assertion.
+    pub expected_register: Option<String>,
+}
+
+impl OmapCase {
+    /// Create a new case. `count` equals 0 means 1 but without explicit
+    /// count. `d_special`/`prevent_change` are silently masked to `false`
+    /// when `motion`/`operator` don't support them (see [`OmapMotion`]);
+    /// `expected_register` is silently masked to `None` outside
+    /// [`OmapOperator::Yank`].
+    pub fn new<C: Into<Count>>(
+        marked_buffer: Vec<String>,
+        count: C,
+        word: bool,
+        operator: OmapOperator,
+        motion: OmapMotion,
+        d_special: bool,
+        prevent_change: bool,
+        expected_register: Option<String>,
+    ) -> Result<Self, cursor_marker::Error> {
+        let output = CursorMarker.strip_markers(marked_buffer)?;
+        Ok(Self {
+            lnum_before: output.before_cursor_position.lnum,
+            col_before: output.before_cursor_position.col,
+            lnum_after: output.after_cursor_position.lnum,
+            col_after: output.after_cursor_position.col,
+            buffer: output.stripped_buffer,
+            count: count.into(),
+            word,
+            operator,
+            motion,
+            d_special: d_special
+                && motion.has_d_special()
+                && operator == OmapOperator::Delete,
+            prevent_change: prevent_change && motion.has_prevent_change(),
+            expected_register: expected_register
+                .filter(|_| operator == OmapOperator::Yank),
+        })
+    }
+
+    fn motion_str(&self) -> &'static str {
+        self.motion.letters(self.word)
+    }
+}
+
+impl VerifiableCase for OmapCase {
+    fn to_vader(&self, path: &Path) {
+        let mut writer = BufWriter::new(File::create(path).unwrap());
+        let buffer = &self.buffer;
+        let lnum_before = self.lnum_before;
+        let col_before = utils::to_vim_col(&buffer[lnum_before - 1], self.col_before);
+        let lnum_after = self.lnum_after;
+        let col_after = utils::to_vim_col(&buffer[lnum_after - 1], self.col_after);
+        let count = self.count.to_string();
+        let motion = self.motion_str();
+        let d_special = self.d_special;
+        let prevent_change = self.prevent_change;
+        let nvim = env::var("VIM_BIN_NAME")
+            .map(|s| s == "nvim")
+            .unwrap_or(false);
+        let assert_register = self.expected_register.is_some();
+        let expected_register = self.expected_register.clone().unwrap_or_default();
+
+        let ctx = minijinja::context!(buffer);
+        TEMPLATES
+            .get_template("setup_omap")
+            .unwrap()
+            .render_to_write(ctx, &mut writer)
+            .unwrap();
+        let ctx = minijinja::context!(
+            lnum_before,
+            col_before,
+            lnum_after,
+            col_after,
+            count,
+            motion,
+            o_v => self.motion.o_v(),
+            d_special,
+            prevent_change,
+            nvim,
+            assert_register,
+            expected_register,
+        );
+        TEMPLATES
+            .get_template(self.operator.template())
+            .unwrap()
+            .render_to_write(ctx, &mut writer)
+            .unwrap();
+    }
+}
+
+impl fmt::Display for OmapCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        out.push('\n');
+        out.push_str(&utils::render_motion_snippet(
+            &self.buffer,
+            (self.lnum_before, self.col_before),
+            (self.lnum_after, self.col_after),
+        ));
+        out.push_str(&format!(
+            "\nMotion: {}{}{}\n",
+            self.operator.letter(),
+            self.count.to_string(),
+            self.motion_str(),
+        ));
+        if self.motion.has_d_special() {
+            if self.d_special {
+                out.push_str("\nd-special on\n");
+            } else {
+                out.push_str("\nd-special off\n");
+            }
+        }
+        if self.motion.has_prevent_change() {
+            if self.prevent_change {
+                out.push_str("\nprevent-change on\n");
+            } else {
+                out.push_str("\nprevent-change off\n");
+            }
+        }
+        if let Some(register) = &self.expected_register {
+            out.push_str(&format!("\nExpected register: {:?}\n", register));
+        }
+        write!(f, "{}", out)
+    }
+}
+
+impl Into<MotionOutput> for OmapCase {
+    fn into(self) -> MotionOutput {
+        MotionOutput {
+            new_cursor_pos: (self.lnum_after, self.col_after),
+            d_special: self.d_special,
+            prevent_change: self.prevent_change,
+        }
+    }
+}
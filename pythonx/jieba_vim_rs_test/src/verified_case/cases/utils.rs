@@ -12,17 +12,396 @@
 // License for the specific language governing permissions and limitations
 // under the License.
 
-/// Replace space with '·', and append '␊' as newline.
-pub fn display_buffer(buffer: &[String]) -> String {
+use std::fmt;
+
+use similar::{ChangeTag, TextDiff};
+
+/// Glyphs [`display_buffer`] substitutes for whitespace and control
+/// characters, so motion-boundary bugs around tabs/NBSP/trailing whitespace
+/// -- which jieba treats as segmentation whitespace just like a regular
+/// space -- show up in rendered test output instead of looking identical to
+/// their surroundings.
+pub struct DisplayOptions {
+    /// Substituted for U+0020 space. Defaults to '·'.
+    pub space: char,
+    /// Substituted for U+0009 tab, repeated (expanding to the next multiple
+    /// of `tabstop`). Defaults to '→'.
+    pub tab: char,
+    /// Tab stop width in cells. Defaults to 8.
+    pub tabstop: usize,
+    /// Substituted for U+00A0 non-breaking space. Defaults to '␣'.
+    pub nbsp: char,
+    /// If set, inserted right before any run of trailing whitespace at the
+    /// end of a line, marking where it starts so it stands out from
+    /// whitespace in the middle of the line. Defaults to `None`.
+    pub trailing_whitespace_marker: Option<char>,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            space: '·',
+            tab: '→',
+            tabstop: 8,
+            nbsp: '␣',
+            trailing_whitespace_marker: None,
+        }
+    }
+}
+
+/// Render `buffer` with whitespace and control characters substituted per
+/// `opts`, and '␊' appended as each line's newline.
+pub fn display_buffer(buffer: &[String], opts: &DisplayOptions) -> String {
     let mut out = String::new();
     for line in buffer {
-        out.push_str(&line.replace(' ', "·"));
+        let total_chars = line.chars().count();
+        let trailing_start =
+            line.trim_end_matches([' ', '\t', '\u{a0}']).chars().count();
+        let mut col = 0;
+        if trailing_start == 0 && total_chars > 0 {
+            if let Some(marker) = opts.trailing_whitespace_marker {
+                out.push(marker);
+            }
+        }
+        for (i, c) in line.chars().enumerate() {
+            match c {
+                ' ' => {
+                    out.push(opts.space);
+                    col += 1;
+                }
+                '\t' => {
+                    let width = opts.tabstop - col % opts.tabstop;
+                    for _ in 0..width {
+                        out.push(opts.tab);
+                    }
+                    col += width;
+                }
+                '\u{a0}' => {
+                    out.push(opts.nbsp);
+                    col += 1;
+                }
+                c => {
+                    out.push(c);
+                    col += 1;
+                }
+            }
+            if i + 1 == trailing_start && trailing_start < total_chars {
+                if let Some(marker) = opts.trailing_whitespace_marker {
+                    out.push(marker);
+                }
+            }
+        }
         out.push('␊');
         out.push('\n');
     }
     out
 }
 
-pub fn to_vim_col(col: usize) -> usize {
-    col + 1
+/// `display_buffer` with [`DisplayOptions::default`], matching this crate's
+/// original space-only rendering.
+pub fn display_buffer_default(buffer: &[String]) -> String {
+    display_buffer(buffer, &DisplayOptions::default())
+}
+
+/// The error that may be raised by [`parse_display_buffer`].
+#[derive(PartialEq, Eq)]
+pub enum DisplayBufferError {
+    /// A line carries more than one `▶` cursor marker.
+    MoreThanOneCursor(usize),
+    /// No line carries a `▶` cursor marker.
+    MissingCursor,
+}
+
+impl fmt::Debug for DisplayBufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MoreThanOneCursor(lnum) => {
+                write!(f, "More than one cursor marker `▶` found on line {}", lnum)
+            }
+            Self::MissingCursor => write!(f, "Missing cursor marker `▶`"),
+        }
+    }
+}
+
+/// Like [`display_buffer`], but also marks `cursor` (1-indexed line,
+/// 0-indexed column) with a `▶` inserted just before the character under the
+/// cursor, so a single fixture string captures both a buffer and a cursor
+/// position. The inverse of [`parse_display_buffer`].
+pub fn display_buffer_with_cursor(
+    buffer: &[String],
+    cursor: (usize, usize),
+) -> String {
+    let (cursor_lnum, cursor_col) = cursor;
+    let mut out = String::new();
+    for (i, line) in buffer.iter().enumerate() {
+        let lnum = i + 1;
+        if lnum == cursor_lnum {
+            for (col, c) in line.chars().enumerate() {
+                if col == cursor_col {
+                    out.push('▶');
+                }
+                out.push(if c == ' ' { '·' } else { c });
+            }
+            if cursor_col >= line.chars().count() {
+                out.push('▶');
+            }
+        } else {
+            out.push_str(&line.replace(' ', "·"));
+        }
+        out.push('␊');
+        out.push('\n');
+    }
+    out
+}
+
+/// The inverse of [`display_buffer_with_cursor`]: parse a buffer annotated
+/// with '·' for space, '␊' for end-of-line, and a single '▶' marking the
+/// cursor, back into the plain buffer and the (1-indexed line, 0-indexed
+/// column) cursor position it encodes.
+pub fn parse_display_buffer(
+    s: &str,
+) -> Result<(Vec<String>, (usize, usize)), DisplayBufferError> {
+    let mut lines = Vec::new();
+    let mut cursor = None;
+    for (i, raw_line) in s.split('\n').filter(|l| !l.is_empty()).enumerate() {
+        let lnum = i + 1;
+        let line = raw_line.strip_suffix('␊').unwrap_or(raw_line);
+        let mut rendered = String::new();
+        let mut col = None;
+        for c in line.chars() {
+            match c {
+                '▶' => {
+                    if col.is_some() {
+                        return Err(DisplayBufferError::MoreThanOneCursor(lnum));
+                    }
+                    col = Some(rendered.chars().count());
+                }
+                '·' => rendered.push(' '),
+                c => rendered.push(c),
+            }
+        }
+        if let Some(col) = col {
+            cursor = Some((lnum, col));
+        }
+        lines.push(rendered);
+    }
+    let cursor = cursor.ok_or(DisplayBufferError::MissingCursor)?;
+    Ok((lines, cursor))
+}
+
+/// Translate a 0-indexed character index into `line` to Vim's 1-indexed
+/// *byte* column, by summing the UTF-8 byte lengths of the `char_idx`
+/// preceding characters. A `char_idx` equal to `line.chars().count()` (the
+/// cursor sitting just past the last character) maps to `line.len() + 1`,
+/// Vim's end-of-line column; an empty `line` maps `char_idx` 0 to column 1.
+pub fn to_vim_col(line: &str, char_idx: usize) -> usize {
+    line.chars().take(char_idx).map(char::len_utf8).sum::<usize>() + 1
+}
+
+/// The inverse of [`to_vim_col`]: translate Vim's 1-indexed byte column
+/// `vim_col` in `line` back to a 0-indexed character index. A `vim_col`
+/// landing inside a multibyte character's bytes snaps back to that
+/// character's own index, same as Vim itself only ever stops on character
+/// boundaries.
+pub fn from_vim_col(line: &str, vim_col: usize) -> usize {
+    let byte_idx = vim_col.saturating_sub(1);
+    if byte_idx >= line.len() {
+        return line.chars().count();
+    }
+    line.char_indices()
+        .take_while(|&(i, _)| i <= byte_idx)
+        .count()
+        - 1
+}
+
+/// The screen-cell width `c` occupies when rendered at zero-indexed virtual
+/// column `col`, given a tab stop every `tabstop` cells: a tab advances to
+/// the next multiple of `tabstop`, a combining mark occupies no cell of its
+/// own, an East-Asian wide/fullwidth codepoint occupies two, and everything
+/// else occupies one.
+pub fn char_display_width(c: char, col: usize, tabstop: usize) -> usize {
+    if c == '\t' {
+        tabstop - col % tabstop
+    } else if is_combining_mark(c) {
+        0
+    } else if is_east_asian_wide(c) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Approximates the Unicode `Mn`/`Mc`/`Me` (combining mark) general
+/// categories by listing the blocks that are, in practice, almost entirely
+/// made of combining marks.
+fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0300..=0x036f // Combining Diacritical Marks
+        | 0x1ab0..=0x1aff // Combining Diacritical Marks Extended
+        | 0x1dc0..=0x1dff // Combining Diacritical Marks Supplement
+        | 0x20d0..=0x20ff // Combining Diacritical Marks for Symbols
+        | 0xfe20..=0xfe2f // Combining Half Marks
+    )
+}
+
+/// Approximates Unicode East Asian Width's Wide (W) and Fullwidth (F)
+/// classes by listing the blocks that account for nearly all double-width
+/// rendering in practice: CJK ideographs and their punctuation, Hiragana/
+/// Katakana, Hangul syllables and jamo, and the fullwidth ASCII forms.
+fn is_east_asian_wide(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1100..=0x115f // Hangul Jamo
+        | 0x2e80..=0x303e // CJK Radicals, Kangxi Radicals, CJK Symbols and Punctuation
+        | 0x3041..=0x33ff // Hiragana, Katakana, Bopomofo, Hangul Compat Jamo, CJK strokes/enclosed
+        | 0x3400..=0x4dbf // CJK Unified Ideographs Extension A
+        | 0x4e00..=0x9fff // CJK Unified Ideographs
+        | 0xa000..=0xa4cf // Yi Syllables and Radicals
+        | 0xac00..=0xd7a3 // Hangul Syllables
+        | 0xf900..=0xfaff // CJK Compatibility Ideographs
+        | 0xfe30..=0xfe4f // CJK Compatibility Forms
+        | 0xff00..=0xff60 // Fullwidth Forms
+        | 0xffe0..=0xffe6 // Fullwidth Signs
+        | 0x20000..=0x3fffd // CJK Unified Ideographs Extension B and beyond, CJK Compatibility Supplement
+    )
+}
+
+/// Translate a 0-indexed character index into `line` to Vim's 1-indexed
+/// virtual column (`virtcol()`), by summing [`char_display_width`] over the
+/// `char_idx` preceding characters with `tabstop`-wide tabs.
+pub fn to_vim_virtcol(line: &str, char_idx: usize, tabstop: usize) -> usize {
+    let mut vcol = 0;
+    for c in line.chars().take(char_idx) {
+        vcol += char_display_width(c, vcol, tabstop);
+    }
+    vcol + 1
+}
+
+/// Insert `marker` at `(lnum, col)` (1-indexed line, 0-indexed column) into
+/// `lines`.
+fn insert_marker(lines: &mut [String], pos: (usize, usize), marker: char) {
+    let (lnum, col) = pos;
+    let line = &mut lines[lnum - 1];
+    let byte_idx = line
+        .char_indices()
+        .nth(col)
+        .map(|(i, _)| i)
+        .unwrap_or(line.len());
+    line.insert(byte_idx, marker);
+}
+
+/// `buffer` with the `{`/`}` cursor markers re-inserted at `before`/`after`,
+/// same convention `vcase` buffers are written with.
+fn mark_buffer(
+    buffer: &[String],
+    before: (usize, usize),
+    after: (usize, usize),
+) -> Vec<String> {
+    let mut lines = buffer.to_vec();
+    // Insert whichever marker has the larger column first, so inserting the
+    // other doesn't shift its byte offset out from under it when both land
+    // on the same line.
+    if before.0 == after.0 && before.1 <= after.1 {
+        insert_marker(&mut lines, after, '}');
+        insert_marker(&mut lines, before, '{');
+    } else {
+        insert_marker(&mut lines, before, '{');
+        insert_marker(&mut lines, after, '}');
+    }
+    lines
+}
+
+/// A line-by-line diff between `expected` and `actual`, with a
+/// character-level diff of each differing line, so the exact span that
+/// changed is visible rather than just which lines differ.
+fn render_buffer_diff(expected: &[String], actual: &[String]) -> String {
+    let mut out = String::new();
+    for (lnum, (e, a)) in expected.iter().zip(actual.iter()).enumerate() {
+        let lnum = lnum + 1;
+        if e == a {
+            out.push_str(&format!("  {}: {}\n", lnum, e.replace(' ', "·")));
+            continue;
+        }
+        out.push_str(&format!("- {}: {}\n", lnum, e.replace(' ', "·")));
+        out.push_str(&format!("+ {}: ", lnum));
+        for change in TextDiff::from_chars(e.as_str(), a.as_str()).iter_all_changes() {
+            let value = change.value().replace(' ', "·");
+            match change.tag() {
+                ChangeTag::Equal => out.push_str(&value),
+                ChangeTag::Delete => {}
+                ChangeTag::Insert => out.push_str(&format!("[{}]", value)),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// The 0-indexed virtual-column offset (see [`to_vim_virtcol`]) a caret
+/// should be printed at to point at `col` in `line`, clamping a `col` at or
+/// past end-of-line to the last character (so the `d_special`/empty-line
+/// cases still land the caret on a glyph instead of one past it) and
+/// treating an empty line as column 0.
+fn caret_offset(line: &str, col: usize) -> usize {
+    let len = line.chars().count();
+    let clamped = if len == 0 { 0 } else { col.min(len - 1) };
+    to_vim_virtcol(line, clamped, 8) - 1
+}
+
+/// Render `buffer` as a line-numbered snippet with caret annotation rows
+/// under `before`/`after` (1-indexed line, 0-indexed column), in the style
+/// of a compiler's annotated source output -- so a failing generated case
+/// shows where the cursor started and where it's expected to land at a
+/// glance, instead of making the reader count columns by hand against a
+/// bare coordinate pair. `before`/`after` landing on the same line share one
+/// annotation row when they also share a column, otherwise each gets its
+/// own row under that line; [`caret_offset`] handles multi-byte display
+/// width and end-of-line clamping.
+pub fn render_motion_snippet(
+    buffer: &[String],
+    before: (usize, usize),
+    after: (usize, usize),
+) -> String {
+    let gutter_width = buffer.len().to_string().len();
+    let mut out = String::new();
+    for (i, line) in buffer.iter().enumerate() {
+        let lnum = i + 1;
+        out.push_str(&format!(
+            "{:>width$} | {}\n",
+            lnum,
+            line,
+            width = gutter_width
+        ));
+        let rows: Vec<(usize, &str)> = match (before.0 == lnum, after.0 == lnum) {
+            (true, true) if before.1 == after.1 => vec![(before.1, "before/after")],
+            (true, true) => vec![(before.1, "before"), (after.1, "after")],
+            (true, false) => vec![(before.1, "before")],
+            (false, true) => vec![(after.1, "after")],
+            (false, false) => vec![],
+        };
+        for (col, label) in rows {
+            out.push_str(&" ".repeat(gutter_width));
+            out.push_str(" | ");
+            out.push_str(&" ".repeat(caret_offset(line, col)));
+            out.push_str(&format!("^ {}\n", label));
+        }
+    }
+    out
+}
+
+/// Render a diff between the cursor position an `omap_b`/`omap_c_b`/`omap_d_b`
+/// case expected an operator to land on and where it actually landed, by
+/// re-marking `buffer` with `{`/`}` at each and diffing the two results.
+/// Meant for a failing case's assertion message, where comparing bare
+/// cursor tuples by eye doesn't show which token boundary was missed.
+pub fn render_cursor_diff(
+    buffer: &[String],
+    before: (usize, usize),
+    expected: (usize, usize),
+    actual: (usize, usize),
+) -> String {
+    let expected_marked = mark_buffer(buffer, before, expected);
+    let actual_marked = mark_buffer(buffer, before, actual);
+    render_buffer_diff(&expected_marked, &actual_marked)
 }
@@ -68,9 +68,9 @@ impl VerifiableCase for XmapGeCase {
         let mut writer = BufWriter::new(File::create(path).unwrap());
         let buffer = &self.buffer;
         let lnum_before = self.lnum_before;
-        let col_before = utils::to_vim_col(self.col_before);
+        let col_before = utils::to_vim_col(&buffer[lnum_before - 1], self.col_before);
         let lnum_after = self.lnum_after;
-        let col_after = utils::to_vim_col(self.col_after);
+        let col_after = utils::to_vim_col(&buffer[lnum_after - 1], self.col_after);
         let count = self.count.to_string();
         let motion = self.motion_str();
         let v = self.visual_kind.visual_prefix();
@@ -101,18 +101,17 @@ impl VerifiableCase for XmapGeCase {
 impl fmt::Display for XmapGeCase {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut out = String::new();
-        out.push_str("\nBuffer:\n");
-        out.push_str(&utils::display_buffer(&self.buffer));
-        out.push_str("\nExpected motion: ");
+        out.push('\n');
+        out.push_str(&utils::render_motion_snippet(
+            &self.buffer,
+            (self.lnum_before, self.col_before),
+            (self.lnum_after, self.col_after),
+        ));
         out.push_str(&format!(
-            "({}, {}) -{}{}{}-> ({}, {})\n",
-            self.lnum_before,
-            self.col_before,
+            "\nMotion: {}{}{}\n",
             self.visual_kind.visual_prefix(),
             self.count.to_string(),
             self.motion_str(),
-            self.lnum_after,
-            self.col_after
         ));
         write!(f, "{}", out)
     }
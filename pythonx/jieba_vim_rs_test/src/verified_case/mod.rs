@@ -1,6 +1,11 @@
 mod base;
 pub mod cases;
+#[cfg(feature = "verifiable_case")]
+pub mod property;
 mod verify;
 
 pub use base::{Count, Mode, Motion, Operator, VisualModeKind};
-pub use verify::verify_cases;
+pub use verify::{
+    assemble_cases, disassemble_cases, render_dot_graph, verify_cases,
+    write_dot_graph, DotTransition,
+};
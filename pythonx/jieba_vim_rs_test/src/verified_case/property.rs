@@ -0,0 +1,562 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+//! A live Vim oracle for property-based differential testing.
+//!
+//! Unlike [`super::cases`], which checks a single hand-written expectation
+//! against Vim once and then trusts a cached `*-verified.json` confirmation,
+//! [`ask`] answers an arbitrary, quickcheck-generated [`OracleQuery`] by
+//! actually asking Vim what the right answer is, every time the query isn't
+//! already in the in-process cache. [`ask_replay`] answers the companion
+//! question for operator-pending motions: given the cursor `WordMotion`
+//! claims `b`/`B` lands on, does replaying the operator from there reproduce
+//! what [`ask`] says Vim's own `b`/`B` does?
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use quickcheck::{Arbitrary, Gen};
+use serde::{Deserialize, Serialize};
+
+/// Which Vim motion key [`OracleQuery`] should run, before `word`/`WORD`
+/// casing is applied. `Ge` maps to the two-key `ge`/`gE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MotionKind {
+    W,
+    E,
+    B,
+    Ge,
+}
+
+impl MotionKind {
+    fn keys(self, word: bool) -> &'static str {
+        match (self, word) {
+            (MotionKind::W, true) => "w",
+            (MotionKind::W, false) => "W",
+            (MotionKind::E, true) => "e",
+            (MotionKind::E, false) => "E",
+            (MotionKind::B, true) => "b",
+            (MotionKind::B, false) => "B",
+            (MotionKind::Ge, true) => "ge",
+            (MotionKind::Ge, false) => "gE",
+        }
+    }
+}
+
+/// A Vim query: apply `operator` (if any), then `count` repetitions of
+/// `motion` (cased by `word`), starting from `cursor` in `buffer`. Doubles
+/// as the cache key in [`ask`], so it must round-trip exactly through the
+/// comparison the property test performs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct OracleQuery {
+    pub buffer: Vec<String>,
+    pub cursor: (usize, usize),
+    pub count: u64,
+    pub word: bool,
+    pub motion: MotionKind,
+    /// `None` for a plain normal-mode motion; `Some('d' | 'c' | 'y')` for an
+    /// operator-pending one.
+    pub operator: Option<char>,
+    /// `None` outside visual mode; `Some('v' | 'V' | '\x16')` to enter
+    /// charwise/linewise/blockwise visual mode before running `motion`.
+    /// Mutually exclusive with `operator` -- a query never sets both.
+    pub visual: Option<char>,
+}
+
+/// Vim's answer: the resulting cursor, and -- when `operator` was set -- the
+/// buffer after the operator ran.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct OracleAnswer {
+    pub cursor: (usize, usize),
+    pub buffer: Vec<String>,
+}
+
+/// Render `ours` vs `vim`'s buffer for a mismatching [`OracleAnswer`] as a
+/// unified line diff, for a quickcheck counterexample message that's
+/// actually readable once shrinking has narrowed the case down to a few
+/// lines -- a raw `Vec<String>` `Debug` dump buries the one differing line
+/// in surrounding context.
+pub fn render_diff(ours: &[String], vim: &[String]) -> String {
+    let ours = ours.join("\n");
+    let vim = vim.join("\n");
+    let diff = similar::TextDiff::from_lines(&ours, &vim);
+    let mut out = String::from("--- ours\n+++ vim\n");
+    for change in diff.iter_all_changes() {
+        let sign = match change.tag() {
+            similar::ChangeTag::Delete => '-',
+            similar::ChangeTag::Insert => '+',
+            similar::ChangeTag::Equal => ' ',
+        };
+        out.push(sign);
+        out.push_str(&change);
+        if !out.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Spawning Vim per query is expensive, so memoize answers for the life of
+/// the process. Shrinking re-asks the same handful of shrunk queries many
+/// times over, which is exactly what this cache is for.
+static CACHE: Lazy<Mutex<HashMap<OracleQuery, OracleAnswer>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Ask real Vim what `query` should produce, reusing a cached answer for an
+/// identical earlier query.
+pub fn ask(query: OracleQuery) -> OracleAnswer {
+    if let Some(answer) = CACHE.lock().unwrap().get(&query) {
+        return answer.clone();
+    }
+    let answer = run_vim(&query);
+    CACHE.lock().unwrap().insert(query, answer.clone());
+    answer
+}
+
+/// Cache key for [`ask_replay`]: a query plus the cursor `WordMotion` claims
+/// as the motion's landing spot.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ReplayQuery {
+    query: OracleQuery,
+    rust_cursor: (usize, usize),
+}
+
+static REPLAY_CACHE: Lazy<Mutex<HashMap<ReplayQuery, OracleAnswer>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Replay `query`'s operator in real Vim, but using `rust_cursor` -- the
+/// landing spot `WordMotion::omap_b` computed -- as the motion's endpoint
+/// instead of letting Vim compute `b`/`B` itself. `query.operator` must be
+/// `Some`. Comparing this answer against [`ask`]'s answer for the same
+/// `query` is how the property test checks that `rust_cursor` is the exact
+/// spot Vim's own operator-pending `b`/`B` would have landed on.
+pub fn ask_replay(query: OracleQuery, rust_cursor: (usize, usize)) -> OracleAnswer {
+    let key = ReplayQuery { query, rust_cursor };
+    if let Some(answer) = REPLAY_CACHE.lock().unwrap().get(&key) {
+        return answer.clone();
+    }
+    let answer = run_vim_replay(&key.query, key.rust_cursor);
+    REPLAY_CACHE.lock().unwrap().insert(key, answer.clone());
+    answer
+}
+
+fn run_vim(query: &OracleQuery) -> OracleAnswer {
+    let basedir: PathBuf = [
+        env::var("CARGO_MANIFEST_DIR").unwrap(),
+        ".property_cases".into(),
+    ]
+    .iter()
+    .collect();
+    fs::create_dir(&basedir).ok();
+
+    let out_path = basedir.join(format!("out-{}.json", std::process::id()));
+    let script_path = basedir.join(format!("script-{}.vim", std::process::id()));
+
+    let motion = query.motion.keys(query.word);
+    let count = if query.count > 1 {
+        query.count.to_string()
+    } else {
+        String::new()
+    };
+    let keys = match (query.visual, query.operator) {
+        (Some(v), _) => format!("{}{}{}", v, count, motion),
+        (None, Some(op)) => format!("{}{}{}", op, count, motion),
+        (None, None) => format!("{}{}", count, motion),
+    };
+    let (lnum, col) = (query.cursor.0, query.cursor.1 + 1);
+
+    let mut script = fs::File::create(&script_path).unwrap();
+    writeln!(script, "set virtualedit=onemore").unwrap();
+    for line in &query.buffer {
+        writeln!(script, "call append(line('$'), {:?})", line).unwrap();
+    }
+    writeln!(script, "1delete").unwrap();
+    writeln!(script, "call cursor({}, {})", lnum, col).unwrap();
+    if query.visual.is_some() {
+        // Leave visual mode explicitly so the `:let` below doesn't have to
+        // guess whether cancelling an active selection changes `col('.')`.
+        writeln!(script, "execute \"normal! {}\\<Esc>\"", keys).unwrap();
+    } else {
+        writeln!(script, "normal! {}", keys).unwrap();
+    }
+    writeln!(
+        script,
+        "let g:result = {{'cursor': [line('.'), col('.')], 'buffer': getline(1, '$')}}"
+    )
+    .unwrap();
+    writeln!(
+        script,
+        "call writefile([json_encode(g:result)], {:?})",
+        out_path.to_str().unwrap()
+    )
+    .unwrap();
+    writeln!(script, "qa!").unwrap();
+
+    let vim_bin = env::var("VIM_BIN_NAME").unwrap_or("vim".into());
+    Command::new(vim_bin)
+        .args(["-N", "-u", "NONE", "-es", "-S", script_path.to_str().unwrap()])
+        .current_dir(&basedir)
+        .output()
+        .unwrap();
+
+    let raw = fs::read_to_string(&out_path).unwrap();
+    let answer: RawAnswer = serde_json::from_str(raw.trim()).unwrap();
+    fs::remove_file(&out_path).ok();
+    fs::remove_file(&script_path).ok();
+    OracleAnswer {
+        cursor: (answer.cursor[0], answer.cursor[1] - 1),
+        buffer: answer.buffer,
+    }
+}
+
+/// Like [`run_vim`], but for an operator query: instead of letting Vim pick
+/// `b`/`B`'s landing spot itself, jump straight to `rust_cursor` via
+/// `VeCursor` (the same trick the hand-written operator cases in
+/// `verified_case::base` use) and apply the operator from there.
+/// `query.operator` must be `Some`.
+fn run_vim_replay(query: &OracleQuery, rust_cursor: (usize, usize)) -> OracleAnswer {
+    let op = query.operator.expect("ask_replay requires an operator query");
+
+    let basedir: PathBuf = [
+        env::var("CARGO_MANIFEST_DIR").unwrap(),
+        ".property_cases".into(),
+    ]
+    .iter()
+    .collect();
+    fs::create_dir(&basedir).ok();
+
+    let out_path = basedir.join(format!("replay-out-{}.json", std::process::id()));
+    let script_path =
+        basedir.join(format!("replay-script-{}.vim", std::process::id()));
+
+    let (lnum, col) = (query.cursor.0, query.cursor.1 + 1);
+    let (rust_lnum, rust_col) = (rust_cursor.0, rust_cursor.1 + 1);
+
+    let mut script = fs::File::create(&script_path).unwrap();
+    writeln!(
+        script,
+        "function! VeCursor(lnum, col)\n  set virtualedit=onemore\n  call cursor(a:lnum, a:col)\nendfunction"
+    )
+    .unwrap();
+    for line in &query.buffer {
+        writeln!(script, "call append(line('$'), {:?})", line).unwrap();
+    }
+    writeln!(script, "1delete").unwrap();
+    writeln!(script, "call cursor({}, {})", lnum, col).unwrap();
+    writeln!(
+        script,
+        "execute \"normal! {}:call VeCursor({}, {})\\<cr>\"",
+        op, rust_lnum, rust_col
+    )
+    .unwrap();
+    writeln!(script, "set virtualedit=").unwrap();
+    writeln!(
+        script,
+        "let g:result = {{'cursor': [line('.'), col('.')], 'buffer': getline(1, '$')}}"
+    )
+    .unwrap();
+    writeln!(
+        script,
+        "call writefile([json_encode(g:result)], {:?})",
+        out_path.to_str().unwrap()
+    )
+    .unwrap();
+    writeln!(script, "qa!").unwrap();
+
+    let vim_bin = env::var("VIM_BIN_NAME").unwrap_or("vim".into());
+    Command::new(vim_bin)
+        .args(["-N", "-u", "NONE", "-es", "-S", script_path.to_str().unwrap()])
+        .current_dir(&basedir)
+        .output()
+        .unwrap();
+
+    let raw = fs::read_to_string(&out_path).unwrap();
+    let answer: RawAnswer = serde_json::from_str(raw.trim()).unwrap();
+    fs::remove_file(&out_path).ok();
+    fs::remove_file(&script_path).ok();
+    OracleAnswer {
+        cursor: (answer.cursor[0], answer.cursor[1] - 1),
+        buffer: answer.buffer,
+    }
+}
+
+#[derive(Deserialize)]
+struct RawAnswer {
+    cursor: [usize; 2],
+    buffer: Vec<String>,
+}
+
+/// Which Vim text object [`TextObjectQuery`] should select: inner (`iw`/
+/// `iW`) or around (`aw`/`aW`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TextObjectKind {
+    Inner,
+    Around,
+}
+
+impl TextObjectKind {
+    fn keys(self, word: bool) -> &'static str {
+        match (self, word) {
+            (TextObjectKind::Inner, true) => "iw",
+            (TextObjectKind::Inner, false) => "iW",
+            (TextObjectKind::Around, true) => "aw",
+            (TextObjectKind::Around, false) => "aW",
+        }
+    }
+}
+
+/// A Vim text-object query: select `count` repetitions of `kind` (cased by
+/// `word`), starting from `cursor` in `buffer`. Doubles as the cache key in
+/// [`ask_text_object`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TextObjectQuery {
+    pub buffer: Vec<String>,
+    pub cursor: (usize, usize),
+    pub count: u64,
+    pub word: bool,
+    pub kind: TextObjectKind,
+}
+
+/// Vim's answer: the inclusive span the text object selected, read back from
+/// the `'<`/`'>` visual marks `gv` leaves after `viw`/`vaw` and friends.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TextObjectAnswer {
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+}
+
+static TEXT_OBJECT_CACHE: Lazy<Mutex<HashMap<TextObjectQuery, TextObjectAnswer>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Ask real Vim what text-object span `query` should produce, reusing a
+/// cached answer for an identical earlier query.
+pub fn ask_text_object(query: TextObjectQuery) -> TextObjectAnswer {
+    if let Some(answer) = TEXT_OBJECT_CACHE.lock().unwrap().get(&query) {
+        return answer.clone();
+    }
+    let answer = run_vim_text_object(&query);
+    TEXT_OBJECT_CACHE.lock().unwrap().insert(query, answer.clone());
+    answer
+}
+
+/// Like [`run_vim`], but enters visual mode, applies the text object, and
+/// reads the resulting selection back from the `'<`/`'>` marks instead of
+/// the cursor position.
+fn run_vim_text_object(query: &TextObjectQuery) -> TextObjectAnswer {
+    let basedir: PathBuf = [
+        env::var("CARGO_MANIFEST_DIR").unwrap(),
+        ".property_cases".into(),
+    ]
+    .iter()
+    .collect();
+    fs::create_dir(&basedir).ok();
+
+    let out_path = basedir.join(format!("tobj-out-{}.json", std::process::id()));
+    let script_path = basedir.join(format!("tobj-script-{}.vim", std::process::id()));
+
+    let motion = query.kind.keys(query.word);
+    let count = if query.count > 1 {
+        query.count.to_string()
+    } else {
+        String::new()
+    };
+    let keys = format!("v{}{}", count, motion);
+    let (lnum, col) = (query.cursor.0, query.cursor.1 + 1);
+
+    let mut script = fs::File::create(&script_path).unwrap();
+    writeln!(script, "set virtualedit=onemore").unwrap();
+    for line in &query.buffer {
+        writeln!(script, "call append(line('$'), {:?})", line).unwrap();
+    }
+    writeln!(script, "1delete").unwrap();
+    writeln!(script, "call cursor({}, {})", lnum, col).unwrap();
+    writeln!(script, "execute \"normal! {}\\<Esc>\"", keys).unwrap();
+    writeln!(
+        script,
+        "let g:result = {{'start': [line(\"'<\"), col(\"'<\")], 'end': [line(\"'>\"), col(\"'>\")]}}"
+    )
+    .unwrap();
+    writeln!(
+        script,
+        "call writefile([json_encode(g:result)], {:?})",
+        out_path.to_str().unwrap()
+    )
+    .unwrap();
+    writeln!(script, "qa!").unwrap();
+
+    let vim_bin = env::var("VIM_BIN_NAME").unwrap_or("vim".into());
+    Command::new(vim_bin)
+        .args(["-N", "-u", "NONE", "-es", "-S", script_path.to_str().unwrap()])
+        .current_dir(&basedir)
+        .output()
+        .unwrap();
+
+    let raw = fs::read_to_string(&out_path).unwrap();
+    let answer: RawTextObjectAnswer = serde_json::from_str(raw.trim()).unwrap();
+    fs::remove_file(&out_path).ok();
+    fs::remove_file(&script_path).ok();
+    TextObjectAnswer {
+        start: (answer.start[0], answer.start[1] - 1),
+        end: (answer.end[0], answer.end[1] - 1),
+    }
+}
+
+#[derive(Deserialize)]
+struct RawTextObjectAnswer {
+    start: [usize; 2],
+    end: [usize; 2],
+}
+
+/// A quickcheck-generated `(buffer, cursor, count)` triple, with cursor
+/// guaranteed to land strictly inside a token (never past the last
+/// character of the last line) so motions never hit the documented
+/// right-of-last-token panic.
+#[derive(Debug, Clone)]
+pub struct MotionCase {
+    pub buffer: Vec<String>,
+    pub cursor: (usize, usize),
+    pub count: u64,
+}
+
+const WORD_CHARS: &str = "abcdefghij";
+const CJK_CHARS: &str = "的一是不了人我在有他这中大来上国";
+const PUNCT_CHARS: &str = ".,;!?";
+
+fn arbitrary_line(g: &mut Gen) -> String {
+    if bool::arbitrary(g) && u8::arbitrary(g) % 8 == 0 {
+        // A wholly empty line, sampled with low probability.
+        return String::new();
+    }
+    let alphabet: Vec<char> = WORD_CHARS
+        .chars()
+        .chain(CJK_CHARS.chars())
+        .chain(PUNCT_CHARS.chars())
+        .chain([' ', ' ', ' '])
+        .collect();
+    let len = 1 + usize::arbitrary(g) % 15;
+    (0..len)
+        .map(|_| alphabet[usize::arbitrary(g) % alphabet.len()])
+        .collect()
+}
+
+impl Arbitrary for MotionCase {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let n_lines = 1 + usize::arbitrary(g) % 6;
+        let buffer: Vec<String> =
+            (0..n_lines).map(|_| arbitrary_line(g)).collect();
+        let cursor = arbitrary_cursor_in_token(g, &buffer);
+        let count = 1 + u64::arbitrary(g) % 5;
+        Self {
+            buffer,
+            cursor,
+            count,
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let mut shrunk = vec![];
+
+        // Shrink line count first: drop one line at a time.
+        if self.buffer.len() > 1 {
+            for i in 0..self.buffer.len() {
+                let mut buffer = self.buffer.clone();
+                buffer.remove(i);
+                if let Some(cursor) = clamp_cursor(&buffer, self.cursor, i) {
+                    shrunk.push(Self {
+                        buffer,
+                        cursor,
+                        count: self.count,
+                    });
+                }
+            }
+        }
+
+        // Then shrink each line's length.
+        for (i, line) in self.buffer.iter().enumerate() {
+            if line.chars().count() <= 1 {
+                continue;
+            }
+            let mut chars: Vec<char> = line.chars().collect();
+            chars.pop();
+            let mut buffer = self.buffer.clone();
+            buffer[i] = chars.into_iter().collect();
+            let col = self.cursor.1.min(buffer[i].chars().count().saturating_sub(1));
+            if self.cursor.0 - 1 == i {
+                shrunk.push(Self {
+                    buffer,
+                    cursor: (self.cursor.0, col),
+                    count: self.count,
+                });
+            }
+        }
+
+        // Finally shrink `count` toward 1.
+        if self.count > 1 {
+            shrunk.push(Self {
+                buffer: self.buffer.clone(),
+                cursor: self.cursor,
+                count: self.count - 1,
+            });
+        }
+
+        Box::new(shrunk.into_iter())
+    }
+}
+
+/// Drop line `removed_lnum` (0-indexed) from `cursor`'s frame of reference,
+/// returning `None` if that was the cursor's own line (the caller skips this
+/// shrink candidate rather than guess a replacement position).
+fn clamp_cursor(
+    buffer: &[String],
+    cursor: (usize, usize),
+    removed_lnum: usize,
+) -> Option<(usize, usize)> {
+    let (lnum, col) = cursor;
+    if lnum - 1 == removed_lnum {
+        return None;
+    }
+    let new_lnum = if lnum - 1 > removed_lnum {
+        lnum - 1
+    } else {
+        lnum
+    };
+    if new_lnum == 0 || new_lnum > buffer.len() {
+        return None;
+    }
+    Some((new_lnum, col))
+}
+
+/// Pick a uniformly random cursor that lands inside a non-empty token, or
+/// `(1, 0)` on an all-empty-lines buffer (an empty line is itself a token).
+fn arbitrary_cursor_in_token(g: &mut Gen, buffer: &[String]) -> (usize, usize) {
+    let candidates: Vec<(usize, usize)> = buffer
+        .iter()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            let lnum = i + 1;
+            if line.is_empty() {
+                vec![(lnum, 0)]
+            } else {
+                (0..line.chars().count()).map(move |col| (lnum, col)).collect()
+            }
+        })
+        .collect();
+    candidates[usize::arbitrary(g) % candidates.len()]
+}
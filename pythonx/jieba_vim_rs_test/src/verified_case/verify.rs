@@ -12,15 +12,19 @@
 // License for the specific language governing permissions and limitations
 // under the License.
 
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
 use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Child, Command, Stdio};
+use std::thread;
 
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use super::cases::VerifiableCase;
 
@@ -34,31 +38,46 @@ fn write_group_vader<I: IntoIterator<Item = P>, P: AsRef<Path>>(
     }
 }
 
-/// Verify all cases in the given group. Return `Err(log)` if verification
-/// fails.
-pub fn verify_cases<C>(
+/// The binary cache's on-disk schema version. Bumped whenever
+/// [`BinaryCache`]'s shape changes; [`load_verified_indices`] treats any
+/// other version (including an unreadable or missing file) as a clean
+/// cache miss rather than a panic, so a version bump just re-verifies
+/// everything once instead of breaking the run.
+const BINARY_CACHE_VERSION: u8 = 1;
+
+/// One group's verification cache, serialized as a single `bincode` blob
+/// instead of [`cases::VerifiableCase`]'s one-`*-verified.json`-per-case
+/// layout, so a large `#[vcase]` suite pays one deserialization instead of
+/// one `fs::read_to_string`/`serde_json::from_str` pair per case.
+#[derive(Default, Serialize, Deserialize)]
+struct BinaryCache {
+    version: u8,
+    /// `(case_name, index)` -> [`content_hash`] of the case last verified
+    /// at that slot.
+    entries: HashMap<(String, usize), u64>,
+}
+
+fn binary_cache_path(basedir: &Path, group_name: &str) -> PathBuf {
+    basedir.join(format!("{}.cache.bin", group_name))
+}
+
+/// Load `group_name`'s verification cache and record, per case name, the
+/// indices of the sub-cases in `cases` it already confirms. Gated behind
+/// the `json_cache` feature, which keeps the original one-file-per-case
+/// `serde_json` layout for debuggability (a verified case can be opened and
+/// read directly) at the cost of the per-file I/O this format is meant to
+/// avoid.
+#[cfg(feature = "json_cache")]
+fn load_verified_indices<C>(
+    basedir: &Path,
     group_name: &str,
     cases: &HashMap<String, Vec<C>>,
-) -> Result<(), String>
+) -> HashMap<String, Vec<usize>>
 where
-    C: VerifiableCase + PartialEq + Serialize + DeserializeOwned,
+    C: PartialEq + DeserializeOwned,
 {
-    let basedir: PathBuf = [
-        env::var("CARGO_MANIFEST_DIR").unwrap(),
-        ".verified_cases".into(),
-    ]
-    .iter()
-    .collect();
-    fs::create_dir(&basedir).ok();
-
-    // Create the group directory if not exists.
-    fs::create_dir(basedir.join(group_name)).ok();
-
-    // Try loading verification results, and record the indices of the verified
-    // cases.
     let mut verified_indices: HashMap<String, Vec<usize>> = HashMap::new();
     for (case_name, sub_cases) in cases.iter() {
-        // Whether each case has been verified.
         let ind = verified_indices.entry(case_name.to_string()).or_default();
         for (i, case) in sub_cases.iter().enumerate() {
             let verified_case_path = basedir.join(format!(
@@ -75,17 +94,21 @@ where
             }
         }
     }
+    verified_indices
+}
 
-    // Create a minimal vimrc if not already exists.
-    let vimrc_path = basedir.join("vimrc");
-    let vim_bundle_path =
-        env::var("VIM_BUNDLE_PATH").unwrap_or("~/.vim/bundle".into());
-    if let Ok(mut file) = File::create_new(vimrc_path) {
-        write!(file, "set rtp+={}/vader.vim\n", vim_bundle_path).unwrap();
-    }
-
-    // Create the vim vader files for cases that are not verified.
-    let mut case_paths = Vec::new();
+/// Write the sub-cases of `cases` not already in `verified_indices` to disk
+/// as individual `*-verified.json` files. See [`load_verified_indices`] for
+/// why this layout is feature-gated.
+#[cfg(feature = "json_cache")]
+fn write_verified<C>(
+    basedir: &Path,
+    group_name: &str,
+    cases: &HashMap<String, Vec<C>>,
+    verified_indices: &HashMap<String, Vec<usize>>,
+) where
+    C: Serialize,
+{
     for (case_name, sub_cases) in cases.iter() {
         let ind = verified_indices.get(case_name).unwrap();
         for (i, case) in sub_cases
@@ -93,84 +116,514 @@ where
             .enumerate()
             .filter(|(i, _)| !ind.contains(i))
         {
-            let case_path = basedir.join(format!(
-                "{}/{}-{}.vader",
+            let verified_case_path = basedir.join(format!(
+                "{}/{}-{}-verified.json",
                 group_name,
                 case_name,
-                i + 1
+                i + 1,
             ));
-            case.to_vader(&case_path);
-            case_paths.push(case_path);
+            let s = serde_json::to_string(case).unwrap();
+            let mut file = File::create(verified_case_path).unwrap();
+            write!(file, "{}", s).unwrap();
         }
     }
-    // Create the group vader file.
-    let group_path = basedir.join(format!("{}.vader", group_name));
-    write_group_vader(
-        &group_path,
-        case_paths
-            .iter()
-            .map(|dir| dir.strip_prefix(&basedir).unwrap()),
-    );
+}
 
-    // Run the tests.
-    let vim_bin = env::var("VIM_BIN_NAME").unwrap_or("vim".into());
-    let proc = if vim_bin == "vim" {
+/// Load `group_name`'s verification cache and record, per case name, the
+/// indices of the sub-cases in `cases` it already confirms. Reads the
+/// single [`BinaryCache`] blob [`write_verified`] wrote and compares
+/// [`content_hash`]es instead of `fs::read_to_string`-ing one file per case.
+/// A missing file, a corrupt blob, or a [`BINARY_CACHE_VERSION`] mismatch
+/// are all treated the same: a clean miss that re-verifies every case,
+/// rather than a panic.
+#[cfg(not(feature = "json_cache"))]
+fn load_verified_indices<C>(
+    basedir: &Path,
+    group_name: &str,
+    cases: &HashMap<String, Vec<C>>,
+) -> HashMap<String, Vec<usize>>
+where
+    C: Serialize,
+{
+    let cache = fs::read(binary_cache_path(basedir, group_name))
+        .ok()
+        .and_then(|bytes| bincode::deserialize::<BinaryCache>(&bytes).ok())
+        .filter(|cache| cache.version == BINARY_CACHE_VERSION)
+        .unwrap_or_default();
+
+    let mut verified_indices: HashMap<String, Vec<usize>> = HashMap::new();
+    for (case_name, sub_cases) in cases.iter() {
+        let ind = verified_indices.entry(case_name.to_string()).or_default();
+        for (i, case) in sub_cases.iter().enumerate() {
+            let expected = cache.entries.get(&(case_name.clone(), i));
+            if expected == Some(&content_hash(case)) {
+                ind.push(i);
+            }
+        }
+    }
+    verified_indices
+}
+
+/// Rewrite `group_name`'s [`BinaryCache`] blob from scratch with every case
+/// in `cases` (not just the newly-verified ones -- there is only the one
+/// file, so a partial rewrite would drop the previously-cached entries) and
+/// persist it atomically: write to a sibling `.tmp` path, then rename over
+/// the real one, so a crash mid-write can never leave a half-written blob
+/// behind.
+#[cfg(not(feature = "json_cache"))]
+fn write_verified<C>(
+    basedir: &Path,
+    group_name: &str,
+    cases: &HashMap<String, Vec<C>>,
+    _verified_indices: &HashMap<String, Vec<usize>>,
+) where
+    C: Serialize,
+{
+    let mut entries = HashMap::new();
+    for (case_name, sub_cases) in cases.iter() {
+        for (i, case) in sub_cases.iter().enumerate() {
+            entries.insert((case_name.clone(), i), content_hash(case));
+        }
+    }
+    let cache = BinaryCache {
+        version: BINARY_CACHE_VERSION,
+        entries,
+    };
+    let path = binary_cache_path(basedir, group_name);
+    let tmp_path = path.with_extension("bin.tmp");
+    fs::write(&tmp_path, bincode::serialize(&cache).unwrap()).unwrap();
+    fs::rename(&tmp_path, &path).unwrap();
+}
+
+/// One case's identity within a `#[vcase]` group -- the name given to the
+/// `vcase` attribute and its 1-indexed position among same-named cases, as
+/// used in `{case_name}-{index}.vader` filenames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaseId {
+    pub case_name: String,
+    pub index: usize,
+}
+
+impl fmt::Display for CaseId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.case_name, self.index)
+    }
+}
+
+/// One case's Vader output from a failed [`verify_cases`] run.
+#[derive(Debug)]
+pub struct CaseFailure {
+    pub case: CaseId,
+    pub message: String,
+}
+
+/// The structured result of a failed [`verify_cases`] run, so a consumer
+/// (the `verified_cases` proc-macro) can point at the exact `#[vcase]` that
+/// produced each failure instead of grepping a single opaque log.
+#[derive(Debug)]
+pub struct VerifyReport {
+    pub failures: Vec<CaseFailure>,
+}
+
+impl fmt::Display for VerifyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for failure in &self.failures {
+            writeln!(f, "case `{}` failed:\n{}", failure.case, failure.message)?;
+        }
+        Ok(())
+    }
+}
+
+// `verify_cases` keeps returning `Result<(), String>`, since its one caller
+// (`VerifiedCases::apply_fixture_mode`) already propagates that with `?` --
+// this lets it keep doing so while `VerifyReport` stays available to any
+// future caller that wants the per-case breakdown instead.
+impl From<VerifyReport> for String {
+    fn from(report: VerifyReport) -> String {
+        report.to_string()
+    }
+}
+
+fn spawn_vader(vim_bin: &str, basedir: &Path, vader_path: &Path) -> Child {
+    let vader_cmd = format!("silent Vader! {}", vader_path.to_str().unwrap());
+    if vim_bin == "vim" {
         Command::new("vim")
-            .args(&[
-                "-N",
-                "-u",
-                "vimrc",
-                "-c",
-                &format!("silent Vader! {}", group_path.to_str().unwrap()),
-            ])
+            .args(&["-N", "-u", "vimrc", "-c", &vader_cmd])
             .stdin(Stdio::piped())
             .stdout(Stdio::null())
             .stderr(Stdio::piped())
-            .current_dir(&basedir)
+            .current_dir(basedir)
             .spawn()
             .unwrap()
     } else if vim_bin == "nvim" {
         Command::new("nvim")
-            .args(&[
-                "-u",
-                "vimrc",
-                "-c",
-                &format!("silent Vader! {}", group_path.to_str().unwrap()),
-            ])
+            .args(&["-u", "vimrc", "-c", &vader_cmd])
             .stdin(Stdio::piped())
             .stdout(Stdio::null())
             .stderr(Stdio::piped())
-            .current_dir(&basedir)
+            .current_dir(basedir)
             .spawn()
             .unwrap()
     } else {
         panic!("Unexpected VIM_BIN_NAME: {}", vim_bin);
-    };
-    let proc_out = proc.wait_with_output().unwrap();
-    if proc_out.status.success() {
+    }
+}
+
+/// Bucket one shard's captured Vader stderr by the sub-case `*.vader` file
+/// each line names, carrying a line forward to whichever file was named
+/// most recently -- Vader's error/assertion lines repeat the source file on
+/// the line that introduces a failure, so the lines that follow (the actual
+/// assertion diff) stay attributed to that case even though they don't
+/// repeat the filename themselves. Cases in `shard` that never get a line
+/// attributed to them are assumed to have passed even though the shard's
+/// overall Vader process exited non-zero.
+fn attribute_vader_failures(
+    stderr: &str,
+    shard: &[(CaseId, PathBuf)],
+) -> Vec<CaseFailure> {
+    let mut messages: Vec<String> = vec![String::new(); shard.len()];
+    let mut mentioned: Vec<bool> = vec![false; shard.len()];
+    let mut current = None;
+    for line in stderr.lines() {
+        if let Some(i) = shard.iter().position(|(_, path)| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| line.contains(name))
+                .unwrap_or(false)
+        }) {
+            current = Some(i);
+            mentioned[i] = true;
+        }
+        if let Some(i) = current {
+            messages[i].push_str(line);
+            messages[i].push('\n');
+        }
+    }
+    shard
+        .iter()
+        .zip(messages)
+        .zip(mentioned)
+        .filter(|(_, was_mentioned)| *was_mentioned)
+        .map(|(((case_id, _), message), _)| CaseFailure {
+            case: case_id.clone(),
+            message,
+        })
+        .collect()
+}
+
+/// Verify all cases in the given group. Shards the sub-cases that aren't
+/// already cached (see [`load_verified_indices`]) across the available
+/// parallelism and spawns one headless `vim`/`nvim` process per shard
+/// concurrently, so a large suite verifies in a fraction of the wall time a
+/// single serial run would take. Returns `Err` built from a [`VerifyReport`]
+/// (via [`From<VerifyReport> for String`](VerifyReport)) naming exactly
+/// which cases failed, rather than one shard's raw stderr.
+pub fn verify_cases<C>(
+    group_name: &str,
+    cases: &HashMap<String, Vec<C>>,
+) -> Result<(), String>
+where
+    C: VerifiableCase + PartialEq + Serialize + DeserializeOwned,
+{
+    let basedir: PathBuf = [
+        env::var("CARGO_MANIFEST_DIR").unwrap(),
+        ".verified_cases".into(),
+    ]
+    .iter()
+    .collect();
+    fs::create_dir(&basedir).ok();
+
+    // Create the group directory if not exists.
+    fs::create_dir(basedir.join(group_name)).ok();
+
+    // Try loading verification results, and record the indices of the verified
+    // cases.
+    let verified_indices = load_verified_indices(&basedir, group_name, cases);
+
+    // Create a minimal vimrc if not already exists.
+    let vimrc_path = basedir.join("vimrc");
+    let vim_bundle_path =
+        env::var("VIM_BUNDLE_PATH").unwrap_or("~/.vim/bundle".into());
+    if let Ok(mut file) = File::create_new(vimrc_path) {
+        write!(file, "set rtp+={}/vader.vim\n", vim_bundle_path).unwrap();
+    }
+
+    // Create the vim vader files for cases that are not verified.
+    let mut pending = Vec::new();
+    for (case_name, sub_cases) in cases.iter() {
+        let ind = verified_indices.get(case_name).unwrap();
+        for (i, case) in sub_cases
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !ind.contains(i))
+        {
+            let case_id = CaseId {
+                case_name: case_name.clone(),
+                index: i + 1,
+            };
+            let case_path =
+                basedir.join(format!("{}/{}.vader", group_name, case_id));
+            case.to_vader(&case_path);
+            pending.push((case_id, case_path));
+        }
+    }
+
+    if pending.is_empty() {
+        write_verified(&basedir, group_name, cases, &verified_indices);
+        return Ok(());
+    }
+
+    // Shard the pending cases across the available parallelism, so they
+    // verify via several concurrent editor processes instead of one
+    // serial run.
+    let n_shards = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(pending.len());
+    let mut shards: Vec<Vec<(CaseId, PathBuf)>> = vec![Vec::new(); n_shards];
+    for (i, entry) in pending.into_iter().enumerate() {
+        shards[i % n_shards].push(entry);
+    }
+
+    // Write each shard's own group vader file and spawn its editor process
+    // up front (`Command::spawn` returns immediately), so every shard runs
+    // concurrently rather than one-at-a-time.
+    let vim_bin = env::var("VIM_BIN_NAME").unwrap_or("vim".into());
+    let mut running = Vec::new();
+    for (k, shard) in shards.iter().enumerate() {
+        let shard_path = basedir.join(format!("{}.shard{}.vader", group_name, k));
+        write_group_vader(
+            &shard_path,
+            shard.iter().map(|(_, path)| path.strip_prefix(&basedir).unwrap()),
+        );
+        running.push((shard, spawn_vader(&vim_bin, &basedir, &shard_path)));
+    }
+
+    let mut failures = Vec::new();
+    for (shard, child) in running {
+        let proc_out = child.wait_with_output().unwrap();
+        if !proc_out.status.success() {
+            let stderr = String::from_utf8_lossy(&proc_out.stderr);
+            let attributed = attribute_vader_failures(&stderr, shard);
+            if attributed.is_empty() {
+                // Vader's output didn't name any file from this shard --
+                // blame every case in it rather than silently dropping the
+                // failure.
+                failures.extend(shard.iter().map(|(case_id, _)| CaseFailure {
+                    case: case_id.clone(),
+                    message: stderr.to_string(),
+                }));
+            } else {
+                failures.extend(attributed);
+            }
+        }
+    }
+
+    if failures.is_empty() {
         // Write cache to disk to indicate verification success.
-        for (case_name, sub_cases) in cases.iter() {
-            let ind = verified_indices.get(case_name).unwrap();
-            for (i, case) in sub_cases
+        write_verified(&basedir, group_name, cases, &verified_indices);
+        Ok(())
+    } else {
+        Err(VerifyReport { failures }.into())
+    }
+}
+
+fn fixtures_path(group_name: &str) -> PathBuf {
+    [
+        env::var("CARGO_MANIFEST_DIR").unwrap(),
+        ".verified_cases".into(),
+        format!("{}.fixtures.json", group_name),
+    ]
+    .iter()
+    .collect()
+}
+
+fn dot_graph_path(group_name: &str) -> PathBuf {
+    [
+        env::var("CARGO_MANIFEST_DIR").unwrap(),
+        ".verified_cases".into(),
+        format!("{}.dot", group_name),
+    ]
+    .iter()
+    .collect()
+}
+
+/// One verified motion application, for [`render_dot_graph`] to turn into a
+/// labeled edge: the cursor walked from `before` to `after`, applying the
+/// motion named by `label` (e.g. `"2w"`).
+pub struct DotTransition {
+    pub before: (usize, usize),
+    pub before_glyph: char,
+    pub after: (usize, usize),
+    pub after_glyph: char,
+    pub label: String,
+}
+
+fn dot_node_id(pos: (usize, usize)) -> String {
+    format!("L{}C{}", pos.0, pos.1)
+}
+
+fn dot_quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Render `transitions` as a Graphviz `digraph` named `group_name`: one node
+/// per distinct `(lnum, col)` cursor position, labeled with the buffer glyph
+/// there, and one directed edge per transition, labeled with the motion name
+/// and count that produced it -- so `w`/`b`/`ge` can be eyeballed across a
+/// group's cases without a live editor.
+pub fn render_dot_graph(group_name: &str, transitions: &[DotTransition]) -> String {
+    let mut out = String::new();
+    out.push_str(&format!("digraph {} {{\n", dot_quote(group_name)));
+    let mut seen_nodes: HashMap<String, ()> = HashMap::new();
+    for t in transitions {
+        for (pos, glyph) in [(t.before, t.before_glyph), (t.after, t.after_glyph)] {
+            let id = dot_node_id(pos);
+            if seen_nodes.insert(id.clone(), ()).is_none() {
+                out.push_str(&format!(
+                    "  {} [label={}];\n",
+                    dot_quote(&id),
+                    dot_quote(&glyph.to_string())
+                ));
+            }
+        }
+    }
+    for t in transitions {
+        out.push_str(&format!(
+            "  {} -> {} [label={}];\n",
+            dot_quote(&dot_node_id(t.before)),
+            dot_quote(&dot_node_id(t.after)),
+            dot_quote(&t.label)
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Write [`render_dot_graph`]'s output to
+/// `.verified_cases/{group_name}.dot`, next to the fixtures
+/// [`disassemble_cases`] writes, so contributors can render it (e.g. `dot
+/// -Tpng`) and eyeball whether a motion's cursor transitions land where
+/// expected across multi-byte CJK buffers.
+pub fn write_dot_graph(
+    group_name: &str,
+    transitions: &[DotTransition],
+) -> Result<(), String> {
+    let path = dot_graph_path(group_name);
+    fs::create_dir_all(path.parent().unwrap()).map_err(|err| err.to_string())?;
+    fs::write(&path, render_dot_graph(group_name, transitions))
+        .map_err(|err| err.to_string())
+}
+
+/// Hash `case`'s JSON serialization. Used to key and validate the fixtures
+/// [`disassemble_cases`]/[`assemble_cases`] exchange, independent of the
+/// concrete case type.
+fn content_hash<C: Serialize>(case: &C) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(case).unwrap().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// One case's entry in a `.fixtures.json` file: the case itself (buffer,
+/// cursor positions, count, flags, and the verified `MotionOutput` -- all
+/// bundled together the same way the `VerifiableCase` impls already define
+/// them) plus `input_hash`, so an edit to the originating `vcase` attribute
+/// is detected instead of silently re-asserting a stale expectation.
+#[derive(Serialize, Deserialize)]
+struct CaseFixture {
+    input_hash: u64,
+    case: serde_json::Value,
+}
+
+/// Write every case in `cases` to a single `.verified_cases/{group_name}.fixtures.json`
+/// file, for [`assemble_cases`] to later read back without spawning an
+/// editor. Call once `verify_cases` has confirmed `cases` are correct.
+pub fn disassemble_cases<C>(
+    group_name: &str,
+    cases: &HashMap<String, Vec<C>>,
+) -> Result<(), String>
+where
+    C: Serialize,
+{
+    let path = fixtures_path(group_name);
+    fs::create_dir_all(path.parent().unwrap()).map_err(|err| err.to_string())?;
+    let fixtures: HashMap<&String, Vec<CaseFixture>> = cases
+        .iter()
+        .map(|(case_name, sub_cases)| {
+            let entries = sub_cases
                 .iter()
-                .enumerate()
-                .filter(|(i, _)| !ind.contains(i))
-            {
-                let verified_case_path = basedir.join(format!(
-                    "{}/{}-{}-verified.json",
-                    group_name,
+                .map(|case| CaseFixture {
+                    input_hash: content_hash(case),
+                    case: serde_json::to_value(case).unwrap(),
+                })
+                .collect();
+            (case_name, entries)
+        })
+        .collect();
+    fs::write(&path, serde_json::to_string_pretty(&fixtures).unwrap())
+        .map_err(|err| err.to_string())
+}
+
+/// The editor-less counterpart to `verify_cases`: load the fixture file
+/// [`disassemble_cases`] wrote for `group_name` and check it still matches
+/// `cases`, instead of spawning an editor to re-derive the same answer.
+/// Returns `Err` naming the case whose fixture is missing or whose
+/// `input_hash` no longer matches -- i.e. its `vcase` attribute was edited
+/// since the fixture was last regenerated via `verify_cases`.
+pub fn assemble_cases<C>(
+    group_name: &str,
+    cases: &HashMap<String, Vec<C>>,
+) -> Result<(), String>
+where
+    C: Serialize,
+{
+    let path = fixtures_path(group_name);
+    let s = fs::read_to_string(&path).map_err(|err| {
+        format!(
+            "Missing fixture file {}: {} -- run `verified_cases` (not the \
+             `_assemble` variant) once to regenerate it",
+            path.display(),
+            err
+        )
+    })?;
+    let fixtures: HashMap<String, Vec<CaseFixture>> = serde_json::from_str(&s)
+        .map_err(|err| format!("Corrupt fixture file {}: {}", path.display(), err))?;
+    for (case_name, sub_cases) in cases.iter() {
+        let fixture_cases = fixtures.get(case_name).ok_or_else(|| {
+            format!(
+                "No fixture recorded for case `{}` in group `{}` -- run \
+                 `verified_cases` to regenerate {}",
+                case_name,
+                group_name,
+                path.display()
+            )
+        })?;
+        if fixture_cases.len() != sub_cases.len() {
+            return Err(format!(
+                "Fixture for case `{}` in group `{}` has {} recorded instance(s) \
+                 but {} are now defined -- run `verified_cases` to regenerate {}",
+                case_name,
+                group_name,
+                fixture_cases.len(),
+                sub_cases.len(),
+                path.display()
+            ));
+        }
+        for (i, (case, fixture)) in
+            sub_cases.iter().zip(fixture_cases.iter()).enumerate()
+        {
+            if fixture.input_hash != content_hash(case) {
+                return Err(format!(
+                    "Stale fixture for case `{}-{}` in group `{}` -- its `vcase` \
+                     definition no longer matches what was last verified; run \
+                     `verified_cases` to regenerate {}",
                     case_name,
                     i + 1,
+                    group_name,
+                    path.display()
                 ));
-                let s = serde_json::to_string(case).unwrap();
-                let mut file = File::create(verified_case_path).unwrap();
-                write!(file, "{}", s).unwrap();
             }
         }
-        Ok(())
-    } else {
-        // Otherwise, return the stderr of the process.
-        let stderr = String::from_utf8_lossy(&proc_out.stderr);
-        Err(stderr.into())
     }
+    Ok(())
 }
@@ -17,15 +17,18 @@ mod verified_case;
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
 use verified_case::{
-    NamedVerifiedCasesAndMod, VerifiedCases, VerifiedCasesHeader,
+    FixtureMode, NamedVerifiedCasesAndMod, VerifiedCases, VerifiedCasesHeader,
 };
 
 #[proc_macro_attribute]
 pub fn verified_cases(attr: TokenStream, item: TokenStream) -> TokenStream {
     let header = parse_macro_input!(attr as VerifiedCasesHeader);
     let rest = parse_macro_input!(item as NamedVerifiedCasesAndMod);
-    let verified_cases = VerifiedCases::new(header, rest);
-    match verified_cases.verify_and_write_tests(false) {
+    let verified_cases = match VerifiedCases::new(header, rest) {
+        Ok(verified_cases) => verified_cases,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    match verified_cases.verify_and_write_tests(FixtureMode::Verify) {
         Err(message) => panic!("{}", message),
         Ok(out) => out.into(),
     }
@@ -38,8 +41,33 @@ pub fn verified_cases_dry_run(
 ) -> TokenStream {
     let header = parse_macro_input!(attr as VerifiedCasesHeader);
     let rest = parse_macro_input!(item as NamedVerifiedCasesAndMod);
-    let verified_cases = VerifiedCases::new(header, rest);
-    match verified_cases.verify_and_write_tests(true) {
+    let verified_cases = match VerifiedCases::new(header, rest) {
+        Ok(verified_cases) => verified_cases,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    match verified_cases.verify_and_write_tests(FixtureMode::SkipVerify) {
+        Err(message) => panic!("{}", message),
+        Ok(out) => out.into(),
+    }
+}
+
+/// Like [`verified_cases`], but loads a previously written
+/// `.verified_cases/{group_name}.fixtures.json` fixture instead of spawning
+/// an editor -- for running the suite in CI with no vim/nvim installed.
+/// Fails the build if the fixture is missing or stale; run `verified_cases`
+/// locally first to (re)generate it.
+#[proc_macro_attribute]
+pub fn verified_cases_assemble(
+    attr: TokenStream,
+    item: TokenStream,
+) -> TokenStream {
+    let header = parse_macro_input!(attr as VerifiedCasesHeader);
+    let rest = parse_macro_input!(item as NamedVerifiedCasesAndMod);
+    let verified_cases = match VerifiedCases::new(header, rest) {
+        Ok(verified_cases) => verified_cases,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    match verified_cases.verify_and_write_tests(FixtureMode::Assemble) {
         Err(message) => panic!("{}", message),
         Ok(out) => out.into(),
     }
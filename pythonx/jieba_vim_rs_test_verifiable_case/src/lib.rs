@@ -1,10 +1,9 @@
 use assert_cmd::Command;
 use core::{fmt, panic};
+use jieba_vim_rs_test::cursor_marker;
 use jieba_vim_rs_test::cursor_marker::{CursorMarker, CursorPosition};
-use once_cell::sync::Lazy;
 use proc_macro::TokenStream;
 use quote::quote;
-use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fs::File;
 use std::io::{BufWriter, Write};
@@ -12,7 +11,7 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 use std::{env, fs, io};
 use syn::parse::{Parse, ParseStream};
-use syn::{parse_macro_input, Ident, LitInt, LitStr, Token};
+use syn::{parse_macro_input, Ident, LitStr, Token};
 
 #[derive(Deserialize, Serialize, PartialEq, Eq, Clone)]
 enum Mode {
@@ -70,6 +69,147 @@ impl fmt::Display for Motion {
     }
 }
 
+/// Whether a motion moves the cursor forward (`w`/`W`/`e`/`E`) or backward
+/// (`b`/`B`/`ge`/`gE`) through the buffer -- used to pick which visual mark
+/// (`'>`/`'<`) ends up holding the result of a motion sequence.
+fn is_forward(motion: &Motion) -> bool {
+    matches!(
+        motion,
+        Motion::SmallW(_)
+            | Motion::LargeW(_)
+            | Motion::SmallE(_)
+            | Motion::LargeE(_)
+    )
+}
+
+/// Parse a chain of Vim word motions like `"2w"`, `"wwe"`, or `"2wge"` into
+/// the `(optional-count, motion-keyword)` pairs it's made of, where a
+/// motion-keyword is one of `w W e E b B ge gE`. `ge`/`gE` are matched
+/// greedily ahead of treating `g` as the start of the next pair, so e.g.
+/// `"gege"` parses as two `ge`s rather than failing on a stray `g`.
+fn parse_motions(s: &str) -> Result<Vec<Motion>, String> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    let mut motions = Vec::new();
+    while i < bytes.len() {
+        let count_start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        let count = if i > count_start {
+            s[count_start..i]
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid count in motion sequence: {}", s))?
+        } else {
+            0
+        };
+        if i >= bytes.len() {
+            return Err(format!(
+                "Count with no following motion keyword in: {}",
+                s
+            ));
+        }
+        let motion = if bytes[i] == b'g' {
+            let keyword = s.get(i..i + 2).ok_or_else(|| {
+                format!("Incomplete 'g'-prefixed motion keyword in: {}", s)
+            })?;
+            i += 2;
+            match keyword {
+                "ge" => Motion::SmallGe(count),
+                "gE" => Motion::LargeGe(count),
+                _ => {
+                    return Err(format!(
+                        "Unexpected motion keyword '{}' in: {}",
+                        keyword, s
+                    ))
+                }
+            }
+        } else {
+            let keyword = &s[i..i + 1];
+            i += 1;
+            match keyword {
+                "w" => Motion::SmallW(count),
+                "W" => Motion::LargeW(count),
+                "e" => Motion::SmallE(count),
+                "E" => Motion::LargeE(count),
+                "b" => Motion::SmallB(count),
+                "B" => Motion::LargeB(count),
+                _ => {
+                    return Err(format!(
+                        "Unexpected motion keyword '{}' in: {}",
+                        keyword, s
+                    ))
+                }
+            }
+        };
+        motions.push(motion);
+    }
+    if motions.is_empty() {
+        return Err(format!("Empty motion sequence: {:?}", s));
+    }
+    Ok(motions)
+}
+
+/// Which editor [`verify_case`] asks for ground truth -- Vim's own `gE`/`ge`
+/// and `virtualedit=onemore` differ subtly from Neovim's in a few edge
+/// cases, so a fixture recorded under one editor isn't trustworthy evidence
+/// for the other. Selected by the `JIEBA_VIM_TEST_EDITOR` environment
+/// variable (case-insensitive `"nvim"`/`"neovim"` picks [`Self::Neovim`]);
+/// unset or any other value falls back to [`Self::Vim`]. Stored on
+/// [`VerifiedCaseInputSer`] so it's folded into that struct's cache-key
+/// `PartialEq` impl, and a case recorded under Vim simply misses the cache
+/// (rather than false-hitting) when replayed under Neovim, or vice versa.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone)]
+enum EditorBackend {
+    Vim,
+    Neovim,
+}
+
+impl EditorBackend {
+    fn current() -> Self {
+        match env::var("JIEBA_VIM_TEST_EDITOR") {
+            Ok(v) if v.eq_ignore_ascii_case("nvim") => Self::Neovim,
+            Ok(v) if v.eq_ignore_ascii_case("neovim") => Self::Neovim,
+            _ => Self::Vim,
+        }
+    }
+
+    fn executable(&self) -> &'static str {
+        match self {
+            Self::Vim => "vim",
+            Self::Neovim => "nvim",
+        }
+    }
+
+    /// Name of the minimal config file [`verify_case`] generates once per
+    /// backend -- kept separate per backend since both the `-u` flag above
+    /// and the `set rtp+=` line below differ.
+    fn rc_file_name(&self) -> &'static str {
+        match self {
+            Self::Vim => "vimrc",
+            Self::Neovim => "init.vim",
+        }
+    }
+
+    /// Contents of [`Self::rc_file_name`]: just enough `rtp` to find
+    /// vader.vim, wherever each backend's plugin manager put it.
+    fn rc_file_contents(&self) -> &'static str {
+        match self {
+            Self::Vim => "set rtp+=~/.vim/bundle/vader.vim\n",
+            Self::Neovim => {
+                "set rtp+=~/.local/share/nvim/site/pack/plugins/start/vader.vim\n"
+            }
+        }
+    }
+
+    fn args<'a>(&self, rc_file_name: &'a str, vader_cmd: &'a str) -> Vec<&'a str> {
+        match self {
+            Self::Vim => vec!["-N", "-u", rc_file_name, vader_cmd],
+            Self::Neovim => vec!["--headless", "-u", rc_file_name, vader_cmd],
+        }
+    }
+}
+
 struct VerifiedCaseInput {
     group_id: Ident,
     test_name: Ident,
@@ -79,7 +219,85 @@ struct VerifiedCaseInput {
     stripped_buffers: Vec<String>,
     mode: Mode,
     operator: LitStr,
-    motion: Motion,
+    motions: Vec<Motion>,
+    backend: EditorBackend,
+}
+
+/// How many un-escaped occurrences of `marker` appear in `line`, skipping
+/// `\{`/`\}` escapes the same way [`CursorMarker`]'s lexer does. This is a
+/// best-effort re-scan purely to pin a [`cursor_marker::Error`] (which only
+/// names the offending marker character, not a line) back onto the specific
+/// buffer literal that caused it -- it doesn't understand `{+name}` anchors
+/// the way the real lexer does, since all that matters here is finding
+/// *a* reasonable line to underline, not re-deriving cursor positions.
+fn raw_marker_count(line: &str, marker: char) -> usize {
+    let mut count = 0;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' && matches!(chars.peek(), Some(&next) if next == '{' || next == '}') {
+            chars.next();
+            continue;
+        }
+        if c == marker {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Turn a [`cursor_marker::Error`] into a [`syn::Error`] spanned on the
+/// specific `buffer_lits` literal whose marker was malformed, falling back to
+/// the last buffer literal (or, if there are none, `input`'s own span) when
+/// no single line can be blamed.
+fn spanned_cursor_marker_error(
+    input: ParseStream,
+    err: cursor_marker::Error,
+    buffer_lits: &[LitStr],
+) -> syn::Error {
+    let fallback = |msg: String| match buffer_lits.last() {
+        Some(lit) => syn::Error::new_spanned(lit, msg),
+        None => input.error(msg),
+    };
+    match err {
+        cursor_marker::Error::Missing(marker) => fallback(format!(
+            "expected a `{marker}` cursor marker somewhere in this buffer, found none",
+        )),
+        cursor_marker::Error::MoreThanOne(marker) => {
+            let mut seen = 0;
+            for lit in buffer_lits {
+                seen += raw_marker_count(&lit.value(), marker);
+                if seen > 1 {
+                    return syn::Error::new_spanned(
+                        lit,
+                        format!(
+                            "expected at most one `{marker}` cursor marker, found another here",
+                        ),
+                    );
+                }
+            }
+            fallback(format!("found more than one `{marker}` cursor marker"))
+        }
+        cursor_marker::Error::DuplicateNamedMark(name) => {
+            let needle = format!("{{+{name}}}");
+            let mut seen = false;
+            for lit in buffer_lits {
+                if lit.value().contains(&needle) {
+                    if seen {
+                        return syn::Error::new_spanned(
+                            lit,
+                            format!(
+                                "expected the named mark `{{+{name}}}` once, found it again here",
+                            ),
+                        );
+                    }
+                    seen = true;
+                }
+            }
+            fallback(format!(
+                "named mark `{{+{name}}}` is used more than once",
+            ))
+        }
+    }
 }
 
 impl Parse for VerifiedCaseInput {
@@ -92,34 +310,38 @@ impl Parse for VerifiedCaseInput {
 
         let content;
         syn::bracketed!(content in input);
-        let buffers: Vec<String> = content
+        let buffer_lits: Vec<LitStr> = content
             .parse_terminated(|s| s.parse::<LitStr>(), Token![,])?
             .into_iter()
-            .map(|s| s.value())
             .collect();
+        let buffers: Vec<String> =
+            buffer_lits.iter().map(LitStr::value).collect();
         let parsed_buffers = match CursorMarker.strip_markers(buffers.clone()) {
             Err(err) => {
-                return Err(input.error(format!(
-                    "Failed to parse cursor positions from buffers: {:?}",
-                    err
-                )))
+                return Err(spanned_cursor_marker_error(
+                    input,
+                    err,
+                    &buffer_lits,
+                ))
             }
             Ok(o) => o,
         };
         input.parse::<Token![,]>()?;
 
-        let mode: LitStr = input.parse()?;
-        let mode = match mode.value().as_str() {
+        let mode_lit: LitStr = input.parse()?;
+        let mode = match mode_lit.value().as_str() {
             "n" => Mode::Normal,
             "xc" => Mode::VisualChar,
             "xl" => Mode::VisualLine,
             "xb" => Mode::VisualBlock,
             "o" => Mode::Operator,
-            mode_str => {
-                return Err(input.error(format!(
-                    "Expecting 'n'/'xc'/'xl'/'xb'/'o' but found: {}",
-                    mode_str
-                )))
+            found => {
+                return Err(syn::Error::new_spanned(
+                    &mode_lit,
+                    format!(
+                        r#"expected one of "n"/"xc"/"xl"/"xb"/"o", found "{found}""#
+                    ),
+                ))
             }
         };
         input.parse::<Token![,]>()?;
@@ -131,46 +353,30 @@ impl Parse for VerifiedCaseInput {
             | Mode::VisualLine
             | Mode::VisualBlock => {
                 if !operator.value().is_empty() {
-                    return Err(input.error(
-                        "When mode is not 'o', operator should be empty",
+                    return Err(syn::Error::new_spanned(
+                        &operator,
+                        format!(
+                            r#"expected an empty operator for mode "{}", found "{}""#,
+                            mode_lit.value(),
+                            operator.value()
+                        ),
                     ));
                 }
             }
             Mode::Operator => {
                 if operator.value().is_empty() {
-                    return Err(input.error(
-                        "When mode is 'o', operator should not be empty",
+                    return Err(syn::Error::new_spanned(
+                        &operator,
+                        r#"expected a non-empty operator for mode "o", found """#,
                     ));
                 }
             }
         }
         input.parse::<Token![,]>()?;
 
-        let count: LitInt = input.parse()?;
-        input.parse::<Token![,]>()?;
         let motion: LitStr = input.parse()?;
-        static MOTION_RE: Lazy<Regex> =
-            Lazy::new(|| Regex::new(r"^(w|W|e|E|b|B|ge|gE)$").unwrap());
-        let motion = match MOTION_RE.captures(&motion.value()) {
-            None => {
-                return Err(input
-                    .error(format!("Unexpected motion: {}", motion.value())))
-            }
-            Some(cap) => {
-                let count = count.base10_parse::<usize>()?;
-                match cap.get(1).unwrap().as_str() {
-                    "w" => Motion::SmallW(count),
-                    "W" => Motion::LargeW(count),
-                    "e" => Motion::SmallE(count),
-                    "E" => Motion::LargeE(count),
-                    "b" => Motion::SmallB(count),
-                    "B" => Motion::LargeB(count),
-                    "ge" => Motion::SmallGe(count),
-                    "gE" => Motion::LargeGe(count),
-                    _ => panic!("Unexpected error"),
-                }
-            }
-        };
+        let motions = parse_motions(&motion.value())
+            .map_err(|err| syn::Error::new_spanned(&motion, err))?;
 
         Ok(VerifiedCaseInput {
             group_id,
@@ -181,7 +387,8 @@ impl Parse for VerifiedCaseInput {
             stripped_buffers: parsed_buffers.striped_lines,
             mode,
             operator,
-            motion,
+            motions,
+            backend: EditorBackend::current(),
         })
     }
 }
@@ -196,7 +403,8 @@ struct VerifiedCaseInputSer {
     stripped_buffers: Vec<String>,
     mode: Mode,
     operator: String,
-    motion: Motion,
+    motions: Vec<Motion>,
+    backend: EditorBackend,
     verified: Option<bool>,
 }
 
@@ -211,7 +419,8 @@ impl PartialEq for VerifiedCaseInputSer {
             && self.stripped_buffers == other.stripped_buffers
             && self.mode == other.mode
             && self.operator == other.operator
-            && self.motion == other.motion
+            && self.motions == other.motions
+            && self.backend == other.backend
     }
 }
 
@@ -232,7 +441,8 @@ impl VerifiedCaseInput {
             stripped_buffers: self.stripped_buffers.clone(),
             mode: self.mode.clone(),
             operator: self.operator.value(),
-            motion: self.motion.clone(),
+            motions: self.motions.clone(),
+            backend: self.backend.clone(),
             verified: None,
         }
     }
@@ -262,7 +472,8 @@ impl VerifiedCaseInput {
         let lnum_after = self.after_cursor_position.lnum;
         let col_after = self.after_cursor_position.col + 1;
         let operator = self.operator.value();
-        let motion = &self.motion;
+        let motion: String =
+            self.motions.iter().map(Motion::to_string).collect();
 
         match self.mode {
             Mode::Normal => {
@@ -284,15 +495,13 @@ Then:
             }
             Mode::VisualChar | Mode::VisualLine | Mode::VisualBlock => {
                 write_vader_given_block(&mut tofile, &buffer_lines)?;
-                let reg = match motion {
-                    Motion::SmallW(_)
-                    | Motion::LargeW(_)
-                    | Motion::SmallE(_)
-                    | Motion::LargeE(_) => "'>",
-                    Motion::SmallB(_)
-                    | Motion::LargeB(_)
-                    | Motion::SmallGe(_)
-                    | Motion::LargeGe(_) => "'<",
+                // The mark holding the result is picked by the last motion
+                // in the sequence, same as a single motion would: forward
+                // motions grow the selection towards `'>`, backward motions
+                // towards `'<`.
+                let reg = match self.motions.last() {
+                    Some(last) if is_forward(last) => "'>",
+                    _ => "'<",
                 };
                 let v = match self.mode {
                     Mode::VisualChar => "v",
@@ -364,12 +573,12 @@ Before:
 }
 
 /// Usage: `verified_case_dry_run!(group_id, test_name, buffer_lines, mode,
-/// operator, count, motion)`.
+/// operator, motion)`.
 ///
 /// For example,
 ///
 /// ```norun
-/// verified_case!(motion_nmap_w, test_empty, ["{abc }def"], "n", "", 1, "w")
+/// verified_case!(motion_nmap_w, test_empty, ["{abc }def"], "n", "", "w")
 /// ```
 #[proc_macro]
 pub fn verified_case(tokens: TokenStream) -> TokenStream {
@@ -406,12 +615,12 @@ pub fn verified_case(tokens: TokenStream) -> TokenStream {
 /// Check the macro input only without actually verifying the test case.
 ///
 /// Usage: `verified_case_dry_run!(group_id, test_name, buffer_lines, mode,
-/// operator, count, motion)`.
+/// operator, motion)`.
 ///
 /// For example,
 ///
 /// ```norun
-/// verified_case!(motion_nmap_w, test_empty, ["{abc }def"], "n", "", 1, "w")
+/// verified_case!(motion_nmap_w, test_empty, ["{abc }def"], "n", "", "w")
 /// ```
 #[proc_macro]
 pub fn verified_case_dry_run(input: TokenStream) -> TokenStream {
@@ -455,13 +664,15 @@ fn verify_case(case_info: &VerifiedCaseInput) -> Result<bool, String> {
         }
     }
 
-    // Create a minimal vimrc if not already exists.
-    let vimrc_file_path: PathBuf =
-        [&basedir, Path::new("vimrc")].iter().collect();
-    if let Ok(mut vimrc_file) = File::create_new(vimrc_file_path) {
-        vimrc_file
-            .write_all("set rtp+=~/.vim/bundle/vader.vim\n".as_bytes())
-            .map_err(|_| format!("Failed to write vimrc file"))?;
+    // Create a minimal rc file for the selected backend if not already
+    // exists.
+    let backend = &case_info.backend;
+    let rc_file_path: PathBuf =
+        [&basedir, Path::new(backend.rc_file_name())].iter().collect();
+    if let Ok(mut rc_file) = File::create_new(rc_file_path) {
+        rc_file
+            .write_all(backend.rc_file_contents().as_bytes())
+            .map_err(|_| format!("Failed to write rc file"))?;
     }
 
     // Create the vim vader test file.
@@ -479,14 +690,11 @@ fn verify_case(case_info: &VerifiedCaseInput) -> Result<bool, String> {
         })?;
     } // `vader_file` should be closed here.
 
-    // Run vader test with vim, and see if the case can be verified.
-    let assert = Command::new("vim")
-        .args(&[
-            "-N",
-            "-u",
-            "vimrc",
-            &format!("+:Vader! {}", vader_file_name),
-        ])
+    // Run the vader test with the selected backend, and see if the case can
+    // be verified.
+    let vader_cmd = format!("+:Vader! {}", vader_file_name);
+    let assert = Command::new(backend.executable())
+        .args(backend.args(backend.rc_file_name(), &vader_cmd))
         .current_dir(&basedir)
         .timeout(Duration::from_secs(5))
         .assert();
@@ -0,0 +1,108 @@
+// Copyright 2024 Kaiwen Wu. All Rights Reserved.
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not
+// use this file except in compliance with the License. You may obtain a copy
+// of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS, WITHOUT
+// WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the
+// License for the specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Default number of distinct lines `SegmentationCache` remembers before
+/// evicting the least recently used entry.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+struct CacheEntry {
+    /// Byte-offset ranges of the tokens `Jieba::cut` produced for this line,
+    /// rather than the `&str` slices themselves, since the slices borrow
+    /// from whatever `sentence` is passed to the next `cut_hmm` call and
+    /// would not outlive it.
+    ranges: Vec<Range<usize>>,
+    last_used: u64,
+}
+
+/// A bounded, LRU-evicted cache from line content to the byte ranges jieba
+/// segmented it into. Repeated motions over an unchanged line (e.g. holding
+/// `w`, or a large `count`) hit this cache instead of re-running
+/// `Jieba::cut`.
+pub struct SegmentationCache {
+    capacity: usize,
+    entries: HashMap<String, CacheEntry>,
+    clock: u64,
+}
+
+impl SegmentationCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// Return the tokens for `sentence`, computing and caching them with
+    /// `cut` on a miss. `cut` must return slices that borrow from
+    /// `sentence`, matching `JiebaPlaceholder::cut_hmm`'s contract.
+    pub fn get_or_insert_with<'a>(
+        &mut self,
+        sentence: &'a str,
+        cut: impl FnOnce(&'a str) -> Vec<&'a str>,
+    ) -> Vec<&'a str> {
+        self.clock += 1;
+        if let Some(entry) = self.entries.get_mut(sentence) {
+            entry.last_used = self.clock;
+            return entry
+                .ranges
+                .iter()
+                .map(|range| &sentence[range.clone()])
+                .collect();
+        }
+
+        let tokens = cut(sentence);
+        let ranges = tokens.iter().map(|token| byte_range(sentence, token)).collect();
+        self.evict_if_full();
+        self.entries.insert(
+            sentence.to_owned(),
+            CacheEntry {
+                ranges,
+                last_used: self.clock,
+            },
+        );
+        tokens
+    }
+
+    /// Drop every cached entry. The Vim side calls this when a buffer is
+    /// edited, since cached ranges are only valid for the exact line content
+    /// they were computed from.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn evict_if_full(&mut self) {
+        if self.entries.len() < self.capacity {
+            return;
+        }
+        if let Some(lru_key) = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(key, _)| key.clone())
+        {
+            self.entries.remove(&lru_key);
+        }
+    }
+}
+
+/// The byte range `token` occupies within `sentence`, assuming `token` is a
+/// sub-slice of `sentence` (guaranteed by `Jieba::cut`).
+fn byte_range(sentence: &str, token: &str) -> Range<usize> {
+    let start = token.as_ptr() as usize - sentence.as_ptr() as usize;
+    start..start + token.len()
+}
@@ -1,3 +1,4 @@
+mod cache;
 mod wrappers;
 
 use pyo3::prelude::*;
@@ -6,6 +7,7 @@ use pyo3::prelude::*;
 #[pymodule]
 fn jieba_navi_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<wrappers::WordMotionWrapper>()?;
+    m.add_class::<wrappers::CharClassifierWrapper>()?;
 
     Ok(())
 }
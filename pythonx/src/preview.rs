@@ -55,3 +55,170 @@ where
 
     Ok(positions)
 }
+
+/// Like [`preview`] with a positive `preview_limit` (which already crosses
+/// line boundaries), but pairs each target with the 1-based count of steps
+/// that would land the cursor there -- e.g. for rendering `1`, `2`, `3`
+/// count-hint labels at successive `w`/`e` targets. `preview_limit` must be
+/// positive; pass it straight through to [`preview`] to get the BOF/EOF
+/// fixed-point break for free.
+pub fn preview_with_count<'b, B: BufferLike, M>(
+    motion1: M,
+    buffer: &'b B,
+    cursor_pos: (usize, usize),
+    preview_limit: usize,
+) -> Result<Vec<((usize, usize), usize)>, B::Error>
+where
+    M: FnMut(&'b B, (usize, usize)) -> Result<(usize, usize), B::Error>,
+{
+    Ok(preview(motion1, buffer, cursor_pos, preview_limit)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, pos)| (pos, i + 1))
+        .collect())
+}
+
+/// The `(start, end)` span, in buffer order, that a pending operator or
+/// visual-mode motion from `cursor_pos` to `target` would cover.
+pub fn span(
+    cursor_pos: (usize, usize),
+    target: (usize, usize),
+) -> ((usize, usize), (usize, usize)) {
+    if target < cursor_pos {
+        (target, cursor_pos)
+    } else {
+        (cursor_pos, target)
+    }
+}
+
+/// Pair `positions` (ordered nearest-first, as returned by [`preview`]) with
+/// short, collision-free labels drawn from `alphabet`. Uses the shortest
+/// label length, up to `max_label_len`, that can assign every position a
+/// distinct label; positions beyond `alphabet.len().pow(max_label_len)` are
+/// left unlabeled and dropped.
+pub fn label_targets(
+    positions: Vec<(usize, usize)>,
+    alphabet: &[char],
+    max_label_len: usize,
+) -> Vec<(String, (usize, usize))> {
+    if alphabet.is_empty() || max_label_len == 0 {
+        return vec![];
+    }
+    let base = alphabet.len();
+    let mut len = 1;
+    while len < max_label_len
+        && base
+            .checked_pow(len as u32)
+            .map_or(true, |cap| cap < positions.len())
+    {
+        len += 1;
+    }
+    let capacity = base.checked_pow(len as u32).unwrap_or(usize::MAX);
+    positions
+        .into_iter()
+        .take(capacity)
+        .enumerate()
+        .map(|(i, pos)| (label_for_index(i, alphabet, len), pos))
+        .collect()
+}
+
+/// Render `index` as a fixed-`len` label over `alphabet`, treating `index` as
+/// a number in base `alphabet.len()`.
+fn label_for_index(mut index: usize, alphabet: &[char], len: usize) -> String {
+    let base = alphabet.len();
+    let mut chars = vec![alphabet[0]; len];
+    for slot in chars.iter_mut().rev() {
+        *slot = alphabet[index % base];
+        index /= base;
+    }
+    chars.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestBuffer(Vec<&'static str>);
+
+    impl BufferLike for TestBuffer {
+        type Error = ();
+
+        fn getline(&self, lnum: usize) -> Result<String, Self::Error> {
+            self.0.get(lnum - 1).map(|s| s.to_string()).ok_or(())
+        }
+
+        fn lines(&self) -> Result<usize, Self::Error> {
+            Ok(self.0.len())
+        }
+    }
+
+    #[test]
+    fn test_preview_with_count_crosses_lines_and_numbers_each_step() {
+        // One step per char, wrapping to the next line's column 0 at EOL;
+        // stays put (the BOF/EOF fixed point) once past the last char.
+        let buffer = TestBuffer(vec!["ab", "c"]);
+        let positions = preview_with_count(
+            |buf: &TestBuffer, (lnum, col)| {
+                let line_len = buf.getline(lnum)?.len();
+                if col + 1 < line_len {
+                    Ok((lnum, col + 1))
+                } else if lnum < buf.lines()? {
+                    Ok((lnum + 1, 0))
+                } else {
+                    Ok((lnum, col))
+                }
+            },
+            &buffer,
+            (1, 0),
+            5,
+        )
+        .unwrap();
+        assert_eq!(positions, vec![((1, 1), 1), ((2, 0), 2)]);
+    }
+
+    #[test]
+    fn test_label_targets_single_char_when_alphabet_covers_all() {
+        let alphabet: Vec<char> = "ab".chars().collect();
+        let positions = vec![(1, 0), (1, 2)];
+        let labeled = label_targets(positions, &alphabet, 2);
+        assert_eq!(
+            labeled,
+            vec![
+                ("a".to_string(), (1, 0)),
+                ("b".to_string(), (1, 2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_label_targets_grows_length_when_alphabet_too_small() {
+        let alphabet: Vec<char> = "ab".chars().collect();
+        let positions = vec![(1, 0), (1, 1), (1, 2)];
+        let labeled = label_targets(positions, &alphabet, 2);
+        let labels: Vec<&str> = labeled.iter().map(|(l, _)| l.as_str()).collect();
+        assert_eq!(labels, vec!["aa", "ab", "ba"]);
+    }
+
+    #[test]
+    fn test_label_targets_drops_positions_beyond_capacity() {
+        let alphabet: Vec<char> = "a".chars().collect();
+        let positions = vec![(1, 0), (1, 1)];
+        let labeled = label_targets(positions, &alphabet, 1);
+        assert_eq!(labeled, vec![("a".to_string(), (1, 0))]);
+    }
+
+    #[test]
+    fn test_label_targets_empty_alphabet() {
+        assert_eq!(label_targets(vec![(1, 0)], &[], 2), vec![]);
+    }
+
+    #[test]
+    fn test_span_forward() {
+        assert_eq!(span((1, 2), (1, 5)), ((1, 2), (1, 5)));
+    }
+
+    #[test]
+    fn test_span_backward() {
+        assert_eq!(span((1, 5), (1, 2)), ((1, 2), (1, 5)));
+    }
+}
@@ -12,16 +12,19 @@
 // License for the specific language governing permissions and limitations
 // under the License.
 
-use std::cell::RefCell;
+use std::cell::{RefCell, RefMut};
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::BufReader;
 
 use jieba_rs::Jieba;
-use jieba_vim_rs_core::motion::{BufferLike, MotionOutput, WordMotion};
+use jieba_vim_rs_core::char_class::{CharCategory, CharClass, CharClassifier};
+use jieba_vim_rs_core::motion::{BufferLike, MotionOutput, TextObjectOutput, WordMotion};
 use jieba_vim_rs_core::token::JiebaPlaceholder;
 use pyo3::exceptions::{PyIOError, PyValueError};
 use pyo3::prelude::*;
 
+use crate::cache::{self, SegmentationCache};
 use crate::preview;
 
 struct BoundWrapper<'b, 'py, T>(&'b Bound<'py, T>);
@@ -44,31 +47,251 @@ impl<'b, 'py> BufferLike for BoundWrapper<'b, 'py, PyAny> {
     }
 }
 
-struct JiebaWrapper(Jieba);
+struct JiebaWrapper {
+    jieba: RefCell<Jieba>,
+    cache: RefCell<SegmentationCache>,
+    classifier: CharClassifier,
+}
+
+impl JiebaWrapper {
+    fn new(jieba: Jieba, classifier: CharClassifier) -> Self {
+        Self {
+            jieba: RefCell::new(jieba),
+            cache: RefCell::new(SegmentationCache::new(cache::DEFAULT_CAPACITY)),
+            classifier,
+        }
+    }
+
+    fn clear_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    /// Register `word` (with optional `freq`/`tag`) in the underlying
+    /// `Jieba` instance so segmentation treats it as one token from then
+    /// on, and drop the segmentation cache, since previously cached splits
+    /// may no longer apply.
+    fn add_word(&self, word: &str, freq: Option<usize>, tag: Option<&str>) {
+        self.jieba.borrow_mut().add_word(word, freq, tag);
+        self.clear_cache();
+    }
+
+    /// Merge a user dictionary (jieba's `word freq tag` format, one entry
+    /// per line) from `path` into the underlying `Jieba` instance, and drop
+    /// the segmentation cache.
+    fn load_dict(&self, path: &str) -> PyResult<()> {
+        let mut reader =
+            BufReader::new(File::open(path).map_err(|err| PyIOError::new_err(err))?);
+        self.jieba.borrow_mut().load_dict(&mut reader).map_err(|err| {
+            PyValueError::new_err(format!("jieba error: {}", err))
+        })?;
+        self.clear_cache();
+        Ok(())
+    }
+
+    /// Register every `(word, freq, tag)` in `words` (see [`Self::add_word`]),
+    /// dropping the segmentation cache once afterward instead of once per
+    /// entry.
+    fn add_words<'a, I>(&self, words: I)
+    where
+        I: IntoIterator<Item = (&'a str, Option<usize>, Option<&'a str>)>,
+    {
+        {
+            let mut jieba = self.jieba.borrow_mut();
+            for (word, freq, tag) in words {
+                jieba.add_word(word, freq, tag);
+            }
+        }
+        self.clear_cache();
+    }
+}
 
 impl JiebaPlaceholder for JiebaWrapper {
     fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
-        self.0.cut(sentence, true)
+        self.cache
+            .borrow_mut()
+            .get_or_insert_with(sentence, |sentence| self.jieba.borrow().cut(sentence, true))
+    }
+
+    fn cut_other<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        jieba_vim_rs_core::token::subword::split(sentence)
+    }
+
+    fn classifier(&self) -> &CharClassifier {
+        &self.classifier
     }
 }
 
 struct LazyJiebaWrapper {
     path: Option<String>,
     jieba: RefCell<Option<Jieba>>,
+    cache: RefCell<SegmentationCache>,
+    classifier: CharClassifier,
 }
 
-impl JiebaPlaceholder for LazyJiebaWrapper {
-    fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
-        self.jieba
-            .borrow_mut()
-            .get_or_insert_with(|| match &self.path {
+impl LazyJiebaWrapper {
+    fn new(path: Option<String>, classifier: CharClassifier) -> Self {
+        Self {
+            path,
+            jieba: RefCell::new(None),
+            cache: RefCell::new(SegmentationCache::new(cache::DEFAULT_CAPACITY)),
+            classifier,
+        }
+    }
+
+    fn clear_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    /// Construct the underlying `Jieba` on first use (from `self.path`, or
+    /// the default dictionary if `None`), and return it for read or write.
+    fn jieba_mut(&self) -> RefMut<'_, Jieba> {
+        let mut jieba = self.jieba.borrow_mut();
+        if jieba.is_none() {
+            *jieba = Some(match &self.path {
                 None => Jieba::new(),
                 Some(path) => {
                     let mut reader = BufReader::new(File::open(path).unwrap());
                     Jieba::with_dict(&mut reader).unwrap()
                 }
+            });
+        }
+        RefMut::map(jieba, |jieba| jieba.as_mut().unwrap())
+    }
+
+    /// Register `word` (with optional `freq`/`tag`) in the underlying
+    /// `Jieba` instance so segmentation treats it as one token from then
+    /// on, and drop the segmentation cache, since previously cached splits
+    /// may no longer apply.
+    fn add_word(&self, word: &str, freq: Option<usize>, tag: Option<&str>) {
+        self.jieba_mut().add_word(word, freq, tag);
+        self.clear_cache();
+    }
+
+    /// Merge a user dictionary (jieba's `word freq tag` format, one entry
+    /// per line) from `path` into the underlying `Jieba` instance, and drop
+    /// the segmentation cache.
+    fn load_dict(&self, path: &str) -> PyResult<()> {
+        let mut reader =
+            BufReader::new(File::open(path).map_err(|err| PyIOError::new_err(err))?);
+        self.jieba_mut().load_dict(&mut reader).map_err(|err| {
+            PyValueError::new_err(format!("jieba error: {}", err))
+        })?;
+        self.clear_cache();
+        Ok(())
+    }
+
+    /// Register every `(word, freq, tag)` in `words` (see [`Self::add_word`]),
+    /// dropping the segmentation cache once afterward instead of once per
+    /// entry.
+    fn add_words<'a, I>(&self, words: I)
+    where
+        I: IntoIterator<Item = (&'a str, Option<usize>, Option<&'a str>)>,
+    {
+        {
+            let mut jieba = self.jieba_mut();
+            for (word, freq, tag) in words {
+                jieba.add_word(word, freq, tag);
+            }
+        }
+        self.clear_cache();
+    }
+}
+
+impl JiebaPlaceholder for LazyJiebaWrapper {
+    fn cut_hmm<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        let jieba = self.jieba_mut();
+        self.cache
+            .borrow_mut()
+            .get_or_insert_with(sentence, |sentence| jieba.cut(sentence, true))
+    }
+
+    fn cut_other<'a>(&self, sentence: &'a str) -> Vec<&'a str> {
+        jieba_vim_rs_core::token::subword::split(sentence)
+    }
+
+    fn classifier(&self) -> &CharClassifier {
+        &self.classifier
+    }
+}
+
+/// Parse one of `"word"`, `"punct"`, `"blank"` into a [`CharClass`], or error
+/// out with the valid spellings.
+fn parse_char_class(class: &str) -> PyResult<CharClass> {
+    match class {
+        "word" => Ok(CharClass::Word),
+        "punct" => Ok(CharClass::Punct),
+        "blank" => Ok(CharClass::Blank),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown char class {:?}, expected \"word\", \"punct\", or \"blank\"",
+            class
+        ))),
+    }
+}
+
+/// Parse one of the category names a `with_category_hook` callback may
+/// return into a [`CharCategory`], or error out with the valid spellings.
+fn parse_char_category(category: &str) -> PyResult<CharCategory> {
+    match category {
+        "space" => Ok(CharCategory::Space),
+        "hanzi" => Ok(CharCategory::WordHanzi),
+        "hiragana" => Ok(CharCategory::WordHiragana),
+        "katakana" => Ok(CharCategory::WordKatakana),
+        "hangul" => Ok(CharCategory::WordHangul),
+        "word" => Ok(CharCategory::WordOther),
+        "left_punc" => Ok(CharCategory::NonWordLeftPunc),
+        "right_punc" => Ok(CharCategory::NonWordRightPunc),
+        "isolated_punc" => Ok(CharCategory::NonWordIsolatedPunc),
+        "punct" => Ok(CharCategory::NonWordOther),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown char category {:?}, expected one of \"space\", \"hanzi\", \"hiragana\", \
+             \"katakana\", \"hangul\", \"word\", \"left_punc\", \"right_punc\", \
+             \"isolated_punc\", or \"punct\"",
+            category
+        ))),
+    }
+}
+
+/// Overrides the word/punctuation/blank classification that [`WordMotion`]
+/// consults for every `nmap_*`/`omap_*`/`xmap_*` motion, for users who need to
+/// tune word boundaries in mixed-script buffers. Pass one to
+/// [`WordMotionWrapper::from_dict`] or [`LazyWordMotionWrapper::from_dict`].
+#[pyclass]
+#[pyo3(name = "CharClassifier")]
+#[derive(Clone, Default)]
+pub struct CharClassifierWrapper(CharClassifier);
+
+#[pymethods]
+impl CharClassifierWrapper {
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Force every char in `start..=end` to classify as `class`, one of
+    /// `"word"`, `"punct"`, or `"blank"`. Returns a new classifier rather
+    /// than mutating `self`, mirroring the Rust builder.
+    pub fn with_override(&self, start: char, end: char, class: &str) -> PyResult<Self> {
+        let class = parse_char_class(class)?;
+        Ok(Self(self.0.clone().with_override(start..=end, class)))
+    }
+
+    /// Run `callback` before every other classification rule -- including
+    /// the hardcoded CJK/punctuation tables and [`Self::with_override`]
+    /// ranges -- for every char, letting users reclassify specific
+    /// characters from their own Neovim config without recompiling:
+    /// `callback` takes a one-character `str` and returns `None` (falls
+    /// through to the normal rules) or one of `"space"`, `"hanzi"`,
+    /// `"word"`, `"left_punc"`, `"right_punc"`, `"isolated_punc"`, or
+    /// `"punct"`. Returns a new classifier rather than mutating `self`,
+    /// mirroring the Rust builder.
+    pub fn with_category_hook(&self, callback: PyObject) -> Self {
+        Self(self.0.clone().with_category_hook(move |c| {
+            Python::with_gil(|py| {
+                let result = callback.call1(py, (c.to_string(),)).ok()?;
+                let category: Option<String> = result.extract(py).ok()?;
+                category.and_then(|s| parse_char_category(&s).ok())
             })
-            .cut(sentence, true)
+        }))
     }
 }
 
@@ -94,19 +317,105 @@ impl MotionOutputWrapper {
     }
 }
 
+#[pyclass]
+#[pyo3(name = "TextObjectOutput")]
+pub struct TextObjectOutputWrapper(TextObjectOutput);
+
+#[pymethods]
+impl TextObjectOutputWrapper {
+    #[getter]
+    pub fn start(&self) -> (usize, usize) {
+        self.0.start
+    }
+
+    #[getter]
+    pub fn end(&self) -> (usize, usize) {
+        self.0.end
+    }
+}
+
 #[pyclass]
 #[pyo3(name = "WordMotion")]
 pub struct WordMotionWrapper {
     wm: WordMotion<JiebaWrapper>,
+    jump_targets: RefCell<HashMap<String, (usize, usize)>>,
+}
+
+impl WordMotionWrapper {
+    /// Label `positions` with [`preview::label_targets`] and remember the
+    /// label -> position mapping so `resolve_jump` can look it up later.
+    fn label_and_store_jump_targets(
+        &self,
+        positions: Vec<(usize, usize)>,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> Vec<(String, (usize, usize))> {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        let labeled = preview::label_targets(positions, &alphabet, max_label_len);
+        *self.jump_targets.borrow_mut() = labeled.iter().cloned().collect();
+        labeled
+    }
+
+    /// Run the single motion named `name` (matching one of the `nmap_*`/
+    /// `xmap_*`/`omap_*` method names below) against `buf`. Shared by
+    /// [`Self::batch_motions`] so every query in a batch goes through the
+    /// same dispatch as a one-off call.
+    fn dispatch_motion(
+        &self,
+        buf: &BoundWrapper<'_, '_, PyAny>,
+        name: &str,
+        cursor_pos: (usize, usize),
+        count: u64,
+        operator: Option<&str>,
+    ) -> PyResult<MotionOutput> {
+        Ok(match name {
+            "nmap_w" => self.wm.nmap_w(buf, cursor_pos, count, true)?,
+            "nmap_W" => self.wm.nmap_w(buf, cursor_pos, count, false)?,
+            "xmap_w" => self.wm.xmap_w(buf, cursor_pos, count, true)?,
+            "xmap_W" => self.wm.xmap_w(buf, cursor_pos, count, false)?,
+            "omap_w" if operator == Some("c") => self.wm.omap_c_w(buf, cursor_pos, count, true)?,
+            "omap_w" => self.wm.omap_w(buf, cursor_pos, count, true)?,
+            "omap_W" if operator == Some("c") => self.wm.omap_c_w(buf, cursor_pos, count, false)?,
+            "omap_W" => self.wm.omap_w(buf, cursor_pos, count, false)?,
+            "nmap_e" => self.wm.nmap_e(buf, cursor_pos, count, true)?,
+            "nmap_E" => self.wm.nmap_e(buf, cursor_pos, count, false)?,
+            "xmap_e" => self.wm.xmap_e(buf, cursor_pos, count, true)?,
+            "xmap_E" => self.wm.xmap_e(buf, cursor_pos, count, false)?,
+            "omap_e" => self.wm.omap_e(buf, cursor_pos, count, true)?,
+            "omap_E" => self.wm.omap_e(buf, cursor_pos, count, false)?,
+            "nmap_b" => self.wm.nmap_b(buf, cursor_pos, count, true)?,
+            "nmap_B" => self.wm.nmap_b(buf, cursor_pos, count, false)?,
+            "xmap_b" => self.wm.xmap_b(buf, cursor_pos, count, true)?,
+            "xmap_B" => self.wm.xmap_b(buf, cursor_pos, count, false)?,
+            "omap_b" => self.wm.omap_b(buf, cursor_pos, count, true)?,
+            "omap_B" => self.wm.omap_b(buf, cursor_pos, count, false)?,
+            "nmap_ge" => self.wm.nmap_ge(buf, cursor_pos, count, true)?,
+            "nmap_gE" => self.wm.nmap_ge(buf, cursor_pos, count, false)?,
+            "xmap_ge" => self.wm.xmap_ge(buf, cursor_pos, count, true)?,
+            "xmap_gE" => self.wm.xmap_ge(buf, cursor_pos, count, false)?,
+            "omap_ge" => self.wm.omap_ge(buf, cursor_pos, count, true)?,
+            "omap_gE" => self.wm.omap_ge(buf, cursor_pos, count, false)?,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown motion name {:?}",
+                    name
+                )))
+            }
+        })
+    }
 }
 
 #[pymethods]
 impl WordMotionWrapper {
     /// Load jieba with the default dictionary, or with custom dictionary given
-    /// dictionary path.
+    /// dictionary path. `classifier` overrides the default word/punctuation/
+    /// blank classification used for chars outside the CJK-specific rules.
     #[new]
-    #[pyo3(signature = (path=None))]
-    pub fn from_dict(path: Option<&str>) -> PyResult<Self> {
+    #[pyo3(signature = (path=None, classifier=None))]
+    pub fn from_dict(
+        path: Option<&str>,
+        classifier: Option<CharClassifierWrapper>,
+    ) -> PyResult<Self> {
         let jieba = match path {
             None => Jieba::new(),
             Some(path) => {
@@ -119,10 +428,82 @@ impl WordMotionWrapper {
             }
         };
         Ok(Self {
-            wm: WordMotion::new(JiebaWrapper(jieba)),
+            wm: WordMotion::new(JiebaWrapper::new(
+                jieba,
+                classifier.unwrap_or_default().0,
+            )),
+            jump_targets: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Drop the per-line segmentation cache and the per-line token cache.
+    /// Call this after editing the buffer, since both are only valid for the
+    /// exact line content they were computed from.
+    pub fn clear_cache(&self) {
+        self.wm.clear_cache();
+        self.wm.jieba().clear_cache();
+    }
+
+    /// Register `word` (with optional `freq`/`tag`) so `w`/`e`/`b` treat it
+    /// as a single token from then on. Clears the per-line segmentation
+    /// cache, since previously cached splits may no longer apply.
+    #[pyo3(signature = (word, freq=None, tag=None))]
+    pub fn add_word(&self, word: &str, freq: Option<usize>, tag: Option<&str>) {
+        self.wm.jieba().add_word(word, freq, tag);
+    }
+
+    /// Register a list of `(word, freq, tag)` entries in one call (see
+    /// [`Self::add_word`]), clearing the segmentation cache once afterward
+    /// instead of once per entry.
+    pub fn add_words(&self, words: Vec<(String, Option<usize>, Option<String>)>) {
+        self.wm.jieba().add_words(
+            words
+                .iter()
+                .map(|(word, freq, tag)| (word.as_str(), *freq, tag.as_deref())),
+        );
+    }
+
+    /// Merge a user dictionary from `path` (jieba's `word freq tag` format,
+    /// one entry per line) into the current `Jieba` instance. Clears the
+    /// per-line segmentation cache.
+    pub fn load_user_dict(&self, path: &str) -> PyResult<()> {
+        self.wm.jieba().load_dict(path)
+    }
+
+    /// Resolve a label returned by a `jump_targets_*` call back to its cursor
+    /// position. Errors if `label` isn't among the most recently computed
+    /// jump targets.
+    pub fn resolve_jump(&self, label: &str) -> PyResult<(usize, usize)> {
+        self.jump_targets.borrow().get(label).copied().ok_or_else(|| {
+            PyValueError::new_err(format!("unknown jump label {:?}", label))
         })
     }
 
+    /// Answer many motion queries against `buffer` in one call, each given
+    /// as `(name, cursor_pos, count, operator)`. `name` is one of the
+    /// single-motion method names on this class (e.g. `"nmap_w"`,
+    /// `"omap_E"`, `"xmap_ge"`); `operator` is only consulted for `omap_*`
+    /// names (`"c"` for `omap_w`/`omap_W`) and ignored otherwise
+    /// (`omap_e`/`omap_E`/`omap_ge`/`omap_gE` compute their own `d_special`
+    /// regardless of operator). `buffer` is only converted
+    /// to a [`BoundWrapper`] once, and every query shares the one
+    /// segmentation cache on this instance, so lines common to several
+    /// queries are only tokenized once. Errors on an unrecognized `name`.
+    pub fn batch_motions(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        queries: Vec<(String, (usize, usize), u64, Option<String>)>,
+    ) -> PyResult<Vec<MotionOutputWrapper>> {
+        let buf = BoundWrapper(buffer);
+        queries
+            .into_iter()
+            .map(|(name, cursor_pos, count, operator)| {
+                self.dispatch_motion(&buf, &name, cursor_pos, count, operator.as_deref())
+                    .map(MotionOutputWrapper)
+            })
+            .collect()
+    }
+
     pub fn nmap_w(
         &self,
         buffer: &Bound<'_, PyAny>,
@@ -288,6 +669,11 @@ impl WordMotionWrapper {
         )?))
     }
 
+    /// `operator` is accepted (and still required by callers, for a
+    /// uniform `omap_*(buffer, cursor_pos, operator, count)` signature
+    /// across all `omap_*` methods) but unused -- `omap_e` computes
+    /// `d_special` itself regardless of which operator is asking.
+    #[allow(unused_variables)]
     pub fn omap_e(
         &self,
         buffer: &Bound<'_, PyAny>,
@@ -295,24 +681,16 @@ impl WordMotionWrapper {
         operator: &str,
         count: u64,
     ) -> PyResult<MotionOutputWrapper> {
-        if operator == "d" {
-            Ok(MotionOutputWrapper(self.wm.omap_d_e(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                true,
-            )?))
-        } else {
-            Ok(MotionOutputWrapper(self.wm.omap_e(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                true,
-            )?))
-        }
+        Ok(MotionOutputWrapper(self.wm.omap_e(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
     }
 
-    #[allow(non_snake_case)]
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(non_snake_case, unused_variables)]
     pub fn omap_E(
         &self,
         buffer: &Bound<'_, PyAny>,
@@ -320,21 +698,12 @@ impl WordMotionWrapper {
         operator: &str,
         count: u64,
     ) -> PyResult<MotionOutputWrapper> {
-        if operator == "d" {
-            Ok(MotionOutputWrapper(self.wm.omap_d_e(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                false,
-            )?))
-        } else {
-            Ok(MotionOutputWrapper(self.wm.omap_e(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                false,
-            )?))
-        }
+        Ok(MotionOutputWrapper(self.wm.omap_e(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
     }
 
     pub fn nmap_b(
@@ -482,6 +851,8 @@ impl WordMotionWrapper {
         )?))
     }
 
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(unused_variables)]
     pub fn omap_ge(
         &self,
         buffer: &Bound<'_, PyAny>,
@@ -489,24 +860,16 @@ impl WordMotionWrapper {
         operator: &str,
         count: u64,
     ) -> PyResult<MotionOutputWrapper> {
-        if operator == "d" {
-            Ok(MotionOutputWrapper(self.wm.omap_d_ge(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                true,
-            )?))
-        } else {
-            Ok(MotionOutputWrapper(self.wm.omap_ge(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                true,
-            )?))
-        }
+        Ok(MotionOutputWrapper(self.wm.omap_ge(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
     }
 
-    #[allow(non_snake_case)]
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(non_snake_case, unused_variables)]
     pub fn omap_gE(
         &self,
         buffer: &Bound<'_, PyAny>,
@@ -514,31 +877,29 @@ impl WordMotionWrapper {
         operator: &str,
         count: u64,
     ) -> PyResult<MotionOutputWrapper> {
-        if operator == "d" {
-            Ok(MotionOutputWrapper(self.wm.omap_d_ge(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                false,
-            )?))
-        } else {
-            Ok(MotionOutputWrapper(self.wm.omap_ge(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                false,
-            )?))
-        }
+        Ok(MotionOutputWrapper(self.wm.omap_ge(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
     }
 
+    /// `count` is the Vim count the motion would run with (e.g. `3` for
+    /// `3w`); the returned targets step by `count` each time unless
+    /// `show_intermediate` is set, in which case every single-step target
+    /// leading up to each `count`-multiple is included too.
     pub fn preview_nmap_w(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
+        count: u64,
         preview_limit: usize,
+        show_intermediate: bool,
     ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
         preview::preview(
-            |b, c| Ok(self.wm.nmap_w(b, c, 1, true)?.new_cursor_pos),
+            |b, c| Ok(self.wm.nmap_w(b, c, step, true)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
             preview_limit,
@@ -550,10 +911,13 @@ impl WordMotionWrapper {
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
+        count: u64,
         preview_limit: usize,
+        show_intermediate: bool,
     ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
         preview::preview(
-            |b, c| Ok(self.wm.nmap_w(b, c, 1, false)?.new_cursor_pos),
+            |b, c| Ok(self.wm.nmap_w(b, c, step, false)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
             preview_limit,
@@ -564,10 +928,13 @@ impl WordMotionWrapper {
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
+        count: u64,
         preview_limit: usize,
+        show_intermediate: bool,
     ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
         preview::preview(
-            |b, c| Ok(self.wm.nmap_e(b, c, 1, true)?.new_cursor_pos),
+            |b, c| Ok(self.wm.nmap_e(b, c, step, true)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
             preview_limit,
@@ -579,10 +946,13 @@ impl WordMotionWrapper {
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
+        count: u64,
         preview_limit: usize,
+        show_intermediate: bool,
     ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
         preview::preview(
-            |b, c| Ok(self.wm.nmap_e(b, c, 1, false)?.new_cursor_pos),
+            |b, c| Ok(self.wm.nmap_e(b, c, step, false)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
             preview_limit,
@@ -593,10 +963,13 @@ impl WordMotionWrapper {
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
+        count: u64,
         preview_limit: usize,
+        show_intermediate: bool,
     ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
         preview::preview(
-            |b, c| Ok(self.wm.nmap_b(b, c, 1, true)?.new_cursor_pos),
+            |b, c| Ok(self.wm.nmap_b(b, c, step, true)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
             preview_limit,
@@ -608,10 +981,13 @@ impl WordMotionWrapper {
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
+        count: u64,
         preview_limit: usize,
+        show_intermediate: bool,
     ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
         preview::preview(
-            |b, c| Ok(self.wm.nmap_b(b, c, 1, false)?.new_cursor_pos),
+            |b, c| Ok(self.wm.nmap_b(b, c, step, false)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
             preview_limit,
@@ -622,10 +998,13 @@ impl WordMotionWrapper {
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
+        count: u64,
         preview_limit: usize,
+        show_intermediate: bool,
     ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
         preview::preview(
-            |b, c| Ok(self.wm.nmap_ge(b, c, 1, true)?.new_cursor_pos),
+            |b, c| Ok(self.wm.nmap_ge(b, c, step, true)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
             preview_limit,
@@ -637,132 +1016,825 @@ impl WordMotionWrapper {
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
+        count: u64,
         preview_limit: usize,
+        show_intermediate: bool,
     ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
         preview::preview(
-            |b, c| Ok(self.wm.nmap_ge(b, c, 1, false)?.new_cursor_pos),
+            |b, c| Ok(self.wm.nmap_ge(b, c, step, false)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
             preview_limit,
         )
     }
-}
 
-#[pyclass]
-#[pyo3(name = "LazyWordMotion")]
-pub struct LazyWordMotionWrapper {
-    wm: WordMotion<LazyJiebaWrapper>,
-}
+    pub fn preview_xmap_w(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_w(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
 
-#[pymethods]
-impl LazyWordMotionWrapper {
-    #[new]
-    #[pyo3(signature = (path=None))]
-    pub fn from_dict(path: Option<String>) -> PyResult<Self> {
-        // Check if `path` is readable beforehand.
-        if let Some(path) = &path {
-            File::open(path).map_err(|err| PyIOError::new_err(err))?;
-        }
-        Ok(Self {
-            wm: WordMotion::new(LazyJiebaWrapper {
-                path,
-                jieba: RefCell::new(None),
-            }),
-        })
+    #[allow(non_snake_case)]
+    pub fn preview_xmap_W(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_w(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
     }
 
-    pub fn nmap_w(
+    pub fn preview_xmap_e(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.nmap_w(
-            &BoundWrapper(buffer),
-            cursor_pos,
-            count,
-            true,
-        )?))
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_e(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
     }
 
     #[allow(non_snake_case)]
-    pub fn nmap_W(
+    pub fn preview_xmap_E(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.nmap_w(
-            &BoundWrapper(buffer),
-            cursor_pos,
-            count,
-            false,
-        )?))
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_e(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
     }
 
-    pub fn xmap_w(
+    pub fn preview_xmap_b(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.xmap_w(
-            &BoundWrapper(buffer),
-            cursor_pos,
-            count,
-            true,
-        )?))
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_b(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
     }
 
     #[allow(non_snake_case)]
-    pub fn xmap_W(
+    pub fn preview_xmap_B(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.xmap_w(
-            &BoundWrapper(buffer),
-            cursor_pos,
-            count,
-            false,
-        )?))
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_b(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
     }
 
-    pub fn omap_w(
+    pub fn preview_xmap_ge(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        operator: &str,
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        if operator == "c" {
-            Ok(MotionOutputWrapper(self.wm.omap_c_w(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                true,
-            )?))
-        } else {
-            Ok(MotionOutputWrapper(self.wm.omap_w(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                true,
-            )?))
-        }
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_ge(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
     }
 
     #[allow(non_snake_case)]
-    pub fn omap_W(
+    pub fn preview_xmap_gE(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_ge(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    pub fn preview_omap_w(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         operator: &str,
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        if operator == "c" {
-            Ok(MotionOutputWrapper(self.wm.omap_c_w(
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = if operator == "c" {
+            self.wm
+                .omap_c_w(&BoundWrapper(buffer), cursor_pos, count, true)?
+                .new_cursor_pos
+        } else {
+            self.wm
+                .omap_w(&BoundWrapper(buffer), cursor_pos, count, true)?
+                .new_cursor_pos
+        };
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_omap_W(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = if operator == "c" {
+            self.wm
+                .omap_c_w(&BoundWrapper(buffer), cursor_pos, count, false)?
+                .new_cursor_pos
+        } else {
+            self.wm
+                .omap_w(&BoundWrapper(buffer), cursor_pos, count, false)?
+                .new_cursor_pos
+        };
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(unused_variables)]
+    pub fn preview_omap_e(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .omap_e(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(non_snake_case, unused_variables)]
+    pub fn preview_omap_E(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .omap_e(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    pub fn preview_omap_b(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self.wm.omap_b(&BoundWrapper(buffer), cursor_pos, count, true)?;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_omap_B(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self.wm.omap_b(&BoundWrapper(buffer), cursor_pos, count, false)?;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(unused_variables)]
+    pub fn preview_omap_ge(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .omap_ge(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(non_snake_case, unused_variables)]
+    pub fn preview_omap_gE(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .omap_ge(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    /// Pair each target `preview_nmap_w` would have highlighted with a short
+    /// label drawn from `alphabet` (nearest-first), up to `max_label_len`
+    /// chars long. Resolve a chosen label back to its position with
+    /// `resolve_jump`.
+    pub fn jump_targets_nmap_w(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_w(b, c, 1, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn jump_targets_nmap_W(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_w(b, c, 1, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
+    }
+
+    pub fn jump_targets_nmap_e(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_e(b, c, 1, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn jump_targets_nmap_E(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_e(b, c, 1, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
+    }
+
+    pub fn jump_targets_nmap_b(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_b(b, c, 1, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn jump_targets_nmap_B(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_b(b, c, 1, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
+    }
+
+    pub fn jump_targets_nmap_ge(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_ge(b, c, 1, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn jump_targets_nmap_gE(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_ge(b, c, 1, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
+    }
+
+    /// Pair each target `preview_nmap_w` would have highlighted with the
+    /// 1-based count of `w` presses that would land the cursor there, for
+    /// rendering `1`/`2`/`3` count-hint labels at successive targets.
+    pub fn count_hint_nmap_w(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+    ) -> PyResult<Vec<((usize, usize), usize)>> {
+        preview::preview_with_count(
+            |b, c| Ok(self.wm.nmap_w(b, c, 1, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    #[allow(non_snake_case)]
+    pub fn count_hint_nmap_W(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+    ) -> PyResult<Vec<((usize, usize), usize)>> {
+        preview::preview_with_count(
+            |b, c| Ok(self.wm.nmap_w(b, c, 1, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    pub fn count_hint_nmap_e(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+    ) -> PyResult<Vec<((usize, usize), usize)>> {
+        preview::preview_with_count(
+            |b, c| Ok(self.wm.nmap_e(b, c, 1, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    #[allow(non_snake_case)]
+    pub fn count_hint_nmap_E(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        preview_limit: usize,
+    ) -> PyResult<Vec<((usize, usize), usize)>> {
+        preview::preview_with_count(
+            |b, c| Ok(self.wm.nmap_e(b, c, 1, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    pub fn omap_iw(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.omap_iw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn omap_iW(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.omap_iw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn omap_aw(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.omap_aw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn omap_aW(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.omap_aw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn xmap_iw(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.xmap_iw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn xmap_iW(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.xmap_iw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn xmap_aw(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.xmap_aw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn xmap_aW(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.xmap_aw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+}
+
+#[pyclass]
+#[pyo3(name = "LazyWordMotion")]
+pub struct LazyWordMotionWrapper {
+    wm: WordMotion<LazyJiebaWrapper>,
+    jump_targets: RefCell<HashMap<String, (usize, usize)>>,
+}
+
+impl LazyWordMotionWrapper {
+    /// Label `positions` with [`preview::label_targets`] and remember the
+    /// label -> position mapping so `resolve_jump` can look it up later.
+    fn label_and_store_jump_targets(
+        &self,
+        positions: Vec<(usize, usize)>,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> Vec<(String, (usize, usize))> {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        let labeled = preview::label_targets(positions, &alphabet, max_label_len);
+        *self.jump_targets.borrow_mut() = labeled.iter().cloned().collect();
+        labeled
+    }
+
+    /// Run the single motion named `name` (matching one of the `nmap_*`/
+    /// `xmap_*`/`omap_*` method names below) against `buf`. Shared by
+    /// [`Self::batch_motions`] so every query in a batch goes through the
+    /// same dispatch as a one-off call.
+    fn dispatch_motion(
+        &self,
+        buf: &BoundWrapper<'_, '_, PyAny>,
+        name: &str,
+        cursor_pos: (usize, usize),
+        count: u64,
+        operator: Option<&str>,
+    ) -> PyResult<MotionOutput> {
+        Ok(match name {
+            "nmap_w" => self.wm.nmap_w(buf, cursor_pos, count, true)?,
+            "nmap_W" => self.wm.nmap_w(buf, cursor_pos, count, false)?,
+            "xmap_w" => self.wm.xmap_w(buf, cursor_pos, count, true)?,
+            "xmap_W" => self.wm.xmap_w(buf, cursor_pos, count, false)?,
+            "omap_w" if operator == Some("c") => self.wm.omap_c_w(buf, cursor_pos, count, true)?,
+            "omap_w" => self.wm.omap_w(buf, cursor_pos, count, true)?,
+            "omap_W" if operator == Some("c") => self.wm.omap_c_w(buf, cursor_pos, count, false)?,
+            "omap_W" => self.wm.omap_w(buf, cursor_pos, count, false)?,
+            "nmap_e" => self.wm.nmap_e(buf, cursor_pos, count, true)?,
+            "nmap_E" => self.wm.nmap_e(buf, cursor_pos, count, false)?,
+            "xmap_e" => self.wm.xmap_e(buf, cursor_pos, count, true)?,
+            "xmap_E" => self.wm.xmap_e(buf, cursor_pos, count, false)?,
+            "omap_e" => self.wm.omap_e(buf, cursor_pos, count, true)?,
+            "omap_E" => self.wm.omap_e(buf, cursor_pos, count, false)?,
+            "nmap_b" => self.wm.nmap_b(buf, cursor_pos, count, true)?,
+            "nmap_B" => self.wm.nmap_b(buf, cursor_pos, count, false)?,
+            "xmap_b" => self.wm.xmap_b(buf, cursor_pos, count, true)?,
+            "xmap_B" => self.wm.xmap_b(buf, cursor_pos, count, false)?,
+            "omap_b" => self.wm.omap_b(buf, cursor_pos, count, true)?,
+            "omap_B" => self.wm.omap_b(buf, cursor_pos, count, false)?,
+            "nmap_ge" => self.wm.nmap_ge(buf, cursor_pos, count, true)?,
+            "nmap_gE" => self.wm.nmap_ge(buf, cursor_pos, count, false)?,
+            "xmap_ge" => self.wm.xmap_ge(buf, cursor_pos, count, true)?,
+            "xmap_gE" => self.wm.xmap_ge(buf, cursor_pos, count, false)?,
+            "omap_ge" => self.wm.omap_ge(buf, cursor_pos, count, true)?,
+            "omap_gE" => self.wm.omap_ge(buf, cursor_pos, count, false)?,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown motion name {:?}",
+                    name
+                )))
+            }
+        })
+    }
+}
+
+#[pymethods]
+impl LazyWordMotionWrapper {
+    #[new]
+    #[pyo3(signature = (path=None, classifier=None))]
+    pub fn from_dict(
+        path: Option<String>,
+        classifier: Option<CharClassifierWrapper>,
+    ) -> PyResult<Self> {
+        // Check if `path` is readable beforehand.
+        if let Some(path) = &path {
+            File::open(path).map_err(|err| PyIOError::new_err(err))?;
+        }
+        Ok(Self {
+            wm: WordMotion::new(LazyJiebaWrapper::new(
+                path,
+                classifier.unwrap_or_default().0,
+            )),
+            jump_targets: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Drop the per-line segmentation cache and the per-line token cache.
+    /// Call this after editing the buffer, since both are only valid for the
+    /// exact line content they were computed from.
+    pub fn clear_cache(&self) {
+        self.wm.clear_cache();
+        self.wm.jieba().clear_cache();
+    }
+
+    /// Register `word` (with optional `freq`/`tag`) so `w`/`e`/`b` treat it
+    /// as a single token from then on. Clears the per-line segmentation
+    /// cache, since previously cached splits may no longer apply.
+    #[pyo3(signature = (word, freq=None, tag=None))]
+    pub fn add_word(&self, word: &str, freq: Option<usize>, tag: Option<&str>) {
+        self.wm.jieba().add_word(word, freq, tag);
+    }
+
+    /// Register a list of `(word, freq, tag)` entries in one call (see
+    /// [`Self::add_word`]), clearing the segmentation cache once afterward
+    /// instead of once per entry.
+    pub fn add_words(&self, words: Vec<(String, Option<usize>, Option<String>)>) {
+        self.wm.jieba().add_words(
+            words
+                .iter()
+                .map(|(word, freq, tag)| (word.as_str(), *freq, tag.as_deref())),
+        );
+    }
+
+    /// Merge a user dictionary from `path` (jieba's `word freq tag` format,
+    /// one entry per line) into the current `Jieba` instance. Clears the
+    /// per-line segmentation cache.
+    pub fn load_user_dict(&self, path: &str) -> PyResult<()> {
+        self.wm.jieba().load_dict(path)
+    }
+
+    /// Resolve a label returned by a `jump_targets_*` call back to its cursor
+    /// position. Errors if `label` isn't among the most recently computed
+    /// jump targets.
+    pub fn resolve_jump(&self, label: &str) -> PyResult<(usize, usize)> {
+        self.jump_targets.borrow().get(label).copied().ok_or_else(|| {
+            PyValueError::new_err(format!("unknown jump label {:?}", label))
+        })
+    }
+
+    /// Answer many motion queries against `buffer` in one call, each given
+    /// as `(name, cursor_pos, count, operator)`. `name` is one of the
+    /// single-motion method names on this class (e.g. `"nmap_w"`,
+    /// `"omap_E"`, `"xmap_ge"`); `operator` is only consulted for `omap_*`
+    /// names (`"c"` for `omap_w`/`omap_W`) and ignored otherwise
+    /// (`omap_e`/`omap_E`/`omap_ge`/`omap_gE` compute their own `d_special`
+    /// regardless of operator). `buffer` is only converted
+    /// to a [`BoundWrapper`] once, and every query shares the one
+    /// segmentation cache on this instance, so lines common to several
+    /// queries are only tokenized once. Errors on an unrecognized `name`.
+    pub fn batch_motions(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        queries: Vec<(String, (usize, usize), u64, Option<String>)>,
+    ) -> PyResult<Vec<MotionOutputWrapper>> {
+        let buf = BoundWrapper(buffer);
+        queries
+            .into_iter()
+            .map(|(name, cursor_pos, count, operator)| {
+                self.dispatch_motion(&buf, &name, cursor_pos, count, operator.as_deref())
+                    .map(MotionOutputWrapper)
+            })
+            .collect()
+    }
+
+    pub fn nmap_w(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.nmap_w(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn nmap_W(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.nmap_w(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn xmap_w(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.xmap_w(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn xmap_W(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.xmap_w(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn omap_w(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        if operator == "c" {
+            Ok(MotionOutputWrapper(self.wm.omap_c_w(
+                &BoundWrapper(buffer),
+                cursor_pos,
+                count,
+                true,
+            )?))
+        } else {
+            Ok(MotionOutputWrapper(self.wm.omap_w(
+                &BoundWrapper(buffer),
+                cursor_pos,
+                count,
+                true,
+            )?))
+        }
+    }
+
+    #[allow(non_snake_case)]
+    pub fn omap_W(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        if operator == "c" {
+            Ok(MotionOutputWrapper(self.wm.omap_c_w(
                 &BoundWrapper(buffer),
                 cursor_pos,
                 count,
@@ -778,13 +1850,195 @@ impl LazyWordMotionWrapper {
         }
     }
 
-    pub fn nmap_e(
+    pub fn nmap_e(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.nmap_e(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn nmap_E(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.nmap_e(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn xmap_e(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.xmap_e(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn xmap_E(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.xmap_e(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    /// `operator` is accepted (and still required by callers, for a
+    /// uniform `omap_*(buffer, cursor_pos, operator, count)` signature
+    /// across all `omap_*` methods) but unused -- `omap_e` computes
+    /// `d_special` itself regardless of which operator is asking.
+    #[allow(unused_variables)]
+    pub fn omap_e(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.omap_e(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(non_snake_case, unused_variables)]
+    pub fn omap_E(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.omap_e(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn nmap_b(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.nmap_b(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn nmap_B(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.nmap_b(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn xmap_b(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.xmap_b(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn xmap_B(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.xmap_b(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn omap_b(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.omap_b(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn omap_B(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         count: u64,
     ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.nmap_e(
+        Ok(MotionOutputWrapper(self.wm.omap_b(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn nmap_ge(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.nmap_ge(
             &BoundWrapper(buffer),
             cursor_pos,
             count,
@@ -793,13 +2047,13 @@ impl LazyWordMotionWrapper {
     }
 
     #[allow(non_snake_case)]
-    pub fn nmap_E(
+    pub fn nmap_gE(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         count: u64,
     ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.nmap_e(
+        Ok(MotionOutputWrapper(self.wm.nmap_ge(
             &BoundWrapper(buffer),
             cursor_pos,
             count,
@@ -807,13 +2061,13 @@ impl LazyWordMotionWrapper {
         )?))
     }
 
-    pub fn xmap_e(
+    pub fn xmap_ge(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         count: u64,
     ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.xmap_e(
+        Ok(MotionOutputWrapper(self.wm.xmap_ge(
             &BoundWrapper(buffer),
             cursor_pos,
             count,
@@ -822,13 +2076,13 @@ impl LazyWordMotionWrapper {
     }
 
     #[allow(non_snake_case)]
-    pub fn xmap_E(
+    pub fn xmap_gE(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         count: u64,
     ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.xmap_e(
+        Ok(MotionOutputWrapper(self.wm.xmap_ge(
             &BoundWrapper(buffer),
             cursor_pos,
             count,
@@ -836,256 +2090,570 @@ impl LazyWordMotionWrapper {
         )?))
     }
 
-    pub fn omap_e(
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(unused_variables)]
+    pub fn omap_ge(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         operator: &str,
         count: u64,
     ) -> PyResult<MotionOutputWrapper> {
-        if operator == "d" {
-            Ok(MotionOutputWrapper(self.wm.omap_d_e(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                true,
-            )?))
+        Ok(MotionOutputWrapper(self.wm.omap_ge(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(non_snake_case, unused_variables)]
+    pub fn omap_gE(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<MotionOutputWrapper> {
+        Ok(MotionOutputWrapper(self.wm.omap_ge(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    /// `count` is the Vim count the motion would run with (e.g. `3` for
+    /// `3w`); the returned targets step by `count` each time unless
+    /// `show_intermediate` is set, in which case every single-step target
+    /// leading up to each `count`-multiple is included too.
+    pub fn preview_nmap_w(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+        preview_limit: usize,
+        show_intermediate: bool,
+    ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
+        preview::preview(
+            |b, c| Ok(self.wm.nmap_w(b, c, step, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_nmap_W(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+        preview_limit: usize,
+        show_intermediate: bool,
+    ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
+        preview::preview(
+            |b, c| Ok(self.wm.nmap_w(b, c, step, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    pub fn preview_nmap_e(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+        preview_limit: usize,
+        show_intermediate: bool,
+    ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
+        preview::preview(
+            |b, c| Ok(self.wm.nmap_e(b, c, step, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_nmap_E(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+        preview_limit: usize,
+        show_intermediate: bool,
+    ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
+        preview::preview(
+            |b, c| Ok(self.wm.nmap_e(b, c, step, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    pub fn preview_nmap_b(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+        preview_limit: usize,
+        show_intermediate: bool,
+    ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
+        preview::preview(
+            |b, c| Ok(self.wm.nmap_b(b, c, step, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_nmap_B(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+        preview_limit: usize,
+        show_intermediate: bool,
+    ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
+        preview::preview(
+            |b, c| Ok(self.wm.nmap_b(b, c, step, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    pub fn preview_nmap_ge(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+        preview_limit: usize,
+        show_intermediate: bool,
+    ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
+        preview::preview(
+            |b, c| Ok(self.wm.nmap_ge(b, c, step, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_nmap_gE(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+        preview_limit: usize,
+        show_intermediate: bool,
+    ) -> PyResult<Vec<(usize, usize)>> {
+        let step = if show_intermediate { 1 } else { count.max(1) };
+        preview::preview(
+            |b, c| Ok(self.wm.nmap_ge(b, c, step, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )
+    }
+
+    pub fn preview_xmap_w(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_w(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_xmap_W(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_w(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    pub fn preview_xmap_e(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_e(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_xmap_E(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_e(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    pub fn preview_xmap_b(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_b(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_xmap_B(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_b(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    pub fn preview_xmap_ge(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_ge(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_xmap_gE(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .xmap_ge(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    pub fn preview_omap_w(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = if operator == "c" {
+            self.wm
+                .omap_c_w(&BoundWrapper(buffer), cursor_pos, count, true)?
+                .new_cursor_pos
         } else {
-            Ok(MotionOutputWrapper(self.wm.omap_e(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                true,
-            )?))
-        }
+            self.wm
+                .omap_w(&BoundWrapper(buffer), cursor_pos, count, true)?
+                .new_cursor_pos
+        };
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn preview_omap_W(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = if operator == "c" {
+            self.wm
+                .omap_c_w(&BoundWrapper(buffer), cursor_pos, count, false)?
+                .new_cursor_pos
+        } else {
+            self.wm
+                .omap_w(&BoundWrapper(buffer), cursor_pos, count, false)?
+                .new_cursor_pos
+        };
+        Ok(preview::span(cursor_pos, target))
+    }
+
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(unused_variables)]
+    pub fn preview_omap_e(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        operator: &str,
+        count: u64,
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .omap_e(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
     }
 
-    #[allow(non_snake_case)]
-    pub fn omap_E(
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(non_snake_case, unused_variables)]
+    pub fn preview_omap_E(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         operator: &str,
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        if operator == "d" {
-            Ok(MotionOutputWrapper(self.wm.omap_d_e(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                false,
-            )?))
-        } else {
-            Ok(MotionOutputWrapper(self.wm.omap_e(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                false,
-            )?))
-        }
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .omap_e(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
     }
 
-    pub fn nmap_b(
+    pub fn preview_omap_b(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.nmap_b(
-            &BoundWrapper(buffer),
-            cursor_pos,
-            count,
-            true,
-        )?))
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self.wm.omap_b(&BoundWrapper(buffer), cursor_pos, count, true)?;
+        Ok(preview::span(cursor_pos, target))
     }
 
     #[allow(non_snake_case)]
-    pub fn nmap_B(
+    pub fn preview_omap_B(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.nmap_b(
-            &BoundWrapper(buffer),
-            cursor_pos,
-            count,
-            false,
-        )?))
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self.wm.omap_b(&BoundWrapper(buffer), cursor_pos, count, false)?;
+        Ok(preview::span(cursor_pos, target))
     }
 
-    pub fn xmap_b(
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(unused_variables)]
+    pub fn preview_omap_ge(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
+        operator: &str,
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.xmap_b(
-            &BoundWrapper(buffer),
-            cursor_pos,
-            count,
-            true,
-        )?))
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .omap_ge(&BoundWrapper(buffer), cursor_pos, count, true)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
     }
 
-    #[allow(non_snake_case)]
-    pub fn xmap_B(
+    /// See [`Self::omap_e`] re: the unused `operator` parameter.
+    #[allow(non_snake_case, unused_variables)]
+    pub fn preview_omap_gE(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
+        operator: &str,
         count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.xmap_b(
-            &BoundWrapper(buffer),
-            cursor_pos,
-            count,
-            false,
-        )?))
+    ) -> PyResult<((usize, usize), (usize, usize))> {
+        let target = self
+            .wm
+            .omap_ge(&BoundWrapper(buffer), cursor_pos, count, false)?
+            .new_cursor_pos;
+        Ok(preview::span(cursor_pos, target))
     }
 
-    pub fn omap_b(
+    /// Pair each target `preview_nmap_w` would have highlighted with a short
+    /// label drawn from `alphabet` (nearest-first), up to `max_label_len`
+    /// chars long. Resolve a chosen label back to its position with
+    /// `resolve_jump`.
+    pub fn jump_targets_nmap_w(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.omap_b(
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_w(b, c, 1, true)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
-            count,
-            true,
-        )?))
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
     }
 
     #[allow(non_snake_case)]
-    pub fn omap_B(
+    pub fn jump_targets_nmap_W(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.omap_b(
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_w(b, c, 1, false)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
-            count,
-            false,
-        )?))
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
     }
 
-    pub fn nmap_ge(
+    pub fn jump_targets_nmap_e(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.nmap_ge(
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_e(b, c, 1, true)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
-            count,
-            true,
-        )?))
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
     }
 
     #[allow(non_snake_case)]
-    pub fn nmap_gE(
+    pub fn jump_targets_nmap_E(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.nmap_ge(
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_e(b, c, 1, false)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
-            count,
-            false,
-        )?))
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
     }
 
-    pub fn xmap_ge(
+    pub fn jump_targets_nmap_b(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.xmap_ge(
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_b(b, c, 1, true)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
-            count,
-            true,
-        )?))
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
     }
 
     #[allow(non_snake_case)]
-    pub fn xmap_gE(
+    pub fn jump_targets_nmap_B(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        Ok(MotionOutputWrapper(self.wm.xmap_ge(
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_b(b, c, 1, false)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
-            count,
-            false,
-        )?))
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
     }
 
-    pub fn omap_ge(
+    pub fn jump_targets_nmap_ge(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        operator: &str,
-        count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        if operator == "d" {
-            Ok(MotionOutputWrapper(self.wm.omap_d_ge(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                true,
-            )?))
-        } else {
-            Ok(MotionOutputWrapper(self.wm.omap_ge(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                true,
-            )?))
-        }
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_ge(b, c, 1, true)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
     }
 
     #[allow(non_snake_case)]
-    pub fn omap_gE(
+    pub fn jump_targets_nmap_gE(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        operator: &str,
-        count: u64,
-    ) -> PyResult<MotionOutputWrapper> {
-        if operator == "d" {
-            Ok(MotionOutputWrapper(self.wm.omap_d_ge(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                false,
-            )?))
-        } else {
-            Ok(MotionOutputWrapper(self.wm.omap_ge(
-                &BoundWrapper(buffer),
-                cursor_pos,
-                count,
-                false,
-            )?))
-        }
+        preview_limit: usize,
+        alphabet: &str,
+        max_label_len: usize,
+    ) -> PyResult<Vec<(String, (usize, usize))>> {
+        let positions = preview::preview(
+            |b, c| Ok(self.wm.nmap_ge(b, c, 1, false)?.new_cursor_pos),
+            &BoundWrapper(buffer),
+            cursor_pos,
+            preview_limit,
+        )?;
+        Ok(self.label_and_store_jump_targets(positions, alphabet, max_label_len))
     }
 
-    pub fn preview_nmap_w(
+    /// Pair each target `preview_nmap_w` would have highlighted with the
+    /// 1-based count of `w` presses that would land the cursor there, for
+    /// rendering `1`/`2`/`3` count-hint labels at successive targets.
+    pub fn count_hint_nmap_w(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         preview_limit: usize,
-    ) -> PyResult<Vec<(usize, usize)>> {
-        preview::preview(
+    ) -> PyResult<Vec<((usize, usize), usize)>> {
+        preview::preview_with_count(
             |b, c| Ok(self.wm.nmap_w(b, c, 1, true)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
@@ -1094,13 +2662,13 @@ impl LazyWordMotionWrapper {
     }
 
     #[allow(non_snake_case)]
-    pub fn preview_nmap_W(
+    pub fn count_hint_nmap_W(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         preview_limit: usize,
-    ) -> PyResult<Vec<(usize, usize)>> {
-        preview::preview(
+    ) -> PyResult<Vec<((usize, usize), usize)>> {
+        preview::preview_with_count(
             |b, c| Ok(self.wm.nmap_w(b, c, 1, false)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
@@ -1108,13 +2676,13 @@ impl LazyWordMotionWrapper {
         )
     }
 
-    pub fn preview_nmap_e(
+    pub fn count_hint_nmap_e(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         preview_limit: usize,
-    ) -> PyResult<Vec<(usize, usize)>> {
-        preview::preview(
+    ) -> PyResult<Vec<((usize, usize), usize)>> {
+        preview::preview_with_count(
             |b, c| Ok(self.wm.nmap_e(b, c, 1, true)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
@@ -1123,13 +2691,13 @@ impl LazyWordMotionWrapper {
     }
 
     #[allow(non_snake_case)]
-    pub fn preview_nmap_E(
+    pub fn count_hint_nmap_E(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
         preview_limit: usize,
-    ) -> PyResult<Vec<(usize, usize)>> {
-        preview::preview(
+    ) -> PyResult<Vec<((usize, usize), usize)>> {
+        preview::preview_with_count(
             |b, c| Ok(self.wm.nmap_e(b, c, 1, false)?.new_cursor_pos),
             &BoundWrapper(buffer),
             cursor_pos,
@@ -1137,61 +2705,119 @@ impl LazyWordMotionWrapper {
         )
     }
 
-    pub fn preview_nmap_b(
+    pub fn omap_iw(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        preview_limit: usize,
-    ) -> PyResult<Vec<(usize, usize)>> {
-        preview::preview(
-            |b, c| Ok(self.wm.nmap_b(b, c, 1, true)?.new_cursor_pos),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.omap_iw(
             &BoundWrapper(buffer),
             cursor_pos,
-            preview_limit,
-        )
+            count,
+            true,
+        )?))
     }
 
     #[allow(non_snake_case)]
-    pub fn preview_nmap_B(
+    pub fn omap_iW(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        preview_limit: usize,
-    ) -> PyResult<Vec<(usize, usize)>> {
-        preview::preview(
-            |b, c| Ok(self.wm.nmap_b(b, c, 1, false)?.new_cursor_pos),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.omap_iw(
             &BoundWrapper(buffer),
             cursor_pos,
-            preview_limit,
-        )
+            count,
+            false,
+        )?))
     }
 
-    pub fn preview_nmap_ge(
+    pub fn omap_aw(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        preview_limit: usize,
-    ) -> PyResult<Vec<(usize, usize)>> {
-        preview::preview(
-            |b, c| Ok(self.wm.nmap_ge(b, c, 1, true)?.new_cursor_pos),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.omap_aw(
             &BoundWrapper(buffer),
             cursor_pos,
-            preview_limit,
-        )
+            count,
+            true,
+        )?))
     }
 
     #[allow(non_snake_case)]
-    pub fn preview_nmap_gE(
+    pub fn omap_aW(
         &self,
         buffer: &Bound<'_, PyAny>,
         cursor_pos: (usize, usize),
-        preview_limit: usize,
-    ) -> PyResult<Vec<(usize, usize)>> {
-        preview::preview(
-            |b, c| Ok(self.wm.nmap_ge(b, c, 1, false)?.new_cursor_pos),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.omap_aw(
             &BoundWrapper(buffer),
             cursor_pos,
-            preview_limit,
-        )
+            count,
+            false,
+        )?))
+    }
+
+    pub fn xmap_iw(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.xmap_iw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn xmap_iW(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.xmap_iw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
+    }
+
+    pub fn xmap_aw(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.xmap_aw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            true,
+        )?))
+    }
+
+    #[allow(non_snake_case)]
+    pub fn xmap_aW(
+        &self,
+        buffer: &Bound<'_, PyAny>,
+        cursor_pos: (usize, usize),
+        count: u64,
+    ) -> PyResult<TextObjectOutputWrapper> {
+        Ok(TextObjectOutputWrapper(self.wm.xmap_aw(
+            &BoundWrapper(buffer),
+            cursor_pos,
+            count,
+            false,
+        )?))
     }
 }